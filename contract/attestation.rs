@@ -0,0 +1,20 @@
+use ink::env::call::{build_call, ExecutionInput, Selector};
+use ink::env::DefaultEnvironment;
+use ink::primitives::{AccountId, Hash};
+
+/// Call `Attestation::verify` on `attester`, returning `false` if the call fails
+///
+/// The attester answers whether it has issued the claim identified by
+/// `claim_hash` (e.g. "over 18") to `subject`, and hasn't revoked it.
+pub fn verify(attester: AccountId, subject: AccountId, claim_hash: Hash) -> bool {
+    let result = build_call::<DefaultEnvironment>()
+        .call(attester)
+        .exec_input(
+            ExecutionInput::new(Selector::new(ink::selector_bytes!("Attestation::verify")))
+                .push_arg(subject)
+                .push_arg(claim_hash),
+        )
+        .returns::<bool>()
+        .try_invoke();
+    matches!(result, Ok(Ok(true)))
+}
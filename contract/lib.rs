@@ -1,5 +1,13 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+pub mod attestation;
+pub mod psp22;
+pub mod nfts;
+pub mod oracle;
+pub mod personhood;
+pub mod psp34;
+pub mod ticketdot_core;
+
 /// # TicketDot Smart Contract
 /// 
 /// A decentralized ticket booking platform where:
@@ -8,19 +16,72 @@
 /// - Tickets can be transferred between users
 /// 
 /// Built with Ink! for Polkadot/Substrate chains
-#[ink::contract]
-mod ticketdot {
+#[ink::contract(env = crate::nfts::TicketDotEnvironment)]
+pub mod ticketdot {
     use ink::prelude::string::String;
     use ink::prelude::vec::Vec;
     use ink::prelude::collections::BTreeSet;
     use ink::storage::Mapping;
+    use ink::storage::traits::StorageKey;
+    use crate::attestation;
+    use crate::oracle;
+    use crate::personhood;
+    use crate::psp22;
+    use crate::psp34::{self, Id, PSP34Error, PSP34Metadata, PSP34};
+    use crate::ticketdot_core::TicketDotCore;
 
     /// Validation constants for security
-    const MAX_TICKETS_PER_EVENT: u32 = 1_000_000;
-    const MIN_TICKET_PRICE: Balance = 1;
-    const MAX_EVENT_NAME_LENGTH: usize = 200;
-    const MAX_METADATA_CID_LENGTH: usize = 1000;
+    const DEFAULT_MAX_TICKETS_PER_EVENT: u32 = 1_000_000;
+    const DEFAULT_MIN_TICKET_PRICE: Balance = 1;
+    const DEFAULT_MAX_EVENT_NAME_LENGTH: u32 = 200;
+    const DEFAULT_MAX_METADATA_CID_LENGTH: u32 = 1000;
+    const MAX_TICKETS_PER_EVENT_LIMIT: u32 = 10_000_000;
+    const MAX_EVENT_NAME_LENGTH_LIMIT: u32 = 1000;
+    const MAX_METADATA_CID_LENGTH_LIMIT: u32 = 4096;
     const MAX_TICKETS_PER_USER: u32 = 1000;
+    const MAX_SUBSCRIPTIONS_PER_USER: u32 = 100;
+    const MAX_PAGE_SIZE: u32 = 100;
+    const MIN_RATING: u8 = 1;
+    const MAX_RATING: u8 = 5;
+    const MAX_REFERRAL_CODE_LENGTH: usize = 32;
+    const MAX_TIERS_PER_EVENT: usize = 10;
+    const MAX_SEATS_PER_EVENT: u32 = 2000;
+    const DEFAULT_MAX_TICKETS_PER_PURCHASE: u32 = 10;
+    const BPS_DENOMINATOR: Balance = 10_000;
+    const MAX_ROYALTY_BPS: u16 = 5_000;
+    const MAX_PLATFORM_FEE_BPS: u16 = 1_000;
+    const MAX_REFERRAL_REWARD_BPS: u16 = 2_000;
+    const MAX_BATCH_SIZE: usize = 100;
+    const MAX_PROOF_LENGTH: usize = 32;
+    const MAX_PRICE_PHASES: usize = 10;
+    const MAX_REFUND_RULES: usize = 10;
+    const MAX_PAYOUT_TRANCHES: usize = 10;
+    const MAX_PAYEES: usize = 10;
+    const MAX_DISPLAY_NAME_LENGTH: usize = 100;
+    const MAX_TICKET_HISTORY: u32 = 10;
+    const MAX_COLLECTION_SYMBOL_LENGTH: usize = 16;
+    const MAX_TAGS_PER_EVENT: usize = 5;
+    const MAX_TAG_LENGTH: usize = 32;
+    const MAX_PASS_EVENTS: usize = 100;
+    const MAX_SERIES_SESSIONS: usize = 52;
+    const MAX_ADDONS_PER_EVENT: u32 = 20;
+    const MAX_POLLS_PER_EVENT: u32 = 20;
+    const MAX_POLL_OPTIONS: usize = 10;
+    const WAITLIST_OFFER_DURATION: u64 = 60 * 60 * 1000; // 1 hour in milliseconds
+    const RESCHEDULE_REFUND_WINDOW: u64 = 7 * 24 * 60 * 60 * 1000; // 7 days in milliseconds
+    const REFUND_CLAIM_PERIOD: u64 = 90 * 24 * 60 * 60 * 1000; // 90 days in milliseconds
+    const DEFAULT_DISPUTE_WINDOW: u64 = 3 * 24 * 60 * 60 * 1000; // 3 days in milliseconds
+    const DEFAULT_FINALIZE_GRACE_PERIOD: u64 = 24 * 60 * 60 * 1000; // 1 day in milliseconds
+    const DEFAULT_MAX_ORACLE_STALENESS: u64 = 10 * 60 * 1000; // 10 minutes in milliseconds
+    const RAFFLE_DRAW_PERIOD: u64 = 3 * 24 * 60 * 60 * 1000; // 3 days in milliseconds
+    const DEFAULT_POINTS_PER_PURCHASE: u64 = 10;
+    const DEFAULT_POINTS_PER_CHECKIN: u64 = 5;
+
+    /// Storage layout version written by this code; bump it together with
+    /// a migration step in `migrate` whenever stored types change
+    const STORAGE_VERSION: u32 = 2;
+    const MAX_TICKETS_PER_PURCHASE_LIMIT: u32 = 50;
+    const MAX_USES_PER_TICKET: u32 = 100;
 
     /// Represents an event created by an organizer
     #[derive(Debug, Clone, scale::Encode, scale::Decode)]
@@ -34,870 +95,14989 @@ mod ticketdot {
         pub available_tickets: u32,
         pub timestamp: u64,
         pub metadata_cid: String, // IPFS CID for event details
-        pub active: bool,
-        pub cancelled: bool,
-        pub completed: bool,
+        pub status: EventStatus,
+        pub referrals_enabled: bool, // Organizer opt-in for referral rewards
+        pub tiers: Vec<TicketTier>, // Capacity reserved out of total_tickets
+        pub schedule: EventSchedule,
+        pub royalty_bps: u16, // Organizer royalty on resales, in basis points
+        pub max_resale_multiplier_bps: u32, // Resale cap vs face value, in basis points (0 = no cap)
+        pub transferable: bool, // False for soulbound tickets locked to the buyer
+        pub payment_token: Option<AccountId>, // PSP22 payment asset, None for native token
+        pub presale_end: u64, // Allowlist-only sales until this timestamp (0 = no presale)
+        pub price_phases: Vec<PricePhase>, // Base price overrides, ordered by start time
+        pub dutch_auction: Option<DutchAuction>, // Decaying base price, replaces `price`
+        pub referral_reward_bps: u16, // Share of each referred sale paid to the referrer
+        pub comp_quota: u32, // Maximum complimentary tickets the organizer can issue
+        pub comps_issued: u32,
+        pub uses_per_ticket: u32, // Check-ins allowed per general admission ticket
+        pub refund_policy: Vec<RefundRule>, // Cancellation refunds, latest deadline last (empty = full refund)
+        pub frozen: bool, // Frozen by a moderator: no sales, check-ins, or withdrawals
+        pub payout_schedule: Vec<PayoutTranche>, // Vesting of earnings, earliest first (empty = lump sum)
+        pub token_gate: Option<TokenGate>, // Holding buyers need, checked on the token contract
+        pub category: u8, // Genre for browsing (0 = uncategorized); meaning is up to the dApp
+        pub tags: Vec<String>, // Free-form discovery tags
+        pub days: Vec<u64>, // Start of each day, ascending (empty = single-day event)
+        pub bonding_curve: Option<BondingCurve>, // Base price rises with tickets sold
     }
 
-    /// Represents a ticket NFT
-    #[derive(Debug, Clone, scale::Encode, scale::Decode)]
+    /// Lifecycle of an event; `EventStatus::can_become` lists the legal moves
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
-    pub struct Ticket {
-        pub id: u64,
-        pub event_id: u64,
-        pub owner: AccountId,
-        pub purchase_time: u64,
-        pub is_used: bool, // True if ticket has been scanned/used
-        pub is_refunded: bool, // True if ticket has been refunded
-    }
-
-    /// Custom error types
-    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-    pub enum Error {
-        /// Event does not exist
-        EventNotFound,
-        /// No tickets available
-        SoldOut,
-        /// Insufficient payment
-        InsufficientPayment,
-        /// Ticket does not exist
-        TicketNotFound,
-        /// Caller is not the ticket owner
-        NotTicketOwner,
-        /// Event is not active
-        EventNotActive,
-        /// Ticket already used
-        TicketAlreadyUsed,
-        /// Transfer failed
-        TransferFailed,
-        /// Caller is not the event organizer
-        NotOrganizer,
-        /// Event already cancelled
-        EventCancelled,
-        /// Event already completed
-        EventCompleted,
-        /// Ticket already refunded
-        TicketAlreadyRefunded,
-        /// Invalid input parameters
-        InvalidInput,
-        /// User has too many tickets
-        TooManyTickets,
-        /// Contract balance insufficient
-        InsufficientBalance,
-        /// Event not completed yet
-        EventNotCompleted,
+    pub enum EventStatus {
+        /// Created unpublished; nothing is sold until `publish_event`
+        Draft,
+        /// Published and selling, within the sale window
+        OnSale,
+        /// Sales halted by the organizer; nothing else is affected
+        Paused,
+        /// Cancelled; holders can claim refunds
+        Cancelled,
+        /// Held and closed; earnings unlock after the dispute window
+        Completed,
     }
 
-    /// Main contract storage
-    #[ink(storage)]
-    pub struct TicketDot {
-        /// Counter for event IDs
-        event_counter: u64,
-        /// Counter for ticket IDs
-        ticket_counter: u64,
-        /// Mapping from event ID to Event
-        events: Mapping<u64, Event>,
-        /// Mapping from ticket ID to Ticket
-        tickets: Mapping<u64, Ticket>,
-        /// Mapping from owner to their ticket IDs (using BTreeSet for efficient operations)
-        owner_tickets: Mapping<AccountId, BTreeSet<u64>>,
-        /// Contract admin (for future governance)
-        admin: AccountId,
+    impl EventStatus {
+        /// Whether an event in this status may move to `to`
+        fn can_become(self, to: EventStatus) -> bool {
+            use EventStatus::*;
+            matches!(
+                (self, to),
+                (Draft, OnSale)
+                    | (OnSale, Paused)
+                    | (Paused, OnSale)
+                    | (Draft | OnSale | Paused, Cancelled)
+                    | (OnSale | Paused, Completed)
+            )
+        }
     }
 
-    /// Events emitted by the contract
-    #[ink(event)]
-    pub struct EventCreated {
-        #[ink(topic)]
-        event_id: u64,
-        #[ink(topic)]
-        organizer: AccountId,
+    /// Layout of `Event` in storage version 1, read back by `migrate`
+    #[derive(scale::Encode, scale::Decode)]
+    struct EventV1 {
+        id: u64,
         name: String,
+        organizer: AccountId,
         price: Balance,
         total_tickets: u32,
+        available_tickets: u32,
+        timestamp: u64,
+        metadata_cid: String,
+        active: bool,
+        cancelled: bool,
+        completed: bool,
     }
 
-    #[ink(event)]
-    pub struct TicketPurchased {
-        #[ink(topic)]
-        ticket_id: u64,
-        #[ink(topic)]
-        event_id: u64,
-        #[ink(topic)]
-        buyer: AccountId,
-        price: Balance,
-    }
-
-    #[ink(event)]
-    pub struct TicketTransferred {
-        #[ink(topic)]
-        ticket_id: u64,
-        #[ink(topic)]
-        from: AccountId,
-        #[ink(topic)]
-        to: AccountId,
+    impl From<EventV1> for Event {
+        fn from(legacy: EventV1) -> Self {
+            let status = if legacy.cancelled {
+                EventStatus::Cancelled
+            } else if legacy.completed {
+                EventStatus::Completed
+            } else if legacy.active {
+                EventStatus::OnSale
+            } else {
+                EventStatus::Paused
+            };
+            Event {
+                id: legacy.id,
+                name: legacy.name,
+                organizer: legacy.organizer,
+                price: legacy.price,
+                total_tickets: legacy.total_tickets,
+                available_tickets: legacy.available_tickets,
+                timestamp: legacy.timestamp,
+                metadata_cid: legacy.metadata_cid,
+                status,
+                referrals_enabled: false,
+                tiers: Vec::new(),
+                schedule: EventSchedule::default(),
+                royalty_bps: 0,
+                max_resale_multiplier_bps: 0,
+                transferable: true,
+                payment_token: None,
+                presale_end: 0,
+                price_phases: Vec::new(),
+                dutch_auction: None,
+                referral_reward_bps: 0,
+                comp_quota: 0,
+                comps_issued: 0,
+                uses_per_ticket: 1,
+                refund_policy: Vec::new(),
+                frozen: false,
+                payout_schedule: Vec::new(),
+                token_gate: None,
+                category: 0,
+                tags: Vec::new(),
+                days: Vec::new(),
+                bonding_curve: None,
+            }
+        }
     }
 
-    #[ink(event)]
-    pub struct TicketUsed {
-        #[ink(topic)]
-        ticket_id: u64,
-        #[ink(topic)]
+    /// Layout of `Ticket` in storage version 1, read back by `migrate`
+    #[derive(scale::Encode, scale::Decode)]
+    struct TicketV1 {
+        id: u64,
         event_id: u64,
+        owner: AccountId,
+        purchase_time: u64,
+        is_used: bool,
+        is_refunded: bool,
     }
 
-    #[ink(event)]
-    pub struct EventCancelled {
-        #[ink(topic)]
-        event_id: u64,
-        #[ink(topic)]
-        organizer: AccountId,
+    impl TicketV1 {
+        /// The ticket in the current layout; version 1 sold single-use
+        /// general admission tickets at the event price
+        fn upgrade(self, price: Balance) -> Ticket {
+            Ticket {
+                id: self.id,
+                event_id: self.event_id,
+                owner: self.owner,
+                purchase_time: self.purchase_time,
+                uses_remaining: u32::from(!self.is_used),
+                times_used: u32::from(self.is_used),
+                is_refunded: self.is_refunded,
+                tier_id: None,
+                price_paid: price,
+                seat_id: None,
+                fee_paid: 0,
+                is_comp: false,
+            }
+        }
     }
 
-    #[ink(event)]
-    pub struct EventCompleted {
-        #[ink(topic)]
-        event_id: u64,
+    /// The parts of an event a ticket list needs to render
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct EventSummary {
+        pub id: u64,
+        pub name: String,
+        pub organizer: AccountId,
+        pub metadata_cid: String,
+        pub schedule: EventSchedule,
+        pub status: EventStatus,
+        pub payment_token: Option<AccountId>,
     }
 
-    #[ink(event)]
-    pub struct TicketRefunded {
-        #[ink(topic)]
-        ticket_id: u64,
-        #[ink(topic)]
-        owner: AccountId,
-        amount: Balance,
-    }
+    impl Event {
+        fn summary(&self) -> EventSummary {
+            EventSummary {
+                id: self.id,
+                name: self.name.clone(),
+                organizer: self.organizer,
+                metadata_cid: self.metadata_cid.clone(),
+                schedule: self.schedule,
+                status: self.status,
+                payment_token: self.payment_token,
+            }
+        }
 
-    #[ink(event)]
-    pub struct TicketCancelled {
-        #[ink(topic)]
-        ticket_id: u64,
-        #[ink(topic)]
-        event_id: u64,
-        #[ink(topic)]
-        owner: AccountId,
-        refund_amount: Balance,
-    }
+        /// Check that the event may move to `to`, without moving it
+        fn check_transition(&self, to: EventStatus) -> Result<(), Error> {
+            if self.status.can_become(to) {
+                return Ok(());
+            }
+            Err(match self.status {
+                EventStatus::Cancelled => Error::EventCancelled,
+                EventStatus::Completed => Error::EventCompleted,
+                _ => Error::InvalidStatusTransition,
+            })
+        }
 
-    impl Default for TicketDot {
-        fn default() -> Self {
-            Self::new()
+        /// Move the event to `to`, if that's a legal transition from its status
+        fn transition(&mut self, to: EventStatus) -> Result<(), Error> {
+            self.check_transition(to)?;
+            self.status = to;
+            Ok(())
         }
-    }
 
-    impl TicketDot {
-        /// Constructor - initializes the contract
-        #[ink(constructor)]
-        pub fn new() -> Self {
-            Self {
-                event_counter: 0,
-                ticket_counter: 0,
-                events: Mapping::default(),
-                tickets: Mapping::default(),
-                owner_tickets: Mapping::default(),
-                admin: Self::env().caller(),
-            }
+        /// Whether the event has been cancelled
+        fn is_cancelled(&self) -> bool {
+            self.status == EventStatus::Cancelled
         }
 
-        /// Create a new event
-        /// 
-        /// # Security
-        /// - Validates all input parameters to prevent resource exhaustion
-        /// - Enforces maximum tickets per event to prevent DoS
-        /// - Validates minimum ticket price to prevent spam
-        /// - Validates string lengths to prevent storage bloat
-        /// 
-        /// # Arguments
-        /// * `name` - Event name (e.g., "Polkadot Conference 2025")
-        /// * `price` - Ticket price in native token (e.g., 1000000000000 for 1 SBY)
-        /// * `total_tickets` - Total number of tickets available
-        /// * `metadata_cid` - IPFS CID containing event metadata (description, image, venue, etc.)
-        /// 
-        /// # Returns
-        /// - `Ok(event_id)` - The ID of the newly created event
-        /// - `Err(Error::InvalidInput)` - Input validation failed
-        #[ink(message)]
-        pub fn create_event(
-            &mut self,
-            name: String,
-            price: Balance,
-            total_tickets: u32,
-            metadata_cid: String,
-        ) -> Result<u64, Error> {
-            // Validate input parameters to prevent resource exhaustion and storage bloat
-            if name.is_empty() || name.len() > MAX_EVENT_NAME_LENGTH {
-                return Err(Error::InvalidInput);
-            }
-            if metadata_cid.is_empty() || metadata_cid.len() > MAX_METADATA_CID_LENGTH {
-                return Err(Error::InvalidInput);
-            }
-            if total_tickets == 0 || total_tickets > MAX_TICKETS_PER_EVENT {
-                return Err(Error::InvalidInput);
+        /// Whether the event has been completed
+        fn is_completed(&self) -> bool {
+            self.status == EventStatus::Completed
+        }
+
+        /// Whether the event is published and neither cancelled nor completed
+        fn is_live(&self) -> bool {
+            matches!(self.status, EventStatus::OnSale | EventStatus::Paused)
+        }
+
+        /// Index of the day of a multi-day event that `now` falls in
+        ///
+        /// Each day lasts until the next one starts; the last one lasts
+        /// until the event window closes.
+        fn day_index(&self, now: u64) -> Option<u32> {
+            self.days
+                .iter()
+                .rposition(|start| *start <= now)
+                .map(|index| index as u32)
+        }
+
+        /// Base price at `now` of the ticket `offset` sales after the next one
+        ///
+        /// Follows the latest phase that has started (or `price`); `offset`
+        /// only matters on a bonding curve, where it prices each ticket of
+        /// a multi-ticket purchase.
+        fn nth_price_at(&self, now: u64, offset: u32) -> Balance {
+            if let Some(auction) = &self.dutch_auction {
+                return auction.price_at(now);
             }
-            if price < MIN_TICKET_PRICE {
-                return Err(Error::InvalidInput);
+            let price = self
+                .price_phases
+                .iter()
+                .rev()
+                .find(|phase| phase.start <= now)
+                .map_or(self.price, |phase| phase.price);
+            match &self.bonding_curve {
+                Some(curve) => {
+                    let sold = self.total_tickets.saturating_sub(self.available_tickets);
+                    price.saturating_add(curve.premium(sold.saturating_add(offset)))
+                }
+                None => price,
             }
+        }
+    }
 
-            let caller = self.env().caller();
-            let event_id = self.event_counter;
+    /// Share of the price refunded on cancellation while at least
+    /// `min_time_before_start` milliseconds remain before the event starts
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct RefundRule {
+        pub min_time_before_start: u64,
+        pub refund_bps: u16,
+    }
 
-            // Create new event
-            let event = Event {
-                id: event_id,
-                name: name.clone(),
-                organizer: caller,
-                price,
-                total_tickets,
-                available_tickets: total_tickets,
-                timestamp: self.env().block_timestamp(),
-                metadata_cid,
-                active: true,
-                cancelled: false,
-                completed: false,
-            };
+    /// Share of an event's earnings that vests `delay` milliseconds after
+    /// the earnings unlock (completion plus the dispute window)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PayoutTranche {
+        pub delay: u64,
+        pub share_bps: u16,
+    }
 
-            // Store event
-            self.events.insert(event_id, &event);
-            self.event_counter = self.event_counter.saturating_add(1);
+    /// Share of an event's earnings owed to a venue, artist, or promoter
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PayoutShare {
+        pub payee: AccountId,
+        pub share_bps: u16,
+    }
 
-            // Emit event
-            self.env().emit_event(EventCreated {
-                event_id,
-                organizer: caller,
-                name,
-                price,
-                total_tickets,
-            });
+    /// Base ticket price that applies from `start` until the next phase
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PricePhase {
+        pub start: u64,
+        pub price: Balance,
+    }
 
-            Ok(event_id)
-        }
+    /// Limits on how often an event's tickets can change hands, to blunt scalping
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct TransferLimits {
+        pub cooldown: u64, // Minimum time a holder keeps a ticket before passing it on (0 = none)
+        pub max_transfers: u32, // Lifetime transfers allowed per ticket (0 = unlimited)
+    }
 
-        /// Buy a ticket for an event
-        /// 
-        /// This function mints an NFT ticket and transfers it to the buyer.
-        /// Payment is handled via the payable mechanism.
-        /// Accepts exact payment amount only to prevent confusion.
-        /// 
-        /// # Security
-        /// - Requires exact payment amount to prevent overpayment
-        /// - Payment is transferred immediately to organizer BEFORE state changes
-        /// - Ticket is minted as NFT owned by buyer
-        /// - Enforces maximum tickets per user to prevent DoS
-        /// 
-        /// # Arguments
-        /// * `event_id` - ID of the event to buy ticket for
-        /// 
-        /// # Returns
-        /// - `Ok(ticket_id)` - The ID of the newly minted ticket
-        /// - `Err(Error::EventNotFound)` - Event doesn't exist
-        /// - `Err(Error::InsufficientPayment)` - Payment != ticket price
-        /// - `Err(Error::SoldOut)` - No tickets available
-        /// - `Err(Error::EventNotActive)` - Event is not active
-        /// - `Err(Error::TransferFailed)` - Payment transfer failed
-        /// - `Err(Error::TooManyTickets)` - User has reached ticket limit
-        #[ink(message, payable)]
-        pub fn buy_ticket(&mut self, event_id: u64) -> Result<u64, Error> {
-            let caller = self.env().caller();
-            let payment = self.env().transferred_value();
+    /// Reference to a verifiable credential held by an attendee
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Credential {
+        pub attester: AccountId, // Attestation contract that issued the claim
+        pub claim_hash: Hash, // Claim the attester vouches for, e.g. "over 18"
+    }
 
-            // Get event or return error
-            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+    /// Platform-wide caps against bots buying the moment sales open
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct RateLimits {
+        pub max_mints_per_block: u32, // Paid tickets sold per event per block (0 = unlimited)
+        pub purchase_cooldown: u32, // Blocks between an account's purchases (0 = none)
+    }
 
-            // Validate event is active
-            if !event.active {
-                return Err(Error::EventNotActive);
-            }
+    /// Holding required to buy tickets for a token-gated event
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum TokenGate {
+        /// At least one NFT from a PSP34 collection
+        Psp34 { collection: AccountId },
+        /// At least `min_balance` of a PSP22 token
+        Psp22 { token: AccountId, min_balance: Balance },
+    }
 
-            // Check if event is cancelled
-            if event.cancelled {
-                return Err(Error::EventCancelled);
+    impl TokenGate {
+        /// Whether `account` meets the gate, asking the token contract
+        fn is_met_by(&self, account: AccountId) -> bool {
+            match *self {
+                TokenGate::Psp34 { collection } => psp34::balance_of(collection, account) > 0,
+                TokenGate::Psp22 { token, min_balance } => {
+                    psp22::balance_of(token, account) >= min_balance
+                }
             }
+        }
+    }
 
-            // Check if event is completed
-            if event.completed {
-                return Err(Error::EventCompleted);
-            }
+    /// Descending-price sale: `start_price` decays to `floor_price` over `duration`
+    ///
+    /// With `step` of 0 the price falls linearly; otherwise it drops once
+    /// every `step` milliseconds.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct DutchAuction {
+        pub start_price: Balance,
+        pub floor_price: Balance,
+        pub start: u64,
+        pub duration: u64,
+        pub step: u64,
+    }
 
-            // Check if tickets are available
-            if event.available_tickets == 0 {
-                return Err(Error::SoldOut);
-            }
+    impl DutchAuction {
+        fn is_valid(&self, min_price: Balance) -> bool {
+            self.floor_price >= min_price
+                && self.start_price > self.floor_price
+                && self.duration > 0
+                && self.step <= self.duration
+        }
 
-            // Validate exact payment amount to prevent confusion
-            // User must pay exactly the ticket price
-            if payment != event.price {
-                return Err(Error::InsufficientPayment);
+        fn price_at(&self, now: u64) -> Balance {
+            let mut elapsed = now.saturating_sub(self.start).min(self.duration);
+            if self.step > 0 {
+                elapsed -= elapsed % self.step;
             }
+            let decay = (self.start_price - self.floor_price)
+                .saturating_mul(elapsed as Balance)
+                / self.duration as Balance;
+            self.start_price.saturating_sub(decay)
+        }
+    }
+
+    /// Rising-price sale: each ticket costs more than the last
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum BondingCurve {
+        /// Price rises by `increment` with every ticket sold
+        Linear { increment: Balance },
+        /// Price rises by `increment` after every `step` tickets sold
+        Step { step: u32, increment: Balance },
+    }
+
+    impl BondingCurve {
+        fn is_valid(&self) -> bool {
+            match *self {
+                BondingCurve::Linear { increment } => increment > 0,
+                BondingCurve::Step { step, increment } => step > 0 && increment > 0,
+            }
+        }
+
+        /// Amount added to the base price once `sold` tickets are sold
+        fn premium(&self, sold: u32) -> Balance {
+            match *self {
+                BondingCurve::Linear { increment } => increment.saturating_mul(sold as Balance),
+                BondingCurve::Step { step, increment } => {
+                    increment.saturating_mul((sold / step) as Balance)
+                }
+            }
+        }
+    }
+
+    /// Share of `amount` given in basis points (1/100 of a percent)
+    fn bps_share(amount: Balance, bps: u16) -> Balance {
+        amount.saturating_mul(bps as Balance) / BPS_DENOMINATOR
+    }
+
+    /// Merkle leaf for an allowlisted account: blake2-256 of its SCALE encoding
+    fn merkle_leaf(account: &AccountId) -> Hash {
+        let mut output = [0u8; 32];
+        ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(account, &mut output);
+        Hash::from(output)
+    }
+
+    /// Hash under which a promo code is registered: blake2-256 of its bytes
+    fn promo_code_hash(code: &str) -> Hash {
+        let mut output = [0u8; 32];
+        ink::env::hash_bytes::<ink::env::hash::Blake2x256>(code.as_bytes(), &mut output);
+        Hash::from(output)
+    }
+
+    /// Hash a private event is locked with: blake2-256 of its access key
+    fn access_key_hash(key: &[u8]) -> Hash {
+        let mut output = [0u8; 32];
+        ink::env::hash_bytes::<ink::env::hash::Blake2x256>(key, &mut output);
+        Hash::from(output)
+    }
+
+    /// Commitment to a commit-reveal purchase: blake2-256 of the SCALE-encoded
+    /// `(account, salt)`
+    fn purchase_commitment(account: &AccountId, salt: &Hash) -> Hash {
+        let mut output = [0u8; 32];
+        ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(&(account, salt), &mut output);
+        Hash::from(output)
+    }
+
+    /// Commitment to a raffle's draw seed: blake2-256 of the seed
+    fn raffle_seed_hash(seed: &Hash) -> Hash {
+        let mut output = [0u8; 32];
+        ink::env::hash_bytes::<ink::env::hash::Blake2x256>(seed.as_ref(), &mut output);
+        Hash::from(output)
+    }
+
+    /// Account controlled by an ECDSA key: blake2-256 of the compressed public key
+    fn ecdsa_account(public_key: &[u8; 33]) -> AccountId {
+        let mut output = [0u8; 32];
+        ink::env::hash_bytes::<ink::env::hash::Blake2x256>(public_key, &mut output);
+        AccountId::from(output)
+    }
+
+    /// Check a Merkle proof for `leaf` against `root`
+    ///
+    /// Pairs are hashed in sorted order, so proofs carry no left/right flags.
+    fn verify_merkle_proof(root: Hash, leaf: Hash, proof: &[Hash]) -> bool {
+        let mut computed = leaf;
+        for sibling in proof {
+            let (first, second) = if computed <= *sibling {
+                (computed, *sibling)
+            } else {
+                (*sibling, computed)
+            };
+            let mut input = [0u8; 64];
+            input[..32].copy_from_slice(first.as_ref());
+            input[32..].copy_from_slice(second.as_ref());
+            let mut output = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&input, &mut output);
+            computed = Hash::from(output);
+        }
+        computed == root
+    }
+
+    /// Sale and event windows, as block timestamps in milliseconds
+    ///
+    /// A value of 0 leaves that side of the window unbounded.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct EventSchedule {
+        pub sale_start: u64,
+        pub sale_end: u64,
+        pub event_start: u64,
+        pub event_end: u64,
+    }
+
+    impl EventSchedule {
+        fn is_valid(&self) -> bool {
+            let ordered = |start: u64, end: u64| start == 0 || end == 0 || start < end;
+            ordered(self.sale_start, self.sale_end) && ordered(self.event_start, self.event_end)
+        }
+
+        fn check_sale_window(&self, now: u64) -> Result<(), Error> {
+            if now < self.sale_start {
+                return Err(Error::SaleNotOpen {
+                    opens_at: self.sale_start,
+                });
+            }
+            if self.sale_end != 0 && now >= self.sale_end {
+                return Err(Error::SaleClosed);
+            }
+            Ok(())
+        }
+
+        /// Whether the event has an end time and it has passed
+        fn has_ended(&self, now: u64) -> bool {
+            self.event_end != 0 && now >= self.event_end
+        }
+
+        fn check_event_window(&self, now: u64) -> Result<(), Error> {
+            if now < self.event_start || (self.event_end != 0 && now >= self.event_end) {
+                return Err(Error::OutsideEventWindow);
+            }
+            Ok(())
+        }
+    }
+
+    /// Platform limits on new events, tunable by admins within hard bounds
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Config {
+        /// Most tickets a single event can have
+        pub max_tickets_per_event: u32,
+        /// Lowest ticket or tier price, never below 1
+        pub min_ticket_price: Balance,
+        /// Longest event or tier name, in bytes
+        pub max_event_name_length: u32,
+        /// Longest IPFS CID accepted anywhere, in bytes
+        pub max_metadata_cid_length: u32,
+    }
+
+    impl Default for Config {
+        fn default() -> Self {
+            Self {
+                max_tickets_per_event: DEFAULT_MAX_TICKETS_PER_EVENT,
+                min_ticket_price: DEFAULT_MIN_TICKET_PRICE,
+                max_event_name_length: DEFAULT_MAX_EVENT_NAME_LENGTH,
+                max_metadata_cid_length: DEFAULT_MAX_METADATA_CID_LENGTH,
+            }
+        }
+    }
+
+    impl Config {
+        fn is_valid(&self) -> bool {
+            (1..=MAX_TICKETS_PER_EVENT_LIMIT).contains(&self.max_tickets_per_event)
+                && self.min_ticket_price >= 1
+                && (1..=MAX_EVENT_NAME_LENGTH_LIMIT).contains(&self.max_event_name_length)
+                && (1..=MAX_METADATA_CID_LENGTH_LIMIT).contains(&self.max_metadata_cid_length)
+        }
+    }
+
+    /// Optional settings for `create_event_with_options`
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct EventOptions {
+        pub schedule: EventSchedule,
+        /// Organizer royalty on resales, in basis points
+        pub royalty_bps: u16,
+        /// Resale price cap relative to face value, in basis points (0 = no cap)
+        pub max_resale_multiplier_bps: u32,
+        /// Whether holders may transfer or resell tickets (false = soulbound)
+        pub transferable: bool,
+        /// PSP22 token used for payments instead of the native token
+        pub payment_token: Option<AccountId>,
+        /// Until this timestamp only allowlisted accounts can buy (0 = no presale)
+        pub presale_end: u64,
+        /// Early-bird/last-minute prices replacing `price` from each start time
+        pub price_phases: Vec<PricePhase>,
+        /// Sell at a decaying price instead of `price` (excludes price phases)
+        pub dutch_auction: Option<DutchAuction>,
+        /// Allocate tickets by raffle, with entries open until this timestamp
+        /// (0 = first come, first served)
+        pub raffle_end: u64,
+        /// Hash of the secret seed the organizer reveals to draw the raffle
+        /// or commit-reveal lottery (see `draw_raffle`); required for both
+        pub raffle_seed_hash: Option<Hash>,
+        /// Check-ins allowed per ticket, for re-entry or multi-day passes
+        pub uses_per_ticket: u32,
+        /// Cancellation refund tiers, e.g. 100% until 7 days before the
+        /// event starts, then 50% until 24h before, then nothing. Needs an
+        /// event start; empty means cancellations are always fully refunded.
+        pub refund_policy: Vec<RefundRule>,
+        /// Release earnings in tranches, e.g. 50% at unlock and 50% 14 days
+        /// later. Shares must add up to 100%; empty means a lump sum.
+        pub payout_schedule: Vec<PayoutTranche>,
+        /// Only sell to holders of an NFT collection or a minimum token balance
+        pub token_gate: Option<TokenGate>,
+        /// Genre to list the event under in `get_events_by_category`
+        pub category: u8,
+        /// Up to `MAX_TAGS_PER_EVENT` distinct tags to list the event under
+        /// in `get_events_by_tag`
+        pub tags: Vec<String>,
+        /// Start of each day of a multi-day event, in ascending order.
+        /// Tickets can then be checked in once per day, and `uses_per_ticket`
+        /// is set to the number of days.
+        pub days: Vec<u64>,
+        /// Raise the base price as tickets sell (excludes Dutch auctions and raffles)
+        pub bonding_curve: Option<BondingCurve>,
+        /// Create the event as a draft, held back from sale until `publish_event`
+        pub draft: bool,
+        /// Holding period and lifetime transfer cap for tickets, enforced on
+        /// transfers and resales alike
+        pub transfer_limits: TransferLimits,
+        /// Price the event in USD cents, converted to the native token at
+        /// purchase time through the price oracle. Replaces `price` for
+        /// general admission; needs native payment and a fixed price.
+        pub usd_price_cents: Option<u64>,
+        /// Only sell to accounts attested as human by the personhood
+        /// registry, capping each person at this many tickets across all
+        /// of their wallets (0 = no attestation needed)
+        pub tickets_per_person: u32,
+        /// Only sell to, and admit, attendees holding a credential from this
+        /// attestation contract, e.g. for 18+ events
+        pub credential_attester: Option<AccountId>,
+        /// Make the event private, selling only to buyers who know the
+        /// access key hashing to this (see `buy_ticket_private`)
+        pub access_hash: Option<Hash>,
+        /// Keep a private event in `get_active_events`; private events are
+        /// unlisted otherwise
+        pub listed: bool,
+        /// Sell through sealed commitments revealed in a second phase, so
+        /// bots watching pending transactions gain nothing (excludes raffles
+        /// and dynamic prices)
+        pub commit_reveal: Option<CommitReveal>,
+    }
+
+    impl Default for EventOptions {
+        fn default() -> Self {
+            Self {
+                schedule: EventSchedule::default(),
+                royalty_bps: 0,
+                max_resale_multiplier_bps: 0,
+                transferable: true,
+                payment_token: None,
+                presale_end: 0,
+                price_phases: Vec::new(),
+                dutch_auction: None,
+                raffle_end: 0,
+                raffle_seed_hash: None,
+                uses_per_ticket: 1,
+                refund_policy: Vec::new(),
+                payout_schedule: Vec::new(),
+                token_gate: None,
+                category: 0,
+                tags: Vec::new(),
+                days: Vec::new(),
+                bonding_curve: None,
+                draft: false,
+                transfer_limits: TransferLimits::default(),
+                usd_price_cents: None,
+                tickets_per_person: 0,
+                credential_attester: None,
+                access_hash: None,
+                listed: false,
+                commit_reveal: None,
+            }
+        }
+    }
+
+    /// Represents a ticket NFT
+    #[derive(Debug, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Ticket {
+        pub id: u64,
+        pub event_id: u64,
+        pub owner: AccountId,
+        pub purchase_time: u64,
+        pub uses_remaining: u32, // Check-ins left; 0 once the ticket is fully used
+        pub times_used: u32, // Check-ins so far; used tickets can't be resold or cancelled
+        pub is_refunded: bool, // True if ticket has been refunded
+        pub tier_id: Option<u32>, // None for general admission
+        pub price_paid: Balance, // Amount refunded if the ticket is cancelled
+        pub seat_id: Option<u32>, // Reserved seat, for seated events
+        pub fee_paid: Balance, // Non-refundable platform fee taken from price_paid
+        pub is_comp: bool, // Complimentary ticket issued by the organizer, never paid for
+    }
+
+    /// A ticket class (e.g. VIP, General, Early-bird) with its own price and capacity
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct TicketTier {
+        pub id: u32,
+        pub name: String,
+        pub price: Balance,
+        pub capacity: u32,
+        pub available: u32,
+        pub metadata_cid: String, // IPFS CID for tier details
+        pub uses_per_ticket: u32, // Check-ins allowed per ticket (e.g. days of a pass)
+    }
+
+    /// An extra sold alongside an event's tickets (parking, merch, meals)
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct AddOn {
+        pub id: u32,
+        pub name: String,
+        pub price: Balance, // In the event's payment asset
+        pub supply: u32, // 0 = unlimited
+        pub sold: u32,
+    }
+
+    /// A question the organizer puts to an event's ticket holders
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Poll {
+        pub id: u32,
+        pub question: String,
+        pub options: Vec<String>,
+        pub votes: Vec<u32>, // Tally per option, in the order of `options`
+        pub closes_at: u64, // Voting ends at this timestamp
+    }
+
+    /// Loyalty point terms an organizer sets for an event
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct LoyaltyTerms {
+        pub min_points: u64, // Points a buyer must hold to buy (0 = open to all)
+        pub redeem_points: u64, // Points spent by `buy_ticket_with_points` (0 = no discount)
+        pub discount_bps: u16, // Discount off the base price when redeeming points
+    }
+
+    /// Seat layout of a reserved-seating event
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum SeatLayout {
+        /// Seats numbered `row * seats_per_row + seat`, starting at 0
+        Grid { rows: u32, seats_per_row: u32 },
+        /// Explicit list of seat IDs
+        List(Vec<u32>),
+    }
+
+    impl SeatLayout {
+        fn seat_count(&self) -> u32 {
+            match self {
+                SeatLayout::Grid { rows, seats_per_row } => rows.saturating_mul(*seats_per_row),
+                SeatLayout::List(seats) => seats.len() as u32,
+            }
+        }
+
+        fn contains(&self, seat_id: u32) -> bool {
+            match self {
+                SeatLayout::Grid { .. } => seat_id < self.seat_count(),
+                SeatLayout::List(seats) => seats.contains(&seat_id),
+            }
+        }
+
+        fn seats(&self) -> Vec<u32> {
+            match self {
+                SeatLayout::Grid { .. } => (0..self.seat_count()).collect(),
+                SeatLayout::List(seats) => seats.clone(),
+            }
+        }
+    }
+
+    /// A ticket offered for resale on the built-in marketplace
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Listing {
+        pub ticket_id: u64,
+        pub seller: AccountId,
+        pub price: Balance,
+        pub listed_at: u64,
+    }
+
+    /// Escrowed offer on a ticket, open until cancelled or accepted
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Offer {
+        pub amount: Balance,
+        pub made_at: u64,
+    }
+
+    /// Raffle allocation state for an oversubscribed event
+    ///
+    /// After the draw, entrant `index` wins if it falls within `winners`
+    /// places of the random `offset`, wrapping around the entry list.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Raffle {
+        pub entry_end: u64,
+        pub entrants: u32,
+        pub drawn: bool,
+        pub offset: u32,
+        pub winners: u32,
+    }
+
+    impl Raffle {
+        fn is_winner(&self, index: u32) -> bool {
+            let entrants = self.entrants as u64;
+            self.drawn
+                && entrants > 0
+                && (index as u64 + entrants - self.offset as u64) % entrants < self.winners as u64
+        }
+    }
+
+    /// An account's raffle entry and the deposit it paid
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct RaffleEntry {
+        pub index: u32,
+        pub deposit: Balance,
+    }
+
+    /// Two-phase sale for hot drops: buyers commit with a deposit until
+    /// `commit_end`, then reveal until `reveal_end`, and tickets are drawn
+    /// by lottery among the reveals
+    ///
+    /// Regular sales open for any tickets left once the lottery is drawn.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct CommitReveal {
+        pub commit_end: u64,
+        pub reveal_end: u64,
+    }
+
+    /// An account's sealed commit-reveal purchase and the deposit it paid
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PurchaseCommitment {
+        pub commitment: Hash,
+        pub deposit: Balance,
+        pub entry: Option<u32>, // Lottery entry, once revealed
+    }
+
+    /// English auction for a premium seat, held back from regular sales until settled
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct SeatAuction {
+        pub reserve: Balance, // Lowest acceptable bid
+        pub end_time: u64, // Bids are accepted until this timestamp
+        pub highest_bidder: Option<AccountId>,
+        pub highest_bid: Balance, // Held by the contract until outbid or settled
+    }
+
+    /// FIFO waitlist for a sold-out event
+    ///
+    /// Queue positions `head..tail` are stored in `waitlist_queue`;
+    /// `open_offers` tickets are held back for waitlisted accounts.
+    #[derive(Debug, Clone, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Waitlist {
+        pub head: u32,
+        pub tail: u32,
+        pub open_offers: u32,
+    }
+
+    /// A discount code registered by an event organizer
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PromoCode {
+        pub discount_bps: u16,
+        pub remaining_uses: u32,
+        pub times_used: u32,
+        pub total_discount: Balance,
+    }
+
+    /// Soulbound proof-of-attendance token, one per attendee per event
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct AttendanceToken {
+        pub id: u64,
+        pub event_id: u64,
+        pub owner: AccountId,
+        pub ticket_id: u64,
+        pub metadata_cid: String, // IPFS CID of the event's attendance artwork
+        pub minted_at: u64,
+    }
+
+    /// A single change of a ticket's owner, from minting onwards
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct OwnershipRecord {
+        pub from: Option<AccountId>, // None when the ticket was minted
+        pub to: AccountId,
+        pub timestamp: u64,
+        pub price: Option<Balance>, // Resale price, None for gifts and transfers
+    }
+
+    /// Which of the organizer's events a pass admits to
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum PassScope {
+        /// Only the listed events
+        Events(Vec<u64>),
+        /// Every event the organizer creates after the pass
+        FutureEvents,
+    }
+
+    /// A season pass admitting once to each event in its scope
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Pass {
+        pub id: u64,
+        pub organizer: AccountId,
+        pub name: String,
+        pub metadata_cid: String, // IPFS CID for pass details
+        pub price: Balance, // In native token
+        pub supply: u32,
+        pub sold: u32,
+        pub scope: PassScope,
+        pub first_event_id: u64, // Lowest event ID a `FutureEvents` pass covers
+        pub valid_until: u64, // Pass expires, and earnings unlock, at this timestamp
+    }
+
+    impl Pass {
+        /// Whether the pass admits to `event`
+        fn covers(&self, event: &Event) -> bool {
+            event.organizer == self.organizer
+                && match &self.scope {
+                    PassScope::Events(event_ids) => event_ids.contains(&event.id),
+                    PassScope::FutureEvents => event.id >= self.first_event_id,
+                }
+        }
+    }
+
+    /// A recurring event, e.g. a weekly show, with one event per session
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Series {
+        pub id: u64,
+        pub organizer: AccountId,
+        pub name: String,
+        pub event_ids: Vec<u64>, // One event per session, earliest first
+    }
+
+    /// A pass bought by a holder, the NFT checked in at each event
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PassToken {
+        pub id: u64,
+        pub pass_id: u64,
+        pub owner: AccountId,
+        pub purchase_time: u64,
+    }
+
+    /// A single check-in in an account's attendance history
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct AttendanceRecord {
+        pub event_id: u64,
+        pub ticket_id: u64,
+        pub tier_id: Option<u32>,
+        pub attended_at: u64,
+    }
+
+    /// A post-event review left by an attendee
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Review {
+        pub event_id: u64,
+        pub reviewer: AccountId,
+        pub rating: u8,
+        pub review_cid: String, // IPFS CID for the review text
+        pub submitted_at: u64,
+    }
+
+    /// Aggregated ratings for an event or organizer
+    #[derive(Debug, Clone, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct RatingSummary {
+        pub review_count: u32,
+        pub rating_total: u64,
+        /// Average rating multiplied by 100 (e.g. 450 = 4.5 stars)
+        pub average_x100: u32,
+    }
+
+    impl RatingSummary {
+        fn add(&mut self, rating: u8) {
+            self.review_count = self.review_count.saturating_add(1);
+            self.rating_total = self.rating_total.saturating_add(rating as u64);
+            self.average_x100 = (self.rating_total.saturating_mul(100)
+                / self.review_count as u64) as u32;
+        }
+    }
+
+    /// Running sales, refund, and attendance totals for an event
+    ///
+    /// Amounts are in the event's payment asset. `net_escrow` is filled in
+    /// from the event's escrow when queried.
+    #[derive(Debug, Clone, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct EventStats {
+        pub tickets_sold: u32,
+        pub tickets_refunded: u32,
+        pub tickets_checked_in: u32,
+        pub gross_revenue: Balance,
+        pub refunds_paid: Balance,
+        pub net_escrow: Balance,
+        pub donations: Balance, // Contributions to the event, on top of ticket revenue
+        pub tips: Balance, // Tips for the organizer, paid into the event's escrow
+    }
+
+    /// Lifetime activity totals for an account
+    ///
+    /// Amounts are in the native token; PSP22 amounts are tracked
+    /// separately per token (see `get_user_token_totals`).
+    #[derive(Debug, Clone, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct UserSummary {
+        pub tickets_bought: u32,
+        pub total_spent: Balance,
+        pub refunds_received: Balance,
+        pub events_attended: u32,
+    }
+
+    /// Public profile of an event organizer
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct OrganizerProfile {
+        pub display_name: String,
+        pub metadata_cid: String, // IPFS CID of the full profile (bio, logo, links)
+        pub contact_hash: Hash, // Hash of off-chain contact details, for verification
+        pub registered_at: u64,
+        pub is_verified: bool, // Vetted by a moderator; cleared when the profile changes
+    }
+
+    /// Branding of the ticket NFT collection shown by wallets and marketplaces
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct CollectionMetadata {
+        pub name: String,
+        pub symbol: String,
+        pub base_uri: String, // Base URI or IPFS CID of collection-level metadata
+    }
+
+    impl Default for CollectionMetadata {
+        fn default() -> Self {
+            Self {
+                name: String::from("TicketDot"),
+                symbol: String::from("TDOT"),
+                base_uri: String::new(),
+            }
+        }
+    }
+
+    /// Lifetime amounts spent and refunded in a single PSP22 token
+    #[derive(Debug, Clone, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct TokenTotals {
+        pub spent: Balance,
+        pub refunded: Balance,
+    }
+
+    /// Where an attendee dispute stands
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum DisputeStatus {
+        /// Awaiting arbitration
+        Open,
+        /// Upheld; the ticket was refunded from the event's escrow
+        Refunded,
+        /// Rejected by an admin
+        Dismissed,
+    }
+
+    /// Whether a ticket would be let in right now, as seen by `verify_ticket`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum TicketStatus {
+        /// Can be checked in
+        Valid,
+        /// No check-ins left
+        Used,
+        /// Refunded and burned
+        Refunded,
+        /// The event was cancelled
+        EventCancelled,
+        /// The event was frozen by a moderator
+        EventFrozen,
+        /// The event is over
+        EventCompleted,
+        /// The event's end time has passed
+        Expired,
+        /// Outside the event's check-in window
+        WrongWindow,
+        /// No such ticket
+        NotFound,
+    }
+
+    /// A step in a ticket's lifecycle, as reported by `TicketStateChanged`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum TicketState {
+        /// Minted and ready to check in
+        Issued,
+        /// Checked in, with check-ins left
+        PartlyUsed,
+        /// No check-ins left
+        Used,
+        /// Moved to another tier
+        Upgraded,
+        /// The event was cancelled, so the ticket can be refunded
+        EventCancelled,
+        /// The event is over, so unused check-ins lapse
+        Expired,
+        /// Refunded and burned
+        Refunded,
+    }
+
+    /// A complaint filed by a ticket holder against a completed event
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Dispute {
+        pub id: u64,
+        pub event_id: u64,
+        pub ticket_id: u64,
+        pub holder: AccountId,
+        pub reason_cid: String, // IPFS CID of the holder's statement and evidence
+        pub status: DisputeStatus,
+        pub filed_at: u64,
+    }
+
+    /// Platform operator roles
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Role {
+        /// Full control, including granting and revoking roles
+        Admin,
+        /// Moderates events (freezing, force-cancelling)
+        Moderator,
+        /// Sets platform fees and the treasury, and withdraws fees
+        FeeManager,
+        /// Pauses and unpauses the contract
+        Pauser,
+    }
+
+    /// What a notification subscription is attached to
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum SubscriptionTarget {
+        /// A single event
+        Event(u64),
+        /// Every event of an organizer
+        Organizer(AccountId),
+    }
+
+    /// Kind of change announced to subscribers
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum NotificationKind {
+        EventCreated,
+        EventCancelled,
+        EventCompleted,
+        TicketsAvailable,
+        EventRescheduled,
+    }
+
+    /// Custom error types
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Event does not exist
+        EventNotFound,
+        /// No tickets available
+        SoldOut,
+        /// Payment doesn't match what's due
+        InsufficientPayment { expected: Balance, got: Balance },
+        /// Ticket does not exist
+        TicketNotFound,
+        /// Caller is not the ticket owner
+        NotTicketOwner,
+        /// Event is not active
+        EventNotActive,
+        /// Ticket already used
+        TicketAlreadyUsed,
+        /// Transfer failed
+        TransferFailed,
+        /// Caller is not the event organizer
+        NotOrganizer,
+        /// Event already cancelled
+        EventCancelled,
+        /// Event already completed
+        EventCompleted,
+        /// Ticket already refunded
+        TicketAlreadyRefunded,
+        /// Invalid input parameters
+        InvalidInput,
+        /// User has too many tickets
+        TooManyTickets,
+        /// Contract balance insufficient
+        InsufficientBalance,
+        /// Event not completed yet
+        EventNotCompleted,
+        /// User has too many subscriptions
+        TooManySubscriptions,
+        /// Subscription does not exist
+        SubscriptionNotFound,
+        /// Ticket has not been checked in
+        TicketNotUsed,
+        /// Account already reviewed this event
+        AlreadyReviewed,
+        /// Referral code does not exist
+        ReferralCodeNotFound,
+        /// Referral code already registered
+        ReferralCodeTaken,
+        /// Buyers can't refer themselves
+        SelfReferral,
+        /// Event doesn't take part in the referral program
+        ReferralsDisabled,
+        /// Caller lacks the role required for this action
+        MissingRole,
+        /// Ticket tier does not exist
+        TierNotFound,
+        /// Seat is not part of the event's layout
+        InvalidSeat,
+        /// Seat is already booked
+        SeatTaken,
+        /// Event uses reserved seating and a seat must be picked
+        SeatRequired,
+        /// Ticket sales haven't started yet
+        SaleNotOpen { opens_at: u64 },
+        /// Ticket sales have ended
+        SaleClosed,
+        /// Check-in is only possible during the event
+        OutsideEventWindow,
+        /// Ticket is not listed for sale
+        NotListed,
+        /// Event has no earnings left to withdraw
+        NothingToWithdraw,
+        /// Contract is paused by a pauser
+        ContractPaused,
+        /// The only remaining admin can't be revoked
+        CannotRemoveLastAdmin,
+        /// Replacing the contract code failed
+        UpgradeFailed,
+        /// Storage is already at the current version
+        AlreadyMigrated,
+        /// Only allowlisted accounts can buy during the presale
+        NotAllowlisted,
+        /// Resale price is above the event's cap
+        ResalePriceTooHigh,
+        /// Event's tickets are soulbound and can't change hands
+        TicketNotTransferable,
+        /// Native token sent to an event paid in a PSP22 token
+        UnexpectedNativePayment,
+        /// Merkle proof doesn't match the event's allowlist root
+        InvalidProof,
+        /// Event's tickets are only allocated through its raffle
+        RaffleOnly,
+        /// Event has no raffle
+        NoRaffle,
+        /// Raffle entries are closed
+        RaffleClosed,
+        /// Raffle entries are still open
+        RaffleStillOpen,
+        /// Raffle winners have already been drawn
+        RaffleAlreadyDrawn,
+        /// Raffle winners haven't been drawn yet
+        RaffleNotDrawn,
+        /// Account already entered the raffle
+        AlreadyEntered,
+        /// Account has no open raffle entry
+        NotRaffleEntrant,
+        /// Account's entry didn't win the raffle
+        NotRaffleWinner,
+        /// Event still has tickets for sale
+        NotSoldOut,
+        /// Account is already waiting or holds an offer
+        AlreadyWaitlisted,
+        /// Account isn't on the waitlist
+        NotWaitlisted,
+        /// Account has no waitlist offer
+        NoWaitlistOffer,
+        /// Waitlist offer ran out
+        WaitlistOfferExpired,
+        /// Waitlist offer can still be claimed
+        WaitlistOfferActive,
+        /// Promo code isn't registered for the event
+        PromoCodeNotFound,
+        /// Promo code has no uses left
+        PromoCodeExhausted,
+        /// Event's complimentary ticket quota is used up
+        CompQuotaExceeded,
+        /// Signature wasn't made by the expected account
+        InvalidSignature,
+        /// Voucher nonce has already been redeemed
+        VoucherAlreadyRedeemed,
+        /// Check-in challenge has already been used for the ticket
+        ChallengeAlreadyUsed,
+        /// Owners can't approve themselves as operator
+        SelfApproval,
+        /// Permit deadline has passed
+        PermitExpired,
+        /// Refunds for the cancelled event can no longer be claimed
+        RefundPeriodEnded,
+        /// Refunds for the cancelled event can still be claimed
+        RefundPeriodOpen,
+        /// Event has been frozen by a moderator
+        EventFrozen,
+        /// Earnings are locked until the event's dispute window closes
+        WithdrawalLocked,
+        /// Too many open disputes against the event to withdraw
+        TooManyDisputes,
+        /// The event's dispute window has closed
+        DisputeWindowClosed,
+        /// Ticket already has a dispute filed
+        AlreadyDisputed,
+        /// Dispute does not exist
+        DisputeNotFound,
+        /// Dispute has already been resolved
+        DisputeNotOpen,
+        /// Earnings have already been released, so the split is fixed
+        PayoutsStarted,
+        /// Account must register an organizer profile first
+        OrganizerNotRegistered,
+        /// Account is banned from organizing events
+        OrganizerBanned,
+        /// Account is blocked from holding tickets for the event
+        AttendeeBlocked,
+        /// Account doesn't hold the tokens the event is gated on
+        NotTokenHolder,
+        /// Pass or pass token does not exist
+        PassNotFound,
+        /// Pass is past its expiry
+        PassExpired,
+        /// Pass doesn't cover the event
+        PassNotValidForEvent,
+        /// Series does not exist
+        SeriesNotFound,
+        /// The organizer has paused ticket sales
+        SalesPaused,
+        /// The event has ended, so its tickets are no longer valid
+        TicketExpired,
+        /// The event has no end time, or its grace period hasn't passed yet
+        EventNotEnded,
+        /// Add-on does not exist
+        AddOnNotFound,
+        /// Ticket already has this add-on
+        AddOnAlreadyPurchased,
+        /// Ticket doesn't have this add-on
+        AddOnNotPurchased,
+        /// Add-on has already been redeemed
+        AddOnAlreadyRedeemed,
+        /// The event has already started
+        EventStarted,
+        /// The event's status can't move to the one requested
+        InvalidStatusTransition,
+        /// More tickets requested than one purchase allows
+        PurchaseLimitReached { limit: u32 },
+        /// No `pallet-nfts` collection is configured for exports
+        NftCollectionNotSet,
+        /// Ticket is already mirrored into the NFT collection
+        TicketAlreadyExported,
+        /// The runtime rejected a call to `pallet-nfts`
+        NftMirrorFailed,
+        /// Caller is not a trusted XCM origin
+        UntrustedXcmOrigin,
+        /// The XCM message has already bought a ticket
+        XcmDepositAlreadyProcessed,
+        /// No price oracle is set, or it didn't answer
+        OracleUnavailable,
+        /// The oracle's quote is older than the staleness bound
+        StalePrice,
+        /// Poll does not exist
+        PollNotFound,
+        /// Poll has stopped taking votes
+        PollClosed,
+        /// Ticket has already voted in this poll
+        AlreadyVoted,
+        /// Account doesn't hold enough loyalty points
+        InsufficientPoints,
+        /// Ticket was acquired too recently to be passed on
+        TransferCooldown { until: u64 },
+        /// Ticket has changed hands as often as the event allows
+        TransferLimitReached { limit: u32 },
+        /// The event has sold as many tickets this block as allowed
+        BlockMintLimitReached,
+        /// Account bought too recently to buy again
+        PurchaseCooldown { until_block: u32 },
+        /// Buyer has no personhood attestation
+        NotVerifiedHuman,
+        /// The person behind the buyer's wallets already holds their quota
+        PersonQuotaReached { limit: u32 },
+        /// Account hasn't presented the credential the event requires
+        CredentialRequired,
+        /// Credential isn't from the event's attester or no longer verifies
+        InvalidCredential,
+        /// Private event needs its access key (see `buy_ticket_private`)
+        AccessKeyRequired,
+        /// Access key doesn't match the private event's hash
+        InvalidAccessKey,
+        /// Event doesn't sell through commit-reveal
+        NoCommitReveal,
+        /// Account has no pending commitment for the event
+        CommitmentNotFound,
+        /// Revealed salt doesn't match the commitment
+        CommitmentMismatch,
+        /// The commit or reveal phase is over
+        PhaseClosed,
+        /// No open auction for the seat
+        AuctionNotFound,
+        /// Auction has stopped taking bids
+        AuctionEnded,
+        /// Auction is still taking bids
+        AuctionNotEnded,
+        /// Bid must beat the reserve and the highest bid
+        BidTooLow { min_bid: Balance },
+        /// Account has no open offer on the ticket
+        OfferNotFound,
+        /// Seed doesn't match the raffle's committed seed hash
+        RaffleSeedMismatch,
+        /// Raffle winners claim their ticket rather than a refund, unless
+        /// they can't get the ticket
+        RaffleWinnerMustClaim,
+        /// Storage is still being migrated to the current version
+        MigrationPending,
+        /// Commitments not revealed in time forfeit their deposit
+        CommitmentForfeited,
+        /// An XCM purchase arrived without a native deposit attached
+        XcmDepositMissing,
+    }
+
+    impl Error {
+        /// Stable numeric code for the error, for frontends to localize
+        ///
+        /// Codes follow declaration order and are never reused: new
+        /// variants are appended and take the next code.
+        pub fn code(&self) -> u16 {
+            match self {
+                Error::EventNotFound => 0,
+                Error::SoldOut => 1,
+                Error::InsufficientPayment { .. } => 2,
+                Error::TicketNotFound => 3,
+                Error::NotTicketOwner => 4,
+                Error::EventNotActive => 5,
+                Error::TicketAlreadyUsed => 6,
+                Error::TransferFailed => 7,
+                Error::NotOrganizer => 8,
+                Error::EventCancelled => 9,
+                Error::EventCompleted => 10,
+                Error::TicketAlreadyRefunded => 11,
+                Error::InvalidInput => 12,
+                Error::TooManyTickets => 13,
+                Error::InsufficientBalance => 14,
+                Error::EventNotCompleted => 15,
+                Error::TooManySubscriptions => 16,
+                Error::SubscriptionNotFound => 17,
+                Error::TicketNotUsed => 18,
+                Error::AlreadyReviewed => 19,
+                Error::ReferralCodeNotFound => 20,
+                Error::ReferralCodeTaken => 21,
+                Error::SelfReferral => 22,
+                Error::ReferralsDisabled => 23,
+                Error::MissingRole => 24,
+                Error::TierNotFound => 25,
+                Error::InvalidSeat => 26,
+                Error::SeatTaken => 27,
+                Error::SeatRequired => 28,
+                Error::SaleNotOpen { .. } => 29,
+                Error::SaleClosed => 30,
+                Error::OutsideEventWindow => 31,
+                Error::NotListed => 32,
+                Error::NothingToWithdraw => 33,
+                Error::ContractPaused => 34,
+                Error::CannotRemoveLastAdmin => 35,
+                Error::UpgradeFailed => 36,
+                Error::AlreadyMigrated => 37,
+                Error::NotAllowlisted => 38,
+                Error::ResalePriceTooHigh => 39,
+                Error::TicketNotTransferable => 40,
+                Error::UnexpectedNativePayment => 41,
+                Error::InvalidProof => 42,
+                Error::RaffleOnly => 43,
+                Error::NoRaffle => 44,
+                Error::RaffleClosed => 45,
+                Error::RaffleStillOpen => 46,
+                Error::RaffleAlreadyDrawn => 47,
+                Error::RaffleNotDrawn => 48,
+                Error::AlreadyEntered => 49,
+                Error::NotRaffleEntrant => 50,
+                Error::NotRaffleWinner => 51,
+                Error::NotSoldOut => 52,
+                Error::AlreadyWaitlisted => 53,
+                Error::NotWaitlisted => 54,
+                Error::NoWaitlistOffer => 55,
+                Error::WaitlistOfferExpired => 56,
+                Error::WaitlistOfferActive => 57,
+                Error::PromoCodeNotFound => 58,
+                Error::PromoCodeExhausted => 59,
+                Error::CompQuotaExceeded => 60,
+                Error::InvalidSignature => 61,
+                Error::VoucherAlreadyRedeemed => 62,
+                Error::ChallengeAlreadyUsed => 63,
+                Error::SelfApproval => 64,
+                Error::PermitExpired => 65,
+                Error::RefundPeriodEnded => 66,
+                Error::RefundPeriodOpen => 67,
+                Error::EventFrozen => 68,
+                Error::WithdrawalLocked => 69,
+                Error::TooManyDisputes => 70,
+                Error::DisputeWindowClosed => 71,
+                Error::AlreadyDisputed => 72,
+                Error::DisputeNotFound => 73,
+                Error::DisputeNotOpen => 74,
+                Error::PayoutsStarted => 75,
+                Error::OrganizerNotRegistered => 76,
+                Error::OrganizerBanned => 77,
+                Error::AttendeeBlocked => 78,
+                Error::NotTokenHolder => 79,
+                Error::PassNotFound => 80,
+                Error::PassExpired => 81,
+                Error::PassNotValidForEvent => 82,
+                Error::SeriesNotFound => 83,
+                Error::SalesPaused => 84,
+                Error::TicketExpired => 85,
+                Error::EventNotEnded => 86,
+                Error::AddOnNotFound => 87,
+                Error::AddOnAlreadyPurchased => 88,
+                Error::AddOnNotPurchased => 89,
+                Error::AddOnAlreadyRedeemed => 90,
+                Error::EventStarted => 91,
+                Error::InvalidStatusTransition => 92,
+                Error::PurchaseLimitReached { .. } => 93,
+                Error::NftCollectionNotSet => 94,
+                Error::TicketAlreadyExported => 95,
+                Error::NftMirrorFailed => 96,
+                Error::UntrustedXcmOrigin => 97,
+                Error::XcmDepositAlreadyProcessed => 98,
+                Error::OracleUnavailable => 99,
+                Error::StalePrice => 100,
+                Error::PollNotFound => 101,
+                Error::PollClosed => 102,
+                Error::AlreadyVoted => 103,
+                Error::InsufficientPoints => 104,
+                Error::TransferCooldown { .. } => 105,
+                Error::TransferLimitReached { .. } => 106,
+                Error::BlockMintLimitReached => 107,
+                Error::PurchaseCooldown { .. } => 108,
+                Error::NotVerifiedHuman => 109,
+                Error::PersonQuotaReached { .. } => 110,
+                Error::CredentialRequired => 111,
+                Error::InvalidCredential => 112,
+                Error::AccessKeyRequired => 113,
+                Error::InvalidAccessKey => 114,
+                Error::NoCommitReveal => 115,
+                Error::CommitmentNotFound => 116,
+                Error::CommitmentMismatch => 117,
+                Error::PhaseClosed => 118,
+                Error::AuctionNotFound => 119,
+                Error::AuctionEnded => 120,
+                Error::AuctionNotEnded => 121,
+                Error::BidTooLow { .. } => 122,
+                Error::OfferNotFound => 123,
+                Error::RaffleSeedMismatch => 124,
+                Error::RaffleWinnerMustClaim => 125,
+                Error::MigrationPending => 126,
+                Error::CommitmentForfeited => 127,
+                Error::XcmDepositMissing => 128,
+            }
+        }
+    }
+
+    /// Root settings and counters of the contract
+    ///
+    /// Version 1 stored only the admin account after the event and ticket
+    /// counters, so root bytes of exactly that size decode to
+    /// `ContractState::legacy` and wait for `migrate`.
+    #[derive(scale::Encode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct ContractState {
+        /// Number of accounts holding the Admin role
+        admin_count: u32,
+        /// Counter for attendance NFT IDs
+        attendance_token_counter: u64,
+        /// Share of each referred sale's platform fee credited to the
+        /// referrer, in basis points
+        referral_fee_share_bps: u16,
+        /// Number of tickets burned by refunds (for PSP34 total supply)
+        burned_tickets: u64,
+        /// Maximum tickets minted by a single `buy_tickets` call
+        max_tickets_per_purchase: u32,
+        /// Platform limits on new events
+        config: Config,
+        /// Platform fee taken from every primary sale, in basis points
+        platform_fee_bps: u16,
+        /// Account receiving withdrawn platform fees
+        treasury: AccountId,
+        /// Platform fees collected in native token and not yet withdrawn
+        platform_fees: Balance,
+        /// Emergency circuit breaker; blocks everything except getters and refunds
+        paused: bool,
+        /// Layout version of the data in storage (see `migrate`)
+        storage_version: u32,
+        /// Bond organizers lock when creating an event, in native token
+        event_bond: Balance,
+        /// Share of the bond slashed when an organizer cancels, in basis points
+        bond_slash_bps: u16,
+        /// How long after an event ends attendees can dispute it, in milliseconds
+        dispute_window: u64,
+        /// Open disputes, as a share of tickets sold, that block withdrawals
+        dispute_threshold_bps: u16,
+        /// Counter for dispute IDs
+        dispute_counter: u64,
+        /// Whether creating events requires a registered organizer profile
+        require_organizer_registration: bool,
+        /// Sum of the escrows of all native-token events
+        total_escrowed: Balance,
+        /// Name, symbol, and base URI of the ticket collection
+        collection_metadata: CollectionMetadata,
+        /// Counter for pass IDs
+        pass_counter: u64,
+        /// Counter for pass token IDs
+        pass_token_counter: u64,
+        /// Counter for series IDs
+        series_counter: u64,
+        /// How long after an event ends anyone can finalize it, in milliseconds
+        finalize_grace_period: u64,
+        /// Next event, then ticket, for `migrate` to convert, while a
+        /// migration is under way
+        migration_cursor: u64,
+        /// Contract quoting the native token price of one US dollar
+        price_oracle: Option<AccountId>,
+        /// Oldest oracle quote accepted for USD prices, in milliseconds
+        max_oracle_staleness: u64,
+        /// `pallet-nfts` collection tickets are exported to (None = exports disabled)
+        nft_collection: Option<u32>,
+        /// Points earned per paid ticket
+        points_per_purchase: u64,
+        /// Points earned on a ticket's first check-in
+        points_per_checkin: u64,
+        /// Purchase rate limits
+        rate_limits: RateLimits,
+        /// Contract attesting which accounts belong to verified humans
+        personhood_registry: Option<AccountId>,
+        /// Admin recorded by version 1 storage, granted the Admin role by `migrate`
+        legacy_admin: Option<AccountId>,
+    }
+
+    impl ContractState {
+        /// Settings of a freshly deployed contract administered by `admin`
+        fn new(admin: AccountId) -> Self {
+            Self {
+                admin_count: 1,
+                attendance_token_counter: 0,
+                referral_fee_share_bps: 0,
+                burned_tickets: 0,
+                max_tickets_per_purchase: DEFAULT_MAX_TICKETS_PER_PURCHASE,
+                config: Config::default(),
+                platform_fee_bps: 0,
+                treasury: admin,
+                platform_fees: 0,
+                paused: false,
+                storage_version: STORAGE_VERSION,
+                event_bond: 0,
+                bond_slash_bps: 0,
+                dispute_window: DEFAULT_DISPUTE_WINDOW,
+                dispute_threshold_bps: 0,
+                dispute_counter: 0,
+                require_organizer_registration: false,
+                total_escrowed: 0,
+                collection_metadata: CollectionMetadata::default(),
+                pass_counter: 0,
+                pass_token_counter: 0,
+                series_counter: 0,
+                finalize_grace_period: DEFAULT_FINALIZE_GRACE_PERIOD,
+                migration_cursor: 0,
+                price_oracle: None,
+                max_oracle_staleness: DEFAULT_MAX_ORACLE_STALENESS,
+                nft_collection: None,
+                points_per_purchase: DEFAULT_POINTS_PER_PURCHASE,
+                points_per_checkin: DEFAULT_POINTS_PER_CHECKIN,
+                rate_limits: RateLimits::default(),
+                personhood_registry: None,
+                legacy_admin: None,
+            }
+        }
+
+        /// Settings of a contract still holding version 1 storage
+        fn legacy(admin: AccountId) -> Self {
+            Self {
+                admin_count: 0,
+                storage_version: 1,
+                legacy_admin: Some(admin),
+                ..Self::new(admin)
+            }
+        }
+    }
+
+    impl scale::Decode for ContractState {
+        fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+            if input.remaining_len()? == Some(32) {
+                return Ok(Self::legacy(scale::Decode::decode(input)?));
+            }
+            Ok(Self {
+                admin_count: scale::Decode::decode(input)?,
+                attendance_token_counter: scale::Decode::decode(input)?,
+                referral_fee_share_bps: scale::Decode::decode(input)?,
+                burned_tickets: scale::Decode::decode(input)?,
+                max_tickets_per_purchase: scale::Decode::decode(input)?,
+                config: scale::Decode::decode(input)?,
+                platform_fee_bps: scale::Decode::decode(input)?,
+                treasury: scale::Decode::decode(input)?,
+                platform_fees: scale::Decode::decode(input)?,
+                paused: scale::Decode::decode(input)?,
+                storage_version: scale::Decode::decode(input)?,
+                event_bond: scale::Decode::decode(input)?,
+                bond_slash_bps: scale::Decode::decode(input)?,
+                dispute_window: scale::Decode::decode(input)?,
+                dispute_threshold_bps: scale::Decode::decode(input)?,
+                dispute_counter: scale::Decode::decode(input)?,
+                require_organizer_registration: scale::Decode::decode(input)?,
+                total_escrowed: scale::Decode::decode(input)?,
+                collection_metadata: scale::Decode::decode(input)?,
+                pass_counter: scale::Decode::decode(input)?,
+                pass_token_counter: scale::Decode::decode(input)?,
+                series_counter: scale::Decode::decode(input)?,
+                finalize_grace_period: scale::Decode::decode(input)?,
+                migration_cursor: scale::Decode::decode(input)?,
+                price_oracle: scale::Decode::decode(input)?,
+                max_oracle_staleness: scale::Decode::decode(input)?,
+                nft_collection: scale::Decode::decode(input)?,
+                points_per_purchase: scale::Decode::decode(input)?,
+                points_per_checkin: scale::Decode::decode(input)?,
+                rate_limits: scale::Decode::decode(input)?,
+                personhood_registry: scale::Decode::decode(input)?,
+                legacy_admin: scale::Decode::decode(input)?,
+            })
+        }
+    }
+
+    /// Main contract storage
+    #[ink(storage)]
+    pub struct TicketDot {
+        /// Counter for event IDs
+        event_counter: u64,
+        /// Counter for ticket IDs
+        ticket_counter: u64,
+        /// Mapping from event ID to Event
+        events: Mapping<u64, Event>,
+        /// Mapping from ticket ID to Ticket
+        tickets: Mapping<u64, Ticket>,
+        /// Mapping from (owner, index) to the ticket IDs they hold, unordered
+        owner_ticket_at: Mapping<(AccountId, u32), u64>,
+        /// Position of each held ticket in its owner's list
+        owner_ticket_index: Mapping<u64, u32>,
+        /// Number of (unrefunded) tickets held per owner
+        owner_ticket_count: Mapping<AccountId, u32>,
+        /// Set of (role, account) grants
+        roles: Mapping<(Role, AccountId), ()>,
+        /// Mapping from (subscriber, target) to opaque endpoint hash
+        subscriptions: Mapping<(AccountId, SubscriptionTarget), Hash>,
+        /// Number of active subscriptions per subscriber
+        subscription_count: Mapping<AccountId, u32>,
+        /// Mapping from (account, index) to attendance record, in check-in order
+        attendance_records: Mapping<(AccountId, u32), AttendanceRecord>,
+        /// Number of attendance records per account
+        attendance_count: Mapping<AccountId, u32>,
+        /// Metadata CID of each event's attendance NFT; unset events mint none
+        attendance_nft_cids: Mapping<u64, String>,
+        /// Attendance NFTs per (event ID, holder); they can never be transferred
+        attendance: Mapping<(u64, AccountId), AttendanceToken>,
+        /// Mapping from (event ID, reviewer) to their review
+        reviews: Mapping<(u64, AccountId), Review>,
+        /// Aggregated ratings per event
+        event_ratings: Mapping<u64, RatingSummary>,
+        /// Aggregated ratings per organizer across all their events
+        organizer_ratings: Mapping<AccountId, RatingSummary>,
+        /// Mapping from referral code to the account it belongs to
+        referral_codes: Mapping<String, AccountId>,
+        /// Claimable referral rewards per (referrer, payment token)
+        referral_rewards: Mapping<(AccountId, Option<AccountId>), Balance>,
+        /// Lifetime activity totals per account, maintained incrementally
+        user_summaries: Mapping<AccountId, UserSummary>,
+        /// Set of (account, event ID) pairs the account has checked in to
+        attended_events: Mapping<(AccountId, u64), ()>,
+        /// Mapping from ticket ID to the operator approved to move it
+        ticket_approvals: Mapping<u64, AccountId>,
+        /// Set of (owner, operator) pairs approved for all of the owner's tickets
+        operator_approvals: Mapping<(AccountId, AccountId), ()>,
+        /// Mapping from event ID to its seat layout (reserved-seating events only)
+        seat_layouts: Mapping<u64, SeatLayout>,
+        /// Mapping from (event ID, seat ID) to the ticket holding the seat
+        booked_seats: Mapping<(u64, u32), u64>,
+        /// Mapping from (account, PSP22 token) to lifetime amounts in that token
+        token_totals: Mapping<(AccountId, AccountId), TokenTotals>,
+        /// Platform fees collected per PSP22 token and not yet withdrawn
+        platform_token_fees: Mapping<AccountId, Balance>,
+        /// Set of (event ID, account) pairs allowed to buy during the presale
+        allowlist: Mapping<(u64, AccountId), ()>,
+        /// Mapping from event ID to the Merkle root of its presale allowlist
+        allowlist_roots: Mapping<u64, Hash>,
+        /// Mapping from ticket ID to its active resale listing
+        listings: Mapping<u64, Listing>,
+        /// Funds held per event (sales net of fees, plus royalties, minus
+        /// refunds and withdrawals), in the event's payment asset
+        escrow_balance: Mapping<u64, Balance>,
+        /// Total earnings paid out to the organizer per event
+        total_withdrawn: Mapping<u64, Balance>,
+        /// Mapping from event ID to its raffle
+        raffles: Mapping<u64, Raffle>,
+        /// Open raffle entries per (event ID, account)
+        raffle_entries: Mapping<(u64, AccountId), RaffleEntry>,
+        /// Mapping from event ID to its waitlist
+        waitlists: Mapping<u64, Waitlist>,
+        /// Waitlist queue entries per (event ID, position)
+        waitlist_queue: Mapping<(u64, u32), AccountId>,
+        /// Queue position of each (event ID, account) pair still waiting
+        waitlisted: Mapping<(u64, AccountId), u32>,
+        /// Expiry of the reservation offered to each (event ID, account)
+        waitlist_offers: Mapping<(u64, AccountId), u64>,
+        /// Promo codes per (event ID, code hash)
+        promo_codes: Mapping<(u64, Hash), PromoCode>,
+        /// Set of redeemed (event ID, voucher nonce) pairs
+        redeemed_vouchers: Mapping<(u64, u64), ()>,
+        /// Set of (event ID, account) pairs allowed to check tickets in
+        checkin_staff: Mapping<(u64, AccountId), ()>,
+        /// Set of (ticket ID, challenge) pairs already used to check in
+        used_challenges: Mapping<(u64, [u8; 32]), ()>,
+        /// Next permit nonce per account; each signed permit uses one
+        permit_nonces: Mapping<AccountId, u64>,
+        /// Until when holders of a rescheduled event can opt out for a refund
+        reschedule_refund_deadlines: Mapping<u64, u64>,
+        /// Until when holders of a cancelled event can claim their refund
+        refund_deadlines: Mapping<u64, u64>,
+        /// Bond locked per event, returned on completion
+        event_bonds: Mapping<u64, Balance>,
+        /// When a completed event's earnings can first be withdrawn
+        withdrawal_unlocks: Mapping<u64, u64>,
+        /// Number of open disputes per event
+        open_disputes: Mapping<u64, u32>,
+        /// Disputes by dispute ID
+        disputes: Mapping<u64, Dispute>,
+        /// Dispute filed per ticket ID
+        ticket_disputes: Mapping<u64, u64>,
+        /// Revenue shares per event; the organizer gets the remainder
+        payout_splits: Mapping<u64, Vec<PayoutShare>>,
+        /// Released earnings not yet pulled, per (event ID, payee)
+        pending_payouts: Mapping<(u64, AccountId), Balance>,
+        /// Set of (event ID, account) pairs sharing operational control of an event
+        co_organizers: Mapping<(u64, AccountId), ()>,
+        /// Registered organizer profiles
+        organizers: Mapping<AccountId, OrganizerProfile>,
+        /// Set of accounts banned from creating events
+        banned_organizers: Mapping<AccountId, ()>,
+        /// Set of (event ID, account) pairs refused by the organizer
+        blocked_attendees: Mapping<(u64, AccountId), ()>,
+        /// Mapping from (organizer, index) to event ID, in creation order
+        organizer_events: Mapping<(AccountId, u32), u64>,
+        /// Number of events per organizer
+        organizer_event_count: Mapping<AccountId, u32>,
+        /// Mapping from (event ID, index) to ticket ID, in mint order
+        event_tickets: Mapping<(u64, u32), u64>,
+        /// Number of tickets ever minted per event
+        event_ticket_count: Mapping<u64, u32>,
+        /// Running sales, refund, and attendance totals per event
+        event_stats: Mapping<u64, EventStats>,
+        /// Ring of the last `MAX_TICKET_HISTORY` owner changes per (ticket ID, slot)
+        ticket_history: Mapping<(u64, u32), OwnershipRecord>,
+        /// Number of owner changes ever recorded per ticket
+        ticket_history_count: Mapping<u64, u32>,
+        /// Metadata CID set for individual tickets, overriding the tier/event one
+        ticket_metadata: Mapping<u64, String>,
+        /// Mapping from (category, index) to event ID, in creation order
+        category_events: Mapping<(u8, u32), u64>,
+        /// Number of events per category
+        category_event_count: Mapping<u8, u32>,
+        /// Mapping from (tag, index) to event ID, in creation order
+        tag_events: Mapping<(String, u32), u64>,
+        /// Number of events per tag
+        tag_event_count: Mapping<String, u32>,
+        /// Mapping from pass ID to Pass
+        passes: Mapping<u64, Pass>,
+        /// Mapping from pass token ID to the pass bought
+        pass_tokens: Mapping<u64, PassToken>,
+        /// Set of (pass token ID, event ID) pairs already checked in
+        pass_uses: Mapping<(u64, u64), ()>,
+        /// Pass sales net of fees, held until the pass expires
+        pass_escrow: Mapping<u64, Balance>,
+        /// Mapping from series ID to Series
+        series: Mapping<u64, Series>,
+        /// Mapping from event ID to the series it is a session of
+        event_series: Mapping<u64, u64>,
+        /// Set of (ticket ID, day index) pairs checked in at multi-day events
+        day_checkins: Mapping<(u64, u32), ()>,
+        /// Add-ons per (event ID, add-on ID)
+        addons: Mapping<(u64, u32), AddOn>,
+        /// Number of add-ons per event
+        addon_count: Mapping<u64, u32>,
+        /// Add-ons bought per (ticket ID, add-on ID), and whether redeemed
+        ticket_addons: Mapping<(u64, u32), bool>,
+        /// Add-on purchases per ticket, net of fees, refunded if the event is cancelled
+        addon_spend: Mapping<u64, Balance>,
+        /// Mapping from event ID to its price in USD cents, for USD-priced events
+        usd_prices: Mapping<u64, u64>,
+        /// Set of accounts XCM purchases arrive from, e.g. parachain sovereign accounts
+        xcm_origins: Mapping<AccountId, ()>,
+        /// Set of (origin, XCM message ID) pairs already turned into tickets
+        xcm_deposits: Mapping<(AccountId, Hash), ()>,
+        /// Collection each exported ticket is mirrored into
+        exported_tickets: Mapping<u64, u32>,
+        /// Polls per (event ID, poll ID)
+        polls: Mapping<(u64, u32), Poll>,
+        /// Number of polls per event
+        poll_count: Mapping<u64, u32>,
+        /// Option voted for per (event ID, poll ID, ticket ID)
+        poll_votes: Mapping<(u64, u32, u64), u32>,
+        /// Loyalty points per account
+        points: Mapping<AccountId, u64>,
+        /// Loyalty point terms per event
+        loyalty_terms: Mapping<u64, LoyaltyTerms>,
+        /// Ticket transfer limits per event
+        transfer_limits: Mapping<u64, TransferLimits>,
+        /// Paid tickets sold per event in the latest block it sold in
+        block_mints: Mapping<u64, (BlockNumber, u32)>,
+        /// Block of each account's latest purchase
+        last_purchase_block: Mapping<AccountId, BlockNumber>,
+        /// Mapping from event ID to its per-person ticket quota, for
+        /// personhood-gated events
+        person_quotas: Mapping<u64, u32>,
+        /// Tickets bought per (event ID, person)
+        person_tickets: Mapping<(u64, Hash), u32>,
+        /// Mapping from event ID to the attester its attendees need a credential from
+        credential_attesters: Mapping<u64, AccountId>,
+        /// Credential presented per (event ID, account)
+        credentials: Mapping<(u64, AccountId), Credential>,
+        /// Claim hash checked when each ticket was first admitted
+        checked_credentials: Mapping<u64, Hash>,
+        /// Mapping from event ID to its access key hash, for private events
+        access_hashes: Mapping<u64, Hash>,
+        /// Set of private events left out of `get_active_events`
+        unlisted_events: Mapping<u64, ()>,
+        /// Set of (event ID, account) pairs that have given a private event's access key
+        event_access: Mapping<(u64, AccountId), ()>,
+        /// Mapping from event ID to its commit-reveal phases
+        commit_reveals: Mapping<u64, CommitReveal>,
+        /// Pending commitments per (event ID, account)
+        purchase_commitments: Mapping<(u64, AccountId), PurchaseCommitment>,
+        /// Lottery among the reveals of each commit-reveal event
+        commit_draws: Mapping<u64, Raffle>,
+        /// Mapping from event ID to the hash chain of its revealed salts
+        commit_entropy: Mapping<u64, Hash>,
+        /// Open seat auctions per (event ID, seat ID)
+        seat_auctions: Mapping<(u64, u32), SeatAuction>,
+        /// Outbid and refunded auction bids each bidder hasn't pulled yet
+        bid_refunds: Mapping<AccountId, Balance>,
+        /// Open offers per (ticket ID, buyer)
+        offers: Mapping<(u64, AccountId), Offer>,
+        /// Mapping from event ID to the hash of its raffle's draw seed
+        raffle_seeds: Mapping<u64, Hash>,
+        /// Mapping from event ID to the hash chain of its raffle entries
+        raffle_entropy: Mapping<u64, Hash>,
+        /// Tickets held back for raffle or commit-reveal winners who haven't
+        /// claimed, per event
+        unclaimed_wins: Mapping<u64, u32>,
+        /// Person each quota-counted ticket was charged to
+        ticket_persons: Mapping<u64, Hash>,
+        /// Person each raffle entry or purchase commitment was charged to,
+        /// per (event ID, account)
+        entry_persons: Mapping<(u64, AccountId), Hash>,
+        /// Platform settings and counters, versioned with the storage layout
+        state: ContractState,
+    }
+
+    /// Events emitted by the contract
+    #[ink(event)]
+    pub struct EventCreated {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        organizer: AccountId,
+        name: String,
+        price: Balance,
+        total_tickets: u32,
+    }
+
+    #[ink(event)]
+    pub struct OrganizerRegistered {
+        #[ink(topic)]
+        organizer: AccountId,
+        display_name: String,
+        metadata_cid: String,
+    }
+
+    #[ink(event)]
+    pub struct OrganizerVerified {
+        #[ink(topic)]
+        organizer: AccountId,
+        #[ink(topic)]
+        moderator: AccountId,
+        verified: bool,
+    }
+
+    #[ink(event)]
+    pub struct OrganizerBanned {
+        #[ink(topic)]
+        organizer: AccountId,
+        #[ink(topic)]
+        admin: AccountId,
+        banned: bool,
+    }
+
+    #[ink(event)]
+    pub struct TicketPurchased {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        buyer: AccountId,
+        tier_id: Option<u32>,
+        seat_id: Option<u32>,
+        price_paid: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TicketTransferred {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct TicketsBatchTransferred {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        ticket_ids: Vec<u64>,
+    }
+
+    #[ink(event)]
+    pub struct TicketUsed {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        event_id: u64,
+        uses_remaining: u32,
+    }
+
+    /// A ticket moved to another step of its lifecycle
+    ///
+    /// Cancelling or completing an event moves all its live tickets at
+    /// once; that's reported once, without a `ticket_id` or `owner`.
+    #[ink(event)]
+    pub struct TicketStateChanged {
+        #[ink(topic)]
+        ticket_id: Option<u64>,
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        owner: Option<AccountId>,
+        state: TicketState,
+    }
+
+    #[ink(event)]
+    pub struct PassCreated {
+        #[ink(topic)]
+        pass_id: u64,
+        #[ink(topic)]
+        organizer: AccountId,
+        price: Balance,
+        supply: u32,
+    }
+
+    #[ink(event)]
+    pub struct PassPurchased {
+        #[ink(topic)]
+        pass_token_id: u64,
+        #[ink(topic)]
+        pass_id: u64,
+        #[ink(topic)]
+        buyer: AccountId,
+        price: Balance,
+        fee: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PassUsed {
+        #[ink(topic)]
+        pass_token_id: u64,
+        #[ink(topic)]
+        event_id: u64,
+    }
+
+    #[ink(event)]
+    pub struct PassEarningsWithdrawn {
+        #[ink(topic)]
+        pass_id: u64,
+        #[ink(topic)]
+        organizer: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct AttendanceNftMinted {
+        #[ink(topic)]
+        token_id: u64,
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        owner: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct TicketUpgraded {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        event_id: u64,
+        from_tier: Option<u32>,
+        to_tier: u32,
+        amount_paid: Balance,
+    }
+
+    #[ink(event)]
+    pub struct EventCancelled {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        organizer: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct SeriesCreated {
+        #[ink(topic)]
+        series_id: u64,
+        #[ink(topic)]
+        organizer: AccountId,
+        event_ids: Vec<u64>,
+    }
+
+    #[ink(event)]
+    pub struct EventFrozen {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        moderator: AccountId,
+        frozen: bool,
+    }
+
+    #[ink(event)]
+    pub struct EventForceCancelled {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        moderator: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct EventMetadataUpdated {
+        #[ink(topic)]
+        event_id: u64,
+        name: String,
+        metadata_cid: String,
+    }
+
+    #[ink(event)]
+    pub struct TicketMetadataUpdated {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        event_id: u64,
+        metadata_cid: String,
+    }
+
+    #[ink(event)]
+    pub struct PriceUpdated {
+        #[ink(topic)]
+        event_id: u64,
+        old_price: Balance,
+        new_price: Balance,
+    }
+
+    #[ink(event)]
+    pub struct EventPublished {
+        #[ink(topic)]
+        event_id: u64,
+    }
+
+    #[ink(event)]
+    pub struct EventSalesPaused {
+        #[ink(topic)]
+        event_id: u64,
+        paused: bool,
+    }
+
+    #[ink(event)]
+    pub struct CapacityChanged {
+        #[ink(topic)]
+        event_id: u64,
+        old_total: u32,
+        new_total: u32,
+    }
+
+    #[ink(event)]
+    pub struct EventRescheduled {
+        #[ink(topic)]
+        event_id: u64,
+        old_start: u64,
+        old_end: u64,
+        new_start: u64,
+        new_end: u64,
+        refund_deadline: u64,
+    }
+
+    #[ink(event)]
+    pub struct UnclaimedFundsSwept {
+        #[ink(topic)]
+        event_id: u64,
+        treasury: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct EventCompleted {
+        #[ink(topic)]
+        event_id: u64,
+    }
+
+    #[ink(event)]
+    pub struct BondSettled {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        organizer: AccountId,
+        returned: Balance,
+        slashed: Balance,
+    }
+
+    #[ink(event)]
+    pub struct AddOnPurchased {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        event_id: u64,
+        addon_id: u32,
+        price: Balance,
+    }
+
+    #[ink(event)]
+    pub struct AddOnRedeemed {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        event_id: u64,
+        addon_id: u32,
+    }
+
+    #[ink(event)]
+    pub struct PointsEarned {
+        #[ink(topic)]
+        account: AccountId,
+        amount: u64,
+        balance: u64,
+    }
+
+    #[ink(event)]
+    pub struct PointsRedeemed {
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        event_id: u64,
+        amount: u64,
+        discount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct CredentialPresented {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        account: AccountId,
+        attester: AccountId,
+        claim_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct PollCreated {
+        #[ink(topic)]
+        event_id: u64,
+        poll_id: u32,
+        closes_at: u64,
+    }
+
+    #[ink(event)]
+    pub struct VoteCast {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        event_id: u64,
+        poll_id: u32,
+        option: u32,
+    }
+
+    #[ink(event)]
+    pub struct DonationReceived {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        donor: AccountId,
+        amount: Balance,
+        tip: bool,
+    }
+
+    #[ink(event)]
+    pub struct TicketRefunded {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        owner: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TicketCancelled {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        owner: AccountId,
+        refund_amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Subscribed {
+        #[ink(topic)]
+        subscriber: AccountId,
+        #[ink(topic)]
+        target: SubscriptionTarget,
+        endpoint_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct Unsubscribed {
+        #[ink(topic)]
+        subscriber: AccountId,
+        #[ink(topic)]
+        target: SubscriptionTarget,
+    }
+
+    /// Emitted once per subscription target affected by a change,
+    /// so off-chain notifiers can filter by topic and ping subscribers
+    #[ink(event)]
+    pub struct SubscriptionNotification {
+        #[ink(topic)]
+        target: SubscriptionTarget,
+        #[ink(topic)]
+        event_id: u64,
+        kind: NotificationKind,
+    }
+
+    #[ink(event)]
+    pub struct ReviewSubmitted {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        reviewer: AccountId,
+        rating: u8,
+        review_cid: String,
+    }
+
+    #[ink(event)]
+    pub struct ReferralCodeRegistered {
+        #[ink(topic)]
+        referrer: AccountId,
+        code: String,
+    }
+
+    /// PSP34 transfer event (`from` is `None` on mint, `to` is `None` on burn)
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        #[ink(topic)]
+        id: Id,
+    }
+
+    /// PSP34 approval event
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        #[ink(topic)]
+        id: Option<Id>,
+        approved: bool,
+    }
+
+    #[ink(event)]
+    pub struct TicketListed {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        seller: AccountId,
+        price: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TicketDelisted {
+        #[ink(topic)]
+        ticket_id: u64,
+    }
+
+    #[ink(event)]
+    pub struct TicketSold {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        seller: AccountId,
+        #[ink(topic)]
+        buyer: AccountId,
+        price: Balance,
+    }
+
+    #[ink(event)]
+    pub struct OfferMade {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        buyer: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct OfferCancelled {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        buyer: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RoyaltyPaid {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        organizer: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct FeeCollected {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        ticket_id: u64,
+        token: Option<AccountId>,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PlatformFeesWithdrawn {
+        #[ink(topic)]
+        treasury: AccountId,
+        token: Option<AccountId>,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PayoutCredited {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        payee: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct EarningsWithdrawn {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        organizer: AccountId,
+        amount: Balance,
+        total_withdrawn: Balance,
+    }
+
+    #[ink(event)]
+    pub struct DisputeFiled {
+        #[ink(topic)]
+        dispute_id: u64,
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        holder: AccountId,
+        ticket_id: u64,
+        reason_cid: String,
+    }
+
+    #[ink(event)]
+    pub struct DisputeResolved {
+        #[ink(topic)]
+        dispute_id: u64,
+        #[ink(topic)]
+        event_id: u64,
+        status: DisputeStatus,
+        refund: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Paused {
+        #[ink(topic)]
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Unpaused {
+        #[ink(topic)]
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RoleGranted {
+        #[ink(topic)]
+        role: Role,
+        #[ink(topic)]
+        account: AccountId,
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RoleRevoked {
+        #[ink(topic)]
+        role: Role,
+        #[ink(topic)]
+        account: AccountId,
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ContractUpgraded {
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct StorageMigrated {
+        from_version: u32,
+        to_version: u32,
+    }
+
+    #[ink(event)]
+    pub struct XcmTicketPurchased {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        message_id: Hash,
+        origin: AccountId,
+        beneficiary: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct TicketExported {
+        #[ink(topic)]
+        ticket_id: u64,
+        #[ink(topic)]
+        owner: AccountId,
+        collection: u32,
+    }
+
+    #[ink(event)]
+    pub struct CollectionMetadataUpdated {
+        #[ink(topic)]
+        admin: AccountId,
+        name: String,
+        symbol: String,
+        base_uri: String,
+    }
+
+    #[ink(event)]
+    pub struct RaffleEntered {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        entrant: AccountId,
+        deposit: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PurchaseCommitted {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        account: AccountId,
+        deposit: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PurchaseRevealed {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        account: AccountId,
+        entry: u32,
+    }
+
+    #[ink(event)]
+    pub struct DepositForfeited {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        account: AccountId,
+        deposit: Balance,
+    }
+
+    #[ink(event)]
+    pub struct CommitRevealDrawn {
+        #[ink(topic)]
+        event_id: u64,
+        revealed: u32,
+        winners: u32,
+    }
+
+    #[ink(event)]
+    pub struct RaffleDrawn {
+        #[ink(topic)]
+        event_id: u64,
+        entrants: u32,
+        winners: u32,
+    }
+
+    #[ink(event)]
+    pub struct SeatAuctionCreated {
+        #[ink(topic)]
+        event_id: u64,
+        seat_id: u32,
+        reserve: Balance,
+        end_time: u64,
+    }
+
+    #[ink(event)]
+    pub struct BidPlaced {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        bidder: AccountId,
+        seat_id: u32,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct SeatAuctionSettled {
+        #[ink(topic)]
+        event_id: u64,
+        seat_id: u32,
+        winner: Option<AccountId>,
+        ticket_id: Option<u64>,
+        price: Balance,
+    }
+
+    #[ink(event)]
+    pub struct WaitlistJoined {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        account: AccountId,
+        position: u32,
+    }
+
+    #[ink(event)]
+    pub struct WaitlistOffered {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        account: AccountId,
+        expires_at: u64,
+    }
+
+    #[ink(event)]
+    pub struct ReferralRewardEarned {
+        #[ink(topic)]
+        referrer: AccountId,
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        ticket_id: u64,
+        buyer: AccountId,
+        token: Option<AccountId>,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ReferralRewardsClaimed {
+        #[ink(topic)]
+        referrer: AccountId,
+        token: Option<AccountId>,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PromoCodeUsed {
+        #[ink(topic)]
+        event_id: u64,
+        #[ink(topic)]
+        code_hash: Hash,
+        #[ink(topic)]
+        ticket_id: u64,
+        buyer: AccountId,
+        discount: Balance,
+    }
+
+    impl Default for TicketDot {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl TicketDot {
+        /// Constructor - initializes the contract
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            let caller = Self::env().caller();
+            let mut roles = Mapping::default();
+            roles.insert((Role::Admin, caller), &());
+
+            Self {
+                event_counter: 0,
+                ticket_counter: 0,
+                events: Mapping::default(),
+                tickets: Mapping::default(),
+                owner_ticket_at: Mapping::default(),
+                owner_ticket_index: Mapping::default(),
+                owner_ticket_count: Mapping::default(),
+                roles,
+                subscriptions: Mapping::default(),
+                subscription_count: Mapping::default(),
+                attendance_records: Mapping::default(),
+                attendance_count: Mapping::default(),
+                attendance_nft_cids: Mapping::default(),
+                attendance: Mapping::default(),
+                reviews: Mapping::default(),
+                event_ratings: Mapping::default(),
+                organizer_ratings: Mapping::default(),
+                referral_codes: Mapping::default(),
+                referral_rewards: Mapping::default(),
+                user_summaries: Mapping::default(),
+                attended_events: Mapping::default(),
+                ticket_approvals: Mapping::default(),
+                operator_approvals: Mapping::default(),
+                seat_layouts: Mapping::default(),
+                booked_seats: Mapping::default(),
+                token_totals: Mapping::default(),
+                platform_token_fees: Mapping::default(),
+                allowlist: Mapping::default(),
+                allowlist_roots: Mapping::default(),
+                listings: Mapping::default(),
+                escrow_balance: Mapping::default(),
+                total_withdrawn: Mapping::default(),
+                raffles: Mapping::default(),
+                raffle_entries: Mapping::default(),
+                waitlists: Mapping::default(),
+                waitlist_queue: Mapping::default(),
+                waitlisted: Mapping::default(),
+                waitlist_offers: Mapping::default(),
+                promo_codes: Mapping::default(),
+                redeemed_vouchers: Mapping::default(),
+                checkin_staff: Mapping::default(),
+                used_challenges: Mapping::default(),
+                permit_nonces: Mapping::default(),
+                reschedule_refund_deadlines: Mapping::default(),
+                refund_deadlines: Mapping::default(),
+                event_bonds: Mapping::default(),
+                withdrawal_unlocks: Mapping::default(),
+                open_disputes: Mapping::default(),
+                disputes: Mapping::default(),
+                ticket_disputes: Mapping::default(),
+                payout_splits: Mapping::default(),
+                pending_payouts: Mapping::default(),
+                co_organizers: Mapping::default(),
+                organizers: Mapping::default(),
+                banned_organizers: Mapping::default(),
+                blocked_attendees: Mapping::default(),
+                organizer_events: Mapping::default(),
+                organizer_event_count: Mapping::default(),
+                event_tickets: Mapping::default(),
+                event_ticket_count: Mapping::default(),
+                event_stats: Mapping::default(),
+                ticket_history: Mapping::default(),
+                ticket_history_count: Mapping::default(),
+                ticket_metadata: Mapping::default(),
+                category_events: Mapping::default(),
+                category_event_count: Mapping::default(),
+                tag_events: Mapping::default(),
+                tag_event_count: Mapping::default(),
+                passes: Mapping::default(),
+                pass_tokens: Mapping::default(),
+                pass_uses: Mapping::default(),
+                pass_escrow: Mapping::default(),
+                series: Mapping::default(),
+                event_series: Mapping::default(),
+                day_checkins: Mapping::default(),
+                addons: Mapping::default(),
+                addon_count: Mapping::default(),
+                ticket_addons: Mapping::default(),
+                addon_spend: Mapping::default(),
+                usd_prices: Mapping::default(),
+                xcm_origins: Mapping::default(),
+                xcm_deposits: Mapping::default(),
+                exported_tickets: Mapping::default(),
+                polls: Mapping::default(),
+                poll_count: Mapping::default(),
+                poll_votes: Mapping::default(),
+                points: Mapping::default(),
+                loyalty_terms: Mapping::default(),
+                transfer_limits: Mapping::default(),
+                block_mints: Mapping::default(),
+                last_purchase_block: Mapping::default(),
+                person_quotas: Mapping::default(),
+                person_tickets: Mapping::default(),
+                credential_attesters: Mapping::default(),
+                credentials: Mapping::default(),
+                checked_credentials: Mapping::default(),
+                access_hashes: Mapping::default(),
+                unlisted_events: Mapping::default(),
+                event_access: Mapping::default(),
+                commit_reveals: Mapping::default(),
+                purchase_commitments: Mapping::default(),
+                commit_draws: Mapping::default(),
+                commit_entropy: Mapping::default(),
+                seat_auctions: Mapping::default(),
+                bid_refunds: Mapping::default(),
+                offers: Mapping::default(),
+                raffle_seeds: Mapping::default(),
+                raffle_entropy: Mapping::default(),
+                unclaimed_wins: Mapping::default(),
+                ticket_persons: Mapping::default(),
+                entry_persons: Mapping::default(),
+                state: ContractState::new(caller),
+            }
+        }
+
+        /// Create a new event
+        /// 
+        /// # Security
+        /// - Validates all input parameters to prevent resource exhaustion
+        /// - Enforces maximum tickets per event to prevent DoS
+        /// - Validates minimum ticket price to prevent spam
+        /// - Validates string lengths to prevent storage bloat
+        /// 
+        /// # Arguments
+        /// * `name` - Event name (e.g., "Polkadot Conference 2025")
+        /// * `price` - Ticket price in native token (e.g., 1000000000000 for 1 SBY)
+        /// * `total_tickets` - Total number of tickets available
+        /// * `metadata_cid` - IPFS CID containing event metadata (description, image, venue, etc.)
+        /// 
+        /// The organizer must attach the platform's event bond (see
+        /// `get_event_bond_requirement`), returned when the event completes.
+        ///
+        /// # Returns
+        /// - `Ok(event_id)` - The ID of the newly created event
+        /// - `Err(Error::InvalidInput)` - Input validation failed
+        /// - `Err(Error::InsufficientPayment)` - Payment != event bond
+        #[ink(message, payable)]
+        pub fn create_event(
+            &mut self,
+            name: String,
+            price: Balance,
+            total_tickets: u32,
+            metadata_cid: String,
+        ) -> Result<u64, Error> {
+            self.create_event_with_options(
+                name,
+                price,
+                total_tickets,
+                metadata_cid,
+                EventOptions::default(),
+            )
+        }
+
+        /// Create a new event with optional settings
+        ///
+        /// Same as `create_event`, plus the settings in `options`
+        /// (sale and event schedule, resale royalty and price cap, soulbound mode,
+        /// PSP22 payment token, allowlist presale).
+        ///
+        /// For PSP22 events, `price` is denominated in the token and buyers must
+        /// approve this contract to spend it before purchasing.
+        ///
+        /// # Returns
+        /// - `Ok(event_id)` - The ID of the newly created event
+        /// - `Err(Error::InvalidInput)` - Input validation failed
+        /// - `Err(Error::InsufficientPayment)` - Payment != event bond
+        #[ink(message, payable)]
+        pub fn create_event_with_options(
+            &mut self,
+            name: String,
+            price: Balance,
+            total_tickets: u32,
+            metadata_cid: String,
+            options: EventOptions,
+        ) -> Result<u64, Error> {
+            let bond = self.env().transferred_value();
+            self.insert_event(name, price, total_tickets, metadata_cid, options, bond)
+        }
+
+        /// Validate and store a new event, locking `bond` as its organizer bond
+        fn insert_event(
+            &mut self,
+            name: String,
+            price: Balance,
+            total_tickets: u32,
+            metadata_cid: String,
+            options: EventOptions,
+            bond: Balance,
+        ) -> Result<u64, Error> {
+            self.ensure_not_paused()?;
+
+            // Validate input parameters to prevent resource exhaustion and storage bloat
+            if name.is_empty() || name.len() > self.state.config.max_event_name_length as usize {
+                return Err(Error::InvalidInput);
+            }
+            if metadata_cid.is_empty() || metadata_cid.len() > self.state.config.max_metadata_cid_length as usize {
+                return Err(Error::InvalidInput);
+            }
+            if total_tickets == 0 || total_tickets > self.state.config.max_tickets_per_event {
+                return Err(Error::InvalidInput);
+            }
+            if price < self.state.config.min_ticket_price {
+                return Err(Error::InvalidInput);
+            }
+            if !options.schedule.is_valid() || options.royalty_bps > MAX_ROYALTY_BPS {
+                return Err(Error::InvalidInput);
+            }
+            // A resale cap can't force tickets below face value
+            if options.max_resale_multiplier_bps != 0
+                && (options.max_resale_multiplier_bps as Balance) < BPS_DENOMINATOR
+            {
+                return Err(Error::InvalidInput);
+            }
+            // Price phases must be few, valid prices, and strictly ordered
+            if options.price_phases.len() > MAX_PRICE_PHASES
+                || options.price_phases.iter().any(|phase| phase.price < self.state.config.min_ticket_price)
+                || options.price_phases.windows(2).any(|pair| pair[0].start >= pair[1].start)
+            {
+                return Err(Error::InvalidInput);
+            }
+            if let Some(auction) = &options.dutch_auction {
+                if !auction.is_valid(self.state.config.min_ticket_price) || !options.price_phases.is_empty() {
+                    return Err(Error::InvalidInput);
+                }
+            }
+            if options.uses_per_ticket == 0 || options.uses_per_ticket > MAX_USES_PER_TICKET {
+                return Err(Error::InvalidInput);
+            }
+            // Days are distinct and in order, one check-in each
+            if options.days.len() > MAX_USES_PER_TICKET as usize
+                || options.days.windows(2).any(|pair| pair[0] >= pair[1])
+            {
+                return Err(Error::InvalidInput);
+            }
+            // Refund rules count down to a known start, with deadlines in order
+            if !options.refund_policy.is_empty()
+                && (options.schedule.event_start == 0
+                    || options.refund_policy.len() > MAX_REFUND_RULES
+                    || options
+                        .refund_policy
+                        .iter()
+                        .any(|rule| rule.refund_bps as Balance > BPS_DENOMINATOR)
+                    || options
+                        .refund_policy
+                        .windows(2)
+                        .any(|pair| pair[0].min_time_before_start <= pair[1].min_time_before_start))
+            {
+                return Err(Error::InvalidInput);
+            }
+            // Payout tranches vest in order and release everything
+            if !options.payout_schedule.is_empty()
+                && (options.payout_schedule.len() > MAX_PAYOUT_TRANCHES
+                    || options
+                        .payout_schedule
+                        .iter()
+                        .map(|tranche| Balance::from(tranche.share_bps))
+                        .sum::<Balance>()
+                        != BPS_DENOMINATOR
+                    || options
+                        .payout_schedule
+                        .windows(2)
+                        .any(|pair| pair[0].delay >= pair[1].delay))
+            {
+                return Err(Error::InvalidInput);
+            }
+            // A PSP22 gate needs a balance to check against
+            if let Some(TokenGate::Psp22 { min_balance: 0, .. }) = options.token_gate {
+                return Err(Error::InvalidInput);
+            }
+            // A bonding curve sets the price itself
+            if let Some(curve) = &options.bonding_curve {
+                if !curve.is_valid() || options.dutch_auction.is_some() {
+                    return Err(Error::InvalidInput);
+                }
+            }
+            // Tags must be few, short, and distinct
+            if options.tags.len() > MAX_TAGS_PER_EVENT
+                || options.tags.iter().any(|tag| tag.is_empty() || tag.len() > MAX_TAG_LENGTH)
+                || options
+                    .tags
+                    .iter()
+                    .enumerate()
+                    .any(|(index, tag)| options.tags[..index].contains(tag))
+            {
+                return Err(Error::InvalidInput);
+            }
+            // USD prices are converted to the native token as they're paid
+            if options.usd_price_cents.is_some()
+                && (options.usd_price_cents == Some(0)
+                    || options.payment_token.is_some()
+                    || options.dutch_auction.is_some()
+                    || !options.price_phases.is_empty()
+                    || options.bonding_curve.is_some()
+                    || options.raffle_end != 0)
+            {
+                return Err(Error::InvalidInput);
+            }
+            // Commit-reveal deposits are the fixed base price, and like
+            // raffles the draw needs a seed committed before anyone commits
+            if let Some(phases) = options.commit_reveal {
+                if phases.commit_end <= self.env().block_timestamp()
+                    || phases.reveal_end <= phases.commit_end
+                    || options.raffle_seed_hash.is_none()
+                    || options.raffle_end != 0
+                    || options.dutch_auction.is_some()
+                    || !options.price_phases.is_empty()
+                    || options.bonding_curve.is_some()
+                    || options.usd_price_cents.is_some()
+                {
+                    return Err(Error::InvalidInput);
+                }
+            }
+            // Only private events can be listed explicitly
+            if options.listed && options.access_hash.is_none() {
+                return Err(Error::InvalidInput);
+            }
+            // Raffle deposits are the fixed base price, paid before the draw,
+            // and the draw needs a seed committed before anyone enters
+            if options.raffle_end != 0
+                && (options.raffle_end <= self.env().block_timestamp()
+                    || options.raffle_seed_hash.is_none()
+                    || options.dutch_auction.is_some()
+                    || !options.price_phases.is_empty()
+                    || options.bonding_curve.is_some())
+            {
+                return Err(Error::InvalidInput);
+            }
+
+            // The platform may only accept events from known organizers
+            let caller = self.env().caller();
+            if self.banned_organizers.contains(caller) {
+                return Err(Error::OrganizerBanned);
+            }
+            if self.state.require_organizer_registration && !self.organizers.contains(caller) {
+                return Err(Error::OrganizerNotRegistered);
+            }
+
+            // Lock the organizer's bond
+            if bond != self.state.event_bond {
+                return Err(Error::InsufficientPayment {
+                    expected: self.state.event_bond,
+                    got: bond,
+                });
+            }
+
+            let event_id = self.event_counter;
+
+            // Create new event
+            let event = Event {
+                id: event_id,
+                name: name.clone(),
+                organizer: caller,
+                price,
+                total_tickets,
+                available_tickets: total_tickets,
+                timestamp: self.env().block_timestamp(),
+                metadata_cid,
+                status: if options.draft {
+                    EventStatus::Draft
+                } else {
+                    EventStatus::OnSale
+                },
+                referrals_enabled: false,
+                tiers: Vec::new(),
+                schedule: options.schedule,
+                royalty_bps: options.royalty_bps,
+                max_resale_multiplier_bps: options.max_resale_multiplier_bps,
+                transferable: options.transferable,
+                payment_token: options.payment_token,
+                presale_end: options.presale_end,
+                price_phases: options.price_phases,
+                dutch_auction: options.dutch_auction,
+                referral_reward_bps: 0,
+                comp_quota: 0,
+                comps_issued: 0,
+                uses_per_ticket: if options.days.is_empty() {
+                    options.uses_per_ticket
+                } else {
+                    options.days.len() as u32
+                },
+                refund_policy: options.refund_policy,
+                frozen: false,
+                payout_schedule: options.payout_schedule,
+                token_gate: options.token_gate,
+                category: options.category,
+                tags: options.tags,
+                days: options.days,
+                bonding_curve: options.bonding_curve,
+            };
+
+            // Store event
+            self.events.insert(event_id, &event);
+            self.event_counter = self.event_counter.saturating_add(1);
+            let organizer_index = self.organizer_event_count.get(caller).unwrap_or(0);
+            self.organizer_events.insert((caller, organizer_index), &event_id);
+            self.organizer_event_count.insert(caller, &organizer_index.saturating_add(1));
+            let category_index = self.category_event_count.get(event.category).unwrap_or(0);
+            self.category_events.insert((event.category, category_index), &event_id);
+            self.category_event_count.insert(event.category, &category_index.saturating_add(1));
+            for tag in &event.tags {
+                let tag_index = self.tag_event_count.get(tag).unwrap_or(0);
+                self.tag_events.insert((tag.clone(), tag_index), &event_id);
+                self.tag_event_count.insert(tag, &tag_index.saturating_add(1));
+            }
+            if bond > 0 {
+                self.event_bonds.insert(event_id, &bond);
+            }
+            if let Some(usd_price_cents) = options.usd_price_cents {
+                self.usd_prices.insert(event_id, &usd_price_cents);
+            }
+            if options.transfer_limits != TransferLimits::default() {
+                self.transfer_limits.insert(event_id, &options.transfer_limits);
+            }
+            if options.tickets_per_person != 0 {
+                self.person_quotas.insert(event_id, &options.tickets_per_person);
+            }
+            if let Some(attester) = options.credential_attester {
+                self.credential_attesters.insert(event_id, &attester);
+            }
+            if let Some(phases) = options.commit_reveal {
+                if let Some(seed_hash) = options.raffle_seed_hash {
+                    self.raffle_seeds.insert(event_id, &seed_hash);
+                }
+                self.commit_reveals.insert(event_id, &phases);
+                self.commit_draws.insert(
+                    event_id,
+                    &Raffle {
+                        entry_end: phases.reveal_end,
+                        entrants: 0,
+                        drawn: false,
+                        offset: 0,
+                        winners: 0,
+                    },
+                );
+            }
+            if let Some(access_hash) = options.access_hash {
+                self.access_hashes.insert(event_id, &access_hash);
+                if !options.listed {
+                    self.unlisted_events.insert(event_id, &());
+                }
+            }
+
+            if options.raffle_end != 0 {
+                if let Some(seed_hash) = options.raffle_seed_hash {
+                    self.raffle_seeds.insert(event_id, &seed_hash);
+                }
+                self.raffles.insert(
+                    event_id,
+                    &Raffle {
+                        entry_end: options.raffle_end,
+                        entrants: 0,
+                        drawn: false,
+                        offset: 0,
+                        winners: 0,
+                    },
+                );
+            }
+
+            // Emit event
+            self.env().emit_event(EventCreated {
+                event_id,
+                organizer: caller,
+                name,
+                price,
+                total_tickets,
+            });
+            self.notify(&event, NotificationKind::EventCreated);
+
+            Ok(event_id)
+        }
+
+        /// Create a recurring event, one event per session
+        ///
+        /// Every session is a separate event created from the same settings,
+        /// so buyers pick a session by buying a ticket for its event. The
+        /// payment must cover the organizer bond of every session.
+        ///
+        /// # Arguments
+        /// * `options` - Template for every session; its event window is
+        ///   moved to start at each session's time, keeping its length
+        /// * `sessions` - Start time of each session, in ascending order
+        ///
+        /// # Returns
+        /// - `Ok(series_id)` - The ID of the series
+        /// - `Err(Error::InvalidInput)` - No sessions, too many, or out of order
+        #[ink(message, payable)]
+        pub fn create_event_series(
+            &mut self,
+            name: String,
+            price: Balance,
+            total_tickets: u32,
+            metadata_cid: String,
+            options: EventOptions,
+            sessions: Vec<u64>,
+        ) -> Result<u64, Error> {
+            self.ensure_not_paused()?;
+            let now = self.env().block_timestamp();
+            if sessions.is_empty()
+                || sessions.len() > MAX_SERIES_SESSIONS
+                || sessions[0] <= now
+                || sessions.windows(2).any(|pair| pair[0] >= pair[1])
+            {
+                return Err(Error::InvalidInput);
+            }
+            let bonds = self.state.event_bond.saturating_mul(sessions.len() as Balance);
+            let payment = self.env().transferred_value();
+            if payment != bonds {
+                return Err(Error::InsufficientPayment {
+                    expected: bonds,
+                    got: payment,
+                });
+            }
+
+            // Shift the template's event window onto each session
+            let template = options.schedule;
+            let length = if template.event_end == 0 {
+                0
+            } else {
+                template.event_end.saturating_sub(template.event_start)
+            };
+            let mut event_ids = Vec::with_capacity(sessions.len());
+            for start in sessions {
+                let mut session = options.clone();
+                session.schedule.event_start = start;
+                if length != 0 {
+                    session.schedule.event_end = start.saturating_add(length);
+                }
+                let event_id = self.insert_event(
+                    name.clone(),
+                    price,
+                    total_tickets,
+                    metadata_cid.clone(),
+                    session,
+                    self.state.event_bond,
+                )?;
+                event_ids.push(event_id);
+            }
+
+            let series_id = self.state.series_counter;
+            for event_id in &event_ids {
+                self.event_series.insert(event_id, &series_id);
+            }
+            let organizer = self.env().caller();
+            self.series.insert(
+                series_id,
+                &Series {
+                    id: series_id,
+                    organizer,
+                    name,
+                    event_ids: event_ids.clone(),
+                },
+            );
+            self.state.series_counter = self.state.series_counter.saturating_add(1);
+
+            self.env().emit_event(SeriesCreated {
+                series_id,
+                organizer,
+                event_ids,
+            });
+
+            Ok(series_id)
+        }
+
+        /// Get series details
+        #[ink(message)]
+        pub fn get_series(&self, series_id: u64) -> Option<Series> {
+            self.series.get(series_id)
+        }
+
+        /// Get the series an event is a session of
+        #[ink(message)]
+        pub fn get_event_series(&self, event_id: u64) -> Option<u64> {
+            self.event_series.get(event_id)
+        }
+
+        /// Get every session of a series, earliest first
+        #[ink(message)]
+        pub fn get_series_events(&self, series_id: u64) -> Vec<Event> {
+            self.series
+                .get(series_id)
+                .map(|series| {
+                    series
+                        .event_ids
+                        .into_iter()
+                        .filter_map(|event_id| self.events.get(event_id))
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+
+        /// Register or update the caller's organizer profile
+        ///
+        /// Updating a verified profile clears its verification, so a vetted
+        /// name can't be swapped for another.
+        ///
+        /// # Arguments
+        /// * `display_name` - Name shown next to the organizer's events
+        /// * `metadata_cid` - IPFS CID of the full profile (bio, logo, links)
+        /// * `contact_hash` - Hash of off-chain contact details, so support
+        ///   can verify them without publishing them
+        #[ink(message)]
+        pub fn register_organizer(
+            &mut self,
+            display_name: String,
+            metadata_cid: String,
+            contact_hash: Hash,
+        ) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            if display_name.is_empty()
+                || display_name.len() > MAX_DISPLAY_NAME_LENGTH
+                || metadata_cid.len() > self.state.config.max_metadata_cid_length as usize
+            {
+                return Err(Error::InvalidInput);
+            }
+
+            // Keep the original registration time on updates; verification
+            // has to be granted again
+            let caller = self.env().caller();
+            let registered_at = self
+                .organizers
+                .get(caller)
+                .map_or(self.env().block_timestamp(), |profile| profile.registered_at);
+            self.organizers.insert(
+                caller,
+                &OrganizerProfile {
+                    display_name: display_name.clone(),
+                    metadata_cid: metadata_cid.clone(),
+                    contact_hash,
+                    registered_at,
+                    is_verified: false,
+                },
+            );
+
+            self.env().emit_event(OrganizerRegistered {
+                organizer: caller,
+                display_name,
+                metadata_cid,
+            });
+
+            Ok(())
+        }
+
+        /// Get an organizer's profile
+        #[ink(message)]
+        pub fn get_organizer(&self, account: AccountId) -> Option<OrganizerProfile> {
+            self.organizers.get(account)
+        }
+
+        /// Mark an organizer as vetted by the platform
+        /// Only moderators (and admins) can verify organizers
+        #[ink(message)]
+        pub fn verify_organizer(&mut self, account: AccountId) -> Result<(), Error> {
+            self.set_organizer_verified(account, true)
+        }
+
+        /// Remove an organizer's verified badge
+        /// Only moderators (and admins) can revoke verification
+        #[ink(message)]
+        pub fn revoke_verification(&mut self, account: AccountId) -> Result<(), Error> {
+            self.set_organizer_verified(account, false)
+        }
+
+        /// Check whether an account is a verified organizer
+        #[ink(message)]
+        pub fn is_verified_organizer(&self, account: AccountId) -> bool {
+            self.organizers.get(account).is_some_and(|profile| profile.is_verified)
+        }
+
+        /// Get an event together with its organizer's profile, if registered
+        ///
+        /// Lets frontends show who is behind an event, and whether they're
+        /// verified, in a single query.
+        #[ink(message)]
+        pub fn get_event_with_organizer(&self, event_id: u64) -> Option<(Event, Option<OrganizerProfile>)> {
+            let event = self.events.get(event_id)?;
+            let profile = self.organizers.get(event.organizer);
+            Some((event, profile))
+        }
+
+        /// Require a registered organizer profile to create events
+        /// Only admins can change this
+        #[ink(message)]
+        pub fn set_organizer_registration_required(&mut self, required: bool) -> Result<(), Error> {
+            self.ensure_role(Role::Admin)?;
+            self.state.require_organizer_registration = required;
+            Ok(())
+        }
+
+        /// Check whether creating events requires a registered organizer profile
+        #[ink(message)]
+        pub fn is_organizer_registration_required(&self) -> bool {
+            self.state.require_organizer_registration
+        }
+
+        /// Buy several general admission tickets in one call
+        ///
+        /// Either all tickets are minted or none are.
+        ///
+        /// # Arguments
+        /// * `event_id` - ID of the event to buy tickets for
+        /// * `quantity` - Number of tickets, up to the configured per-call maximum
+        ///
+        /// # Returns
+        /// - `Ok(ticket_ids)` - IDs of the minted tickets, in order
+        /// - `Err(Error::InvalidInput)` - Quantity is 0
+        /// - `Err(Error::PurchaseLimitReached)` - Quantity is above the per-call maximum
+        /// - `Err(Error::InsufficientPayment)` - Payment != price * quantity
+        #[ink(message, payable)]
+        pub fn buy_tickets(&mut self, event_id: u64, quantity: u32) -> Result<Vec<u64>, Error> {
+            let caller = self.env().caller();
+
+            if quantity == 0 {
+                return Err(Error::InvalidInput);
+            }
+            if quantity > self.state.max_tickets_per_purchase {
+                return Err(Error::PurchaseLimitReached {
+                    limit: self.state.max_tickets_per_purchase,
+                });
+            }
+
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            self.ensure_on_sale(&event, caller, quantity)?;
+            let person = self.claim_person_quota(event_id, caller, quantity)?;
+
+            // Validate exact payment for the whole batch; on a bonding curve
+            // each ticket costs more than the one before
+            let now = self.env().block_timestamp();
+            let prices = (0..quantity)
+                .map(|offset| self.base_price(&event, now, offset))
+                .collect::<Result<Vec<Balance>, Error>>()?;
+            let total_price = prices
+                .iter()
+                .try_fold(0, |total: Balance, price| total.checked_add(*price))
+                .ok_or(Error::InvalidInput)?;
+            self.collect_payment(&event, caller, total_price)?;
+
+            let mut ticket_ids = Vec::with_capacity(quantity as usize);
+            for price in prices {
+                let ticket_id = self.mint_ticket(&mut event, caller, None, None, price)?;
+                self.record_ticket_person(ticket_id, person);
+                ticket_ids.push(ticket_id);
+            }
+
+            Ok(ticket_ids)
+        }
+
+        /// Buy a ticket in a specific tier of an event
+        ///
+        /// Works like `buy_ticket`, charging the tier price and drawing
+        /// from the tier's own capacity.
+        ///
+        /// # Returns
+        /// - `Err(Error::TierNotFound)` - Tier doesn't exist on the event
+        /// - `Err(Error::SoldOut)` - Tier has no tickets left
+        #[ink(message, payable)]
+        pub fn buy_ticket_tier(&mut self, event_id: u64, tier_id: u32) -> Result<u64, Error> {
+            self.purchase_ticket(event_id, Some(tier_id), None)
+        }
+
+        /// Buy a specific seat at a reserved-seating event
+        ///
+        /// # Returns
+        /// - `Err(Error::InvalidSeat)` - Seat isn't in the event's layout
+        /// - `Err(Error::SeatTaken)` - Seat is already booked
+        #[ink(message, payable)]
+        pub fn buy_ticket_seat(&mut self, event_id: u64, seat_id: u32) -> Result<u64, Error> {
+            self.purchase_ticket(event_id, None, Some(seat_id))
+        }
+
+        /// Buy a ticket crediting the owner of a referral code
+        ///
+        /// Works like `buy_ticket_with_referrer`, with the referrer looked up
+        /// from their code.
+        ///
+        /// # Returns
+        /// - `Err(Error::ReferralCodeNotFound)` - Unknown referral code
+        /// - `Err(Error::SelfReferral)` - Caller owns the referral code
+        /// - `Err(Error::ReferralsDisabled)` - Event didn't opt in
+        #[ink(message, payable)]
+        pub fn buy_ticket_with_referral(
+            &mut self,
+            event_id: u64,
+            code: String,
+        ) -> Result<u64, Error> {
+            let referrer = self.referral_codes.get(&code).ok_or(Error::ReferralCodeNotFound)?;
+            self.purchase_with_referrer(event_id, referrer)
+        }
+
+        /// Buy a ticket crediting `referrer` with a share of the sale
+        ///
+        /// Works like `buy_ticket`. The referrer earns the event's referral
+        /// reward out of the organizer's proceeds, plus the platform's
+        /// referral share of the sale's fee (see `set_referral_fee_share`),
+        /// into a balance they claim with `claim_referral_rewards`.
+        ///
+        /// # Returns
+        /// - `Err(Error::SelfReferral)` - Caller is the referrer
+        /// - `Err(Error::ReferralsDisabled)` - Event didn't opt in
+        #[ink(message, payable)]
+        pub fn buy_ticket_with_referrer(
+            &mut self,
+            event_id: u64,
+            referrer: AccountId,
+        ) -> Result<u64, Error> {
+            self.purchase_with_referrer(event_id, referrer)
+        }
+
+        /// Pay out the caller's referral rewards in the native token
+        #[ink(message)]
+        pub fn claim_referral_rewards(&mut self) -> Result<Balance, Error> {
+            self.claim_referral_rewards_in(None)
+        }
+
+        /// Pay out the caller's referral rewards earned in a PSP22 token
+        #[ink(message)]
+        pub fn claim_referral_token_rewards(&mut self, token: AccountId) -> Result<Balance, Error> {
+            self.claim_referral_rewards_in(Some(token))
+        }
+
+        /// Buy a ticket for a private event
+        ///
+        /// Works like `buy_ticket` once `access_key` is checked against the
+        /// event's access hash. The caller is then remembered, so later
+        /// purchases (tiers, seats, promo codes) need no key.
+        ///
+        /// # Arguments
+        /// * `event_id` - ID of the event
+        /// * `access_key` - Shared secret handed out off-chain by the organizer
+        ///
+        /// # Returns
+        /// - `Err(Error::InvalidInput)` - Event isn't private
+        /// - `Err(Error::InvalidAccessKey)` - Key doesn't match the event's hash
+        #[ink(message, payable)]
+        pub fn buy_ticket_private(
+            &mut self,
+            event_id: u64,
+            access_key: Vec<u8>,
+        ) -> Result<u64, Error> {
+            let caller = self.env().caller();
+
+            let access_hash = self.access_hashes.get(event_id).ok_or(Error::InvalidInput)?;
+            if access_key_hash(&access_key) != access_hash {
+                return Err(Error::InvalidAccessKey);
+            }
+            self.event_access.insert((event_id, caller), &());
+
+            self.purchase_ticket(event_id, None, None)
+        }
+
+        /// Check if an event is private, selling only to holders of its access key
+        #[ink(message)]
+        pub fn is_private_event(&self, event_id: u64) -> bool {
+            self.access_hashes.contains(event_id)
+        }
+
+        /// Buy a ticket during the presale with a Merkle allowlist proof
+        ///
+        /// Works like `buy_ticket`, but the caller may buy before the
+        /// presale ends by proving their account is in the event's Merkle
+        /// allowlist (see `set_allowlist_root`). Every other purchase
+        /// restriction of the event still applies.
+        ///
+        /// # Arguments
+        /// * `event_id` - ID of the event
+        /// * `proof` - Sibling hashes from the caller's leaf up to the root
+        ///
+        /// # Returns
+        /// - `Err(Error::InvalidProof)` - No root set or the proof doesn't match it
+        #[ink(message, payable)]
+        pub fn buy_ticket_with_proof(&mut self, event_id: u64, proof: Vec<Hash>) -> Result<u64, Error> {
+            let caller = self.env().caller();
+
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            self.ensure_selling(&event)?;
+
+            // Verify the caller against the allowlist root
+            if proof.len() > MAX_PROOF_LENGTH {
+                return Err(Error::InvalidInput);
+            }
+            let root = self.allowlist_roots.get(event_id).ok_or(Error::InvalidProof)?;
+            if !verify_merkle_proof(root, merkle_leaf(&caller), &proof) {
+                return Err(Error::InvalidProof);
+            }
+
+            // The proof stands in for the stored allowlist only
+            self.ensure_eligible_as(&event, caller, true)?;
+            self.ensure_within_rate_limits(event_id, caller, 1)?;
+            let person = self.claim_person_quota(event_id, caller, 1)?;
+
+            let price = self.base_price(&event, self.env().block_timestamp(), 0)?;
+            self.collect_payment(&event, caller, price)?;
+            let ticket_id = self.mint_ticket(&mut event, caller, None, None, price)?;
+            self.record_ticket_person(ticket_id, person);
+            Ok(ticket_id)
+        }
+
+        /// Buy a ticket for a user on another parachain
+        ///
+        /// Called by a trusted XCM origin (see `set_xcm_origin`) executing a
+        /// `Transact` after the user's asset transfer landed in its account,
+        /// so users on e.g. Asset Hub or Moonbeam can buy without bridging
+        /// manually.
+        ///
+        /// The contract can't see the XCM message itself: it trusts the
+        /// origin to forward the message's deposit as the attached native
+        /// value and to pass that message's ID. The deposit must cover the
+        /// price, and any surplus goes back to the origin, so PSP22-priced
+        /// events can't be bought this way. Each message ID buys at most one
+        /// ticket per origin. The ticket is minted to `beneficiary`, who must
+        /// meet every purchase restriction of the event.
+        ///
+        /// # Arguments
+        /// * `beneficiary` - Account the ticket is minted to
+        /// * `message_id` - ID of the origin's XCM message carrying the deposit
+        ///
+        /// # Returns
+        /// - `Err(Error::UntrustedXcmOrigin)` - Caller isn't a trusted XCM origin
+        /// - `Err(Error::XcmDepositMissing)` - No native deposit is attached
+        /// - `Err(Error::XcmDepositAlreadyProcessed)` - The message already bought a ticket
+        /// - `Err(Error::InsufficientPayment)` - The deposit doesn't cover the price
+        #[ink(message, payable)]
+        pub fn buy_ticket_xcm(
+            &mut self,
+            event_id: u64,
+            beneficiary: AccountId,
+            message_id: Hash,
+        ) -> Result<u64, Error> {
+            let caller = self.env().caller();
+            if !self.xcm_origins.contains(caller) {
+                return Err(Error::UntrustedXcmOrigin);
+            }
+
+            // The deposit arrives as native value; without one the purchase
+            // would be paid from the origin's own funds
+            if self.env().transferred_value() == 0 {
+                return Err(Error::XcmDepositMissing);
+            }
+
+            // Each deposit buys one ticket
+            if self.xcm_deposits.contains((caller, message_id)) {
+                return Err(Error::XcmDepositAlreadyProcessed);
+            }
+
+            let ticket_id = self.purchase_ticket_for(event_id, beneficiary, None, None)?;
+            self.xcm_deposits.insert((caller, message_id), &());
+
+            self.env().emit_event(XcmTicketPurchased {
+                ticket_id,
+                message_id,
+                origin: caller,
+                beneficiary,
+            });
+
+            Ok(ticket_id)
+        }
+
+        /// Enter an event's raffle by depositing the ticket price
+        ///
+        /// Winners claim their ticket with `claim_raffle_ticket`; everyone
+        /// else gets the deposit back with `claim_raffle_refund`.
+        ///
+        /// # Returns
+        /// - `Err(Error::NoRaffle)` - Event doesn't allocate by raffle
+        /// - `Err(Error::RaffleClosed)` - Entries closed
+        /// - `Err(Error::AlreadyEntered)` - One entry per account
+        #[ink(message, payable)]
+        pub fn enter_raffle(&mut self, event_id: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            if !event.is_live() {
+                return Err(Error::EventNotActive);
+            }
+
+            let mut raffle = self.raffles.get(event_id).ok_or(Error::NoRaffle)?;
+            if self.env().block_timestamp() >= raffle.entry_end {
+                return Err(Error::RaffleClosed);
+            }
+            if self.raffle_entries.contains((event_id, caller)) {
+                return Err(Error::AlreadyEntered);
+            }
+            if self.blocked_attendees.contains((event_id, caller)) {
+                return Err(Error::AttendeeBlocked);
+            }
+            if event.token_gate.is_some_and(|gate| !gate.is_met_by(caller)) {
+                return Err(Error::NotTokenHolder);
+            }
+            self.ensure_event_access(event_id, caller)?;
+            self.check_credential(event_id, caller)?;
+            if let Some(person) = self.claim_person_quota(event_id, caller, 1)? {
+                self.entry_persons.insert((event_id, caller), &person);
+            }
+
+            // Hold the deposit until the draw
+            let deposit = event.price;
+            self.collect_payment(&event, caller, deposit)?;
+
+            // Chain every entry into the draw's entropy
+            let mut entropy = [0u8; 32];
+            ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+                &(self.raffle_entropy.get(event_id), caller, raffle.entrants),
+                &mut entropy,
+            );
+            self.raffle_entropy.insert(event_id, &Hash::from(entropy));
+
+            self.raffle_entries.insert(
+                (event_id, caller),
+                &RaffleEntry {
+                    index: raffle.entrants,
+                    deposit,
+                },
+            );
+            raffle.entrants = raffle.entrants.checked_add(1).ok_or(Error::InvalidInput)?;
+            self.raffles.insert(event_id, &raffle);
+
+            self.env().emit_event(RaffleEntered {
+                event_id,
+                entrant: caller,
+                deposit,
+            });
+
+            Ok(())
+        }
+
+        /// Commit to buying a ticket in a commit-reveal sale, depositing the ticket price
+        ///
+        /// `commitment` is the blake2-256 hash of the SCALE-encoded
+        /// `(caller, salt)` for a random `salt` kept secret until
+        /// `reveal_purchase`. The lottery is seeded by the organizer's
+        /// committed seed together with every revealed salt, so nobody can
+        /// predict it while commitments are open, and a committer who sees
+        /// the other reveals can't steer it by withholding their own.
+        /// Losing deposits are returned by `claim_commit_refund`; deposits
+        /// not revealed in time are forfeited (see `forfeit_commitment`).
+        ///
+        /// # Returns
+        /// - `Err(Error::NoCommitReveal)` - Event doesn't sell through commit-reveal
+        /// - `Err(Error::PhaseClosed)` - Commit phase is over
+        /// - `Err(Error::AlreadyEntered)` - One commitment per account
+        #[ink(message, payable)]
+        pub fn commit_purchase(&mut self, event_id: u64, commitment: Hash) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            let phases = self.commit_reveals.get(event_id).ok_or(Error::NoCommitReveal)?;
+            if self.env().block_timestamp() >= phases.commit_end {
+                return Err(Error::PhaseClosed);
+            }
+            if self.purchase_commitments.contains((event_id, caller)) {
+                return Err(Error::AlreadyEntered);
+            }
+            if self.blocked_attendees.contains((event_id, caller)) {
+                return Err(Error::AttendeeBlocked);
+            }
+
+            // Same checks as a regular purchase, bar the sale phase
+            self.ensure_sale_window_open(&event)?;
+            self.ensure_eligible(&event, caller)?;
+            self.ensure_within_rate_limits(event_id, caller, 1)?;
+            if let Some(person) = self.claim_person_quota(event_id, caller, 1)? {
+                self.entry_persons.insert((event_id, caller), &person);
+            }
+
+            // Hold the deposit until the reveal
+            let deposit = event.price;
+            self.collect_payment(&event, caller, deposit)?;
+
+            self.purchase_commitments.insert(
+                (event_id, caller),
+                &PurchaseCommitment {
+                    commitment,
+                    deposit,
+                    entry: None,
+                },
+            );
+
+            self.env().emit_event(PurchaseCommitted {
+                event_id,
+                account: caller,
+                deposit,
+            });
+
+            Ok(())
+        }
+
+        /// Reveal a commitment once the commit phase closes, entering it in
+        /// the ticket lottery
+        ///
+        /// # Returns
+        /// - `Ok(entry)` - The commitment's lottery entry
+        /// - `Err(Error::SaleNotOpen)` - Commit phase is still open
+        /// - `Err(Error::PhaseClosed)` - Reveal phase is over
+        /// - `Err(Error::CommitmentMismatch)` - `salt` doesn't match the commitment
+        #[ink(message)]
+        pub fn reveal_purchase(&mut self, event_id: u64, salt: Hash) -> Result<u32, Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            self.ensure_sale_window_open(&event)?;
+            self.ensure_eligible(&event, caller)?;
+            let phases = self.commit_reveals.get(event_id).ok_or(Error::NoCommitReveal)?;
+            let now = self.env().block_timestamp();
+            if now < phases.commit_end {
+                return Err(Error::SaleNotOpen {
+                    opens_at: phases.commit_end,
+                });
+            }
+            if now >= phases.reveal_end {
+                return Err(Error::PhaseClosed);
+            }
+            let mut commitment = self
+                .purchase_commitments
+                .get((event_id, caller))
+                .ok_or(Error::CommitmentNotFound)?;
+            if commitment.entry.is_some() {
+                return Err(Error::AlreadyEntered);
+            }
+            if purchase_commitment(&caller, &salt) != commitment.commitment {
+                return Err(Error::CommitmentMismatch);
+            }
+
+            // Enter the lottery, mixing the salt into its seed
+            let mut draw = self.commit_draws.get(event_id).ok_or(Error::NoCommitReveal)?;
+            let entry = draw.entrants;
+            draw.entrants = draw.entrants.checked_add(1).ok_or(Error::InvalidInput)?;
+            self.commit_draws.insert(event_id, &draw);
+            let mut entropy = [0u8; 32];
+            ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+                &(self.commit_entropy.get(event_id), salt),
+                &mut entropy,
+            );
+            self.commit_entropy.insert(event_id, &Hash::from(entropy));
+            commitment.entry = Some(entry);
+            self.purchase_commitments.insert((event_id, caller), &commitment);
+
+            self.env().emit_event(PurchaseRevealed {
+                event_id,
+                account: caller,
+                entry,
+            });
+
+            Ok(entry)
+        }
+
+        /// Draw the ticket lottery of a commit-reveal sale once reveals close,
+        /// revealing the seed whose hash was committed at creation
+        ///
+        /// Works like `draw_raffle`: whoever holds the seed can draw, within
+        /// `RAFFLE_DRAW_PERIOD` of reveals closing, and if nobody does every
+        /// revealed commitment can reclaim its deposit. Winners claim with
+        /// `claim_commit_ticket`, and their tickets are held back until they do.
+        ///
+        /// # Returns
+        /// - `Err(Error::RaffleSeedMismatch)` - `seed` doesn't hash to the commitment
+        /// - `Err(Error::RaffleClosed)` - The draw period is over
+        #[ink(message)]
+        pub fn draw_commit_reveal(&mut self, event_id: u64, seed: Hash) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            let mut draw = self.commit_draws.get(event_id).ok_or(Error::NoCommitReveal)?;
+            if draw.drawn {
+                return Err(Error::RaffleAlreadyDrawn);
+            }
+            let now = self.env().block_timestamp();
+            if now < draw.entry_end {
+                return Err(Error::RaffleStillOpen);
+            }
+            if now >= draw.entry_end.saturating_add(RAFFLE_DRAW_PERIOD) {
+                return Err(Error::RaffleClosed);
+            }
+            if self.raffle_seeds.get(event_id) != Some(raffle_seed_hash(&seed)) {
+                return Err(Error::RaffleSeedMismatch);
+            }
+
+            // Rotate the winning window to a random starting entry
+            if draw.entrants > 0 {
+                let mut output = [0u8; 32];
+                ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+                    &(seed, self.commit_entropy.get(event_id), draw.entrants),
+                    &mut output,
+                );
+                let random = u32::from_le_bytes([output[0], output[1], output[2], output[3]]);
+                draw.offset = random % draw.entrants;
+            }
+            draw.winners = draw.entrants.min(self.unreserved_available(&event));
+            draw.drawn = true;
+            self.commit_draws.insert(event_id, &draw);
+            self.unclaimed_wins.insert(event_id, &draw.winners);
+
+            self.env().emit_event(CommitRevealDrawn {
+                event_id,
+                revealed: draw.entrants,
+                winners: draw.winners,
+            });
+
+            Ok(())
+        }
+
+        /// Mint the ticket for a winning commitment, paid by its deposit
+        #[ink(message)]
+        pub fn claim_commit_ticket(&mut self, event_id: u64) -> Result<u64, Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            match event.status {
+                EventStatus::OnSale => {}
+                EventStatus::Paused => return Err(Error::SalesPaused),
+                EventStatus::Cancelled => return Err(Error::EventCancelled),
+                EventStatus::Draft | EventStatus::Completed => return Err(Error::EventNotActive),
+            }
+            if event.frozen {
+                return Err(Error::EventFrozen);
+            }
+            self.check_credential(event_id, caller)?;
+            let draw = self.commit_draws.get(event_id).ok_or(Error::NoCommitReveal)?;
+            let commitment = self
+                .purchase_commitments
+                .get((event_id, caller))
+                .ok_or(Error::CommitmentNotFound)?;
+            if !draw.drawn {
+                return Err(Error::RaffleNotDrawn);
+            }
+            if !commitment.entry.is_some_and(|entry| draw.is_winner(entry)) {
+                return Err(Error::NotRaffleWinner);
+            }
+
+            // Release the held-back ticket to the winner
+            self.purchase_commitments.remove((event_id, caller));
+            self.release_unclaimed_win(event_id);
+            let person = self.entry_persons.take((event_id, caller));
+            let ticket_id = self.mint_ticket(&mut event, caller, None, None, commitment.deposit)?;
+            self.record_ticket_person(ticket_id, person);
+            Ok(ticket_id)
+        }
+
+        /// Return the deposit of a commitment that wasn't turned into a ticket
+        ///
+        /// Losing commitments are refunded after the draw, every revealed
+        /// one if the draw period lapses, and every one if the event is
+        /// cancelled. Winners who can't claim their ticket are refunded too.
+        ///
+        /// # Returns
+        /// - `Err(Error::RaffleWinnerMustClaim)` - Caller won and can claim the ticket
+        /// - `Err(Error::CommitmentForfeited)` - Caller didn't reveal in time
+        #[ink(message)]
+        pub fn claim_commit_refund(&mut self, event_id: u64) -> Result<Balance, Error> {
+            let caller = self.env().caller();
+
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            let draw = self.commit_draws.get(event_id).ok_or(Error::NoCommitReveal)?;
+            let commitment = self
+                .purchase_commitments
+                .get((event_id, caller))
+                .ok_or(Error::CommitmentNotFound)?;
+            let won = draw.drawn && commitment.entry.is_some_and(|entry| draw.is_winner(entry));
+            let now = self.env().block_timestamp();
+            let draw_lapsed =
+                !draw.drawn && now >= draw.entry_end.saturating_add(RAFFLE_DRAW_PERIOD);
+            if !event.is_cancelled() {
+                if now < draw.entry_end {
+                    return Err(Error::SaleNotOpen {
+                        opens_at: draw.entry_end,
+                    });
+                }
+                if commitment.entry.is_none() {
+                    return Err(Error::CommitmentForfeited);
+                }
+                if !draw.drawn && !draw_lapsed {
+                    return Err(Error::RaffleNotDrawn);
+                }
+                if won && self.can_claim_raffle_win(&event, caller) {
+                    return Err(Error::RaffleWinnerMustClaim);
+                }
+            }
+
+            self.purchase_commitments.remove((event_id, caller));
+            if won {
+                self.release_unclaimed_win(event_id);
+            }
+            let person = self.entry_persons.take((event_id, caller));
+            self.release_person_quota(event_id, person);
+            self.pay_out(event.payment_token, caller, commitment.deposit)?;
+
+            Ok(commitment.deposit)
+        }
+
+        /// Forfeit the deposit of a commitment that wasn't revealed in time
+        ///
+        /// Anyone can call this once reveals close; the deposit goes to
+        /// platform fees, so withholding a reveal costs the ticket price.
+        /// Commitments of cancelled events are refunded instead.
+        ///
+        /// # Returns
+        /// The deposit forfeited, in the event's payment asset
+        #[ink(message)]
+        pub fn forfeit_commitment(
+            &mut self,
+            event_id: u64,
+            account: AccountId,
+        ) -> Result<Balance, Error> {
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            let draw = self.commit_draws.get(event_id).ok_or(Error::NoCommitReveal)?;
+            let commitment = self
+                .purchase_commitments
+                .get((event_id, account))
+                .ok_or(Error::CommitmentNotFound)?;
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if self.env().block_timestamp() < draw.entry_end {
+                return Err(Error::RaffleStillOpen);
+            }
+            if commitment.entry.is_some() {
+                return Err(Error::AlreadyEntered);
+            }
+
+            self.purchase_commitments.remove((event_id, account));
+            let person = self.entry_persons.take((event_id, account));
+            self.release_person_quota(event_id, person);
+            self.credit_platform_fees(event.payment_token, commitment.deposit);
+
+            self.env().emit_event(DepositForfeited {
+                event_id,
+                account,
+                deposit: commitment.deposit,
+            });
+
+            Ok(commitment.deposit)
+        }
+
+        /// Get the ticket lottery of a commit-reveal sale
+        #[ink(message)]
+        pub fn get_commit_draw(&self, event_id: u64) -> Option<Raffle> {
+            self.commit_draws.get(event_id)
+        }
+
+        /// Get an event's commit-reveal phases
+        #[ink(message)]
+        pub fn get_commit_reveal(&self, event_id: u64) -> Option<CommitReveal> {
+            self.commit_reveals.get(event_id)
+        }
+
+        /// Get an account's pending commitment for an event
+        #[ink(message)]
+        pub fn get_purchase_commitment(
+            &self,
+            event_id: u64,
+            account: AccountId,
+        ) -> Option<PurchaseCommitment> {
+            self.purchase_commitments.get((event_id, account))
+        }
+
+        /// Draw an event's raffle winners once entries have closed
+        ///
+        /// Randomness comes from the secret `seed` the organizer committed
+        /// to when creating the event, mixed with every entry. Entrants
+        /// can't predict the outcome without the seed, and the organizer
+        /// can't pick a different seed once entries are in. Whoever holds
+        /// the seed can draw, within `RAFFLE_DRAW_PERIOD` of entries
+        /// closing; if nobody does, every entrant can reclaim their deposit.
+        ///
+        /// # Returns
+        /// - `Err(Error::RaffleSeedMismatch)` - `seed` doesn't hash to the commitment
+        /// - `Err(Error::RaffleClosed)` - The draw period is over
+        #[ink(message)]
+        pub fn draw_raffle(&mut self, event_id: u64, seed: Hash) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            let mut raffle = self.raffles.get(event_id).ok_or(Error::NoRaffle)?;
+            if raffle.drawn {
+                return Err(Error::RaffleAlreadyDrawn);
+            }
+            let now = self.env().block_timestamp();
+            if now < raffle.entry_end {
+                return Err(Error::RaffleStillOpen);
+            }
+            if now >= raffle.entry_end.saturating_add(RAFFLE_DRAW_PERIOD) {
+                return Err(Error::RaffleClosed);
+            }
+            if self.raffle_seeds.get(event_id) != Some(raffle_seed_hash(&seed)) {
+                return Err(Error::RaffleSeedMismatch);
+            }
+
+            // Rotate the winning window to a random starting entry
+            if raffle.entrants > 0 {
+                let seed = (seed, self.raffle_entropy.get(event_id), raffle.entrants);
+                let mut output = [0u8; 32];
+                ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(&seed, &mut output);
+                let random = u32::from_le_bytes([output[0], output[1], output[2], output[3]]);
+                raffle.offset = random % raffle.entrants;
+            }
+            // Hold the winners' tickets back from comps, airdrops and vouchers
+            raffle.winners = raffle.entrants.min(self.unreserved_available(&event));
+            raffle.drawn = true;
+            self.raffles.insert(event_id, &raffle);
+            self.unclaimed_wins.insert(event_id, &raffle.winners);
+
+            self.env().emit_event(RaffleDrawn {
+                event_id,
+                entrants: raffle.entrants,
+                winners: raffle.winners,
+            });
+
+            Ok(())
+        }
+
+        /// Mint the ticket for a winning raffle entry, paid by its deposit
+        #[ink(message)]
+        pub fn claim_raffle_ticket(&mut self, event_id: u64) -> Result<u64, Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            match event.status {
+                EventStatus::OnSale => {}
+                EventStatus::Paused => return Err(Error::SalesPaused),
+                EventStatus::Cancelled => return Err(Error::EventCancelled),
+                EventStatus::Draft | EventStatus::Completed => return Err(Error::EventNotActive),
+            }
+            if event.frozen {
+                return Err(Error::EventFrozen);
+            }
+            self.check_credential(event_id, caller)?;
+            let raffle = self.raffles.get(event_id).ok_or(Error::NoRaffle)?;
+            let entry = self
+                .raffle_entries
+                .get((event_id, caller))
+                .ok_or(Error::NotRaffleEntrant)?;
+            if !raffle.drawn {
+                return Err(Error::RaffleNotDrawn);
+            }
+            if !raffle.is_winner(entry.index) {
+                return Err(Error::NotRaffleWinner);
+            }
+
+            // Release the held-back ticket to the winner
+            self.raffle_entries.remove((event_id, caller));
+            self.release_unclaimed_win(event_id);
+            let person = self.entry_persons.take((event_id, caller));
+            let ticket_id = self.mint_ticket(&mut event, caller, None, None, entry.deposit)?;
+            self.record_ticket_person(ticket_id, person);
+            Ok(ticket_id)
+        }
+
+        /// Return the deposit of a losing raffle entry
+        ///
+        /// Every entrant can reclaim their deposit if the event is cancelled.
+        /// Winners who can't claim their ticket right now (blocked, holding
+        /// too many tickets, without a valid credential, or the event isn't
+        /// on sale or is frozen) are refunded too.
+        ///
+        /// # Returns
+        /// - `Err(Error::RaffleWinnerMustClaim)` - Caller won and can claim the ticket
+        #[ink(message)]
+        pub fn claim_raffle_refund(&mut self, event_id: u64) -> Result<Balance, Error> {
+            let caller = self.env().caller();
+
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            let raffle = self.raffles.get(event_id).ok_or(Error::NoRaffle)?;
+            let entry = self
+                .raffle_entries
+                .get((event_id, caller))
+                .ok_or(Error::NotRaffleEntrant)?;
+            let draw_lapsed = !raffle.drawn
+                && self.env().block_timestamp()
+                    >= raffle.entry_end.saturating_add(RAFFLE_DRAW_PERIOD);
+            if !event.is_cancelled() && !draw_lapsed {
+                if !raffle.drawn {
+                    return Err(Error::RaffleNotDrawn);
+                }
+                if raffle.is_winner(entry.index) && self.can_claim_raffle_win(&event, caller) {
+                    return Err(Error::RaffleWinnerMustClaim);
+                }
+            }
+
+            self.raffle_entries.remove((event_id, caller));
+            if raffle.is_winner(entry.index) {
+                self.release_unclaimed_win(event_id);
+            }
+            let person = self.entry_persons.take((event_id, caller));
+            self.release_person_quota(event_id, person);
+            self.pay_out(event.payment_token, caller, entry.deposit)?;
+
+            Ok(entry.deposit)
+        }
+
+        /// Get an event's raffle state
+        #[ink(message)]
+        pub fn get_raffle(&self, event_id: u64) -> Option<Raffle> {
+            self.raffles.get(event_id)
+        }
+
+        /// Check whether an account holds a winning, unclaimed raffle entry
+        #[ink(message)]
+        pub fn is_raffle_winner(&self, event_id: u64, account: AccountId) -> bool {
+            match (self.raffles.get(event_id), self.raffle_entries.get((event_id, account))) {
+                (Some(raffle), Some(entry)) => raffle.is_winner(entry.index),
+                _ => false,
+            }
+        }
+
+        /// Buy a ticket at a discount using a promo code
+        ///
+        /// The discount applies to the current base price. Tiers and seats
+        /// aren't discounted.
+        ///
+        /// # Arguments
+        /// * `event_id` - ID of the event
+        /// * `code` - Plain-text promo code, checked against its registered hash
+        ///
+        /// # Returns
+        /// - `Err(Error::PromoCodeNotFound)` - Code isn't registered for the event
+        /// - `Err(Error::PromoCodeExhausted)` - Code has no uses left
+        #[ink(message, payable)]
+        pub fn buy_ticket_with_code(&mut self, event_id: u64, code: String) -> Result<u64, Error> {
+            let caller = self.env().caller();
+
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            self.ensure_on_sale(&event, caller, 1)?;
+            let person = self.claim_person_quota(event_id, caller, 1)?;
+
+            // Look up the code by its hash
+            let code_hash = promo_code_hash(&code);
+            let mut promo = self
+                .promo_codes
+                .get((event_id, code_hash))
+                .ok_or(Error::PromoCodeNotFound)?;
+            if promo.remaining_uses == 0 {
+                return Err(Error::PromoCodeExhausted);
+            }
+
+            // Apply the discount to the required payment
+            let base_price = self.base_price(&event, self.env().block_timestamp(), 0)?;
+            let discount = bps_share(base_price, promo.discount_bps);
+            let price = base_price.saturating_sub(discount);
+            self.collect_payment(&event, caller, price)?;
+            let ticket_id = self.mint_ticket(&mut event, caller, None, None, price)?;
+            self.record_ticket_person(ticket_id, person);
+
+            // Record the usage for reporting
+            promo.remaining_uses = promo.remaining_uses.saturating_sub(1);
+            promo.times_used = promo.times_used.saturating_add(1);
+            promo.total_discount = promo.total_discount.saturating_add(discount);
+            self.promo_codes.insert((event_id, code_hash), &promo);
+
+            self.env().emit_event(PromoCodeUsed {
+                event_id,
+                code_hash,
+                ticket_id,
+                buyer: caller,
+                discount,
+            });
+
+            Ok(ticket_id)
+        }
+
+        /// Buy a ticket at a discount by spending loyalty points
+        ///
+        /// Spends the event's `redeem_points` for its `discount_bps` off the
+        /// current base price (see `set_loyalty_terms`). The purchase itself
+        /// still earns points.
+        ///
+        /// # Returns
+        /// - `Err(Error::InvalidInput)` - The event offers no points discount
+        /// - `Err(Error::InsufficientPoints)` - Caller holds fewer points than it costs
+        #[ink(message, payable)]
+        pub fn buy_ticket_with_points(&mut self, event_id: u64) -> Result<u64, Error> {
+            let caller = self.env().caller();
+
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            self.ensure_on_sale(&event, caller, 1)?;
+            let person = self.claim_person_quota(event_id, caller, 1)?;
+
+            let terms = self.loyalty_terms.get(event_id).unwrap_or_default();
+            if terms.redeem_points == 0 {
+                return Err(Error::InvalidInput);
+            }
+            if self.get_points(caller) < terms.redeem_points {
+                return Err(Error::InsufficientPoints);
+            }
+
+            // Apply the discount to the required payment
+            let base_price = self.base_price(&event, self.env().block_timestamp(), 0)?;
+            let discount = bps_share(base_price, terms.discount_bps);
+            let price = base_price.saturating_sub(discount);
+            self.collect_payment(&event, caller, price)?;
+            let ticket_id = self.mint_ticket(&mut event, caller, None, None, price)?;
+            self.record_ticket_person(ticket_id, person);
+
+            // Spend the points
+            let balance = self.get_points(caller).saturating_sub(terms.redeem_points);
+            self.points.insert(caller, &balance);
+
+            self.env().emit_event(PointsRedeemed {
+                account: caller,
+                event_id,
+                amount: terms.redeem_points,
+                discount,
+            });
+
+            Ok(ticket_id)
+        }
+
+        /// Join a sold-out event's waitlist
+        ///
+        /// Cancelled general admission tickets are offered to the waitlist
+        /// in joining order; each offer can be claimed with
+        /// `claim_waitlist_offer` for a limited time.
+        ///
+        /// # Returns
+        /// - `Err(Error::NotSoldOut)` - Tickets can still be bought directly
+        /// - `Err(Error::AlreadyWaitlisted)` - Caller is waiting or holds an offer
+        #[ink(message)]
+        pub fn join_waitlist(&mut self, event_id: u64) -> Result<u32, Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            if !event.is_live() {
+                return Err(Error::EventNotActive);
+            }
+            // Offers are for general admission without a seat
+            if self.seat_layouts.contains(event_id) {
+                return Err(Error::InvalidInput);
+            }
+            if self.unreserved_available(&event) > 0 {
+                return Err(Error::NotSoldOut);
+            }
+            if self.waitlisted.contains((event_id, caller))
+                || self.waitlist_offers.contains((event_id, caller))
+            {
+                return Err(Error::AlreadyWaitlisted);
+            }
+
+            // Append to the queue
+            let mut waitlist = self.waitlists.get(event_id).unwrap_or_default();
+            let position = waitlist.tail;
+            waitlist.tail = waitlist.tail.checked_add(1).ok_or(Error::InvalidInput)?;
+            self.waitlist_queue.insert((event_id, position), &caller);
+            self.waitlisted.insert((event_id, caller), &position);
+            self.waitlists.insert(event_id, &waitlist);
+
+            self.env().emit_event(WaitlistJoined {
+                event_id,
+                account: caller,
+                position,
+            });
+
+            Ok(position)
+        }
+
+        /// Leave an event's waitlist
+        ///
+        /// The caller's queue slot is freed, so joining again goes to the
+        /// back of the queue.
+        #[ink(message)]
+        pub fn leave_waitlist(&mut self, event_id: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let position = self
+                .waitlisted
+                .take((event_id, caller))
+                .ok_or(Error::NotWaitlisted)?;
+            self.waitlist_queue.remove((event_id, position));
+            Ok(())
+        }
+
+        /// Buy the ticket reserved for the caller by a waitlist offer
+        ///
+        /// Charges the current base price, like `buy_ticket`.
+        ///
+        /// # Returns
+        /// - `Err(Error::NoWaitlistOffer)` - Caller holds no offer
+        /// - `Err(Error::WaitlistOfferExpired)` - Offer ran out
+        #[ink(message, payable)]
+        pub fn claim_waitlist_offer(&mut self, event_id: u64) -> Result<u64, Error> {
+            let caller = self.env().caller();
+
+            let expires_at = self
+                .waitlist_offers
+                .get((event_id, caller))
+                .ok_or(Error::NoWaitlistOffer)?;
+            if self.env().block_timestamp() >= expires_at {
+                return Err(Error::WaitlistOfferExpired);
+            }
+
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            self.ensure_on_sale(&event, caller, 1)?;
+            let person = self.claim_person_quota(event_id, caller, 1)?;
+
+            // Release the reservation to the caller
+            self.close_waitlist_offer(event_id, caller);
+
+            let price = self.base_price(&event, self.env().block_timestamp(), 0)?;
+            self.collect_payment(&event, caller, price)?;
+            let ticket_id = self.mint_ticket(&mut event, caller, None, None, price)?;
+            self.record_ticket_person(ticket_id, person);
+            Ok(ticket_id)
+        }
+
+        /// Pass an expired waitlist offer on to the next account in line
+        ///
+        /// Anyone can call this once the offer has run out.
+        #[ink(message)]
+        pub fn expire_waitlist_offer(&mut self, event_id: u64, account: AccountId) -> Result<(), Error> {
+            let expires_at = self
+                .waitlist_offers
+                .get((event_id, account))
+                .ok_or(Error::NoWaitlistOffer)?;
+            if self.env().block_timestamp() < expires_at {
+                return Err(Error::WaitlistOfferActive);
+            }
+
+            self.close_waitlist_offer(event_id, account);
+            if !self.offer_to_waitlist(event_id) {
+                let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+                self.notify(&event, NotificationKind::TicketsAvailable);
+            }
+
+            Ok(())
+        }
+
+        /// Check whether an account is waiting in an event's waitlist
+        #[ink(message)]
+        pub fn is_waitlisted(&self, event_id: u64, account: AccountId) -> bool {
+            self.waitlisted.contains((event_id, account))
+        }
+
+        /// Get the expiry of an account's waitlist offer, if it holds one
+        #[ink(message)]
+        pub fn get_waitlist_offer(&self, event_id: u64, account: AccountId) -> Option<u64> {
+            self.waitlist_offers.get((event_id, account))
+        }
+
+        /// Move a ticket to a pricier tier by paying the difference in face value
+        ///
+        /// The face value is the tier price, or the event price for general
+        /// admission, whatever discount the ticket was bought with; a paid
+        /// upgrade makes a comp ticket a regular one. The ticket's old pool
+        /// gets the place back and the new tier gives one up. Seats, the
+        /// ticket ID, and check-ins already made are kept, so the new tier's
+        /// uses are reduced by them. Only the ticket owner can upgrade,
+        /// while the ticket has uses left.
+        ///
+        /// # Arguments
+        /// * `ticket_id` - ID of the ticket to upgrade
+        /// * `new_tier` - Tier to move to; must cost more than the current face value
+        ///
+        /// # Returns
+        /// - `Err(Error::InsufficientPayment)` - Payment != face value difference
+        /// - `Err(Error::SoldOut)` - Target tier has no tickets left
+        /// - `Err(Error::TicketAlreadyUsed)` - No uses would be left after upgrading
+        /// - `Err(Error::InvalidInput)` - Same tier, or not an upgrade in price
+        /// - `Err(Error::SalesPaused)` - The organizer paused sales
+        /// - `Err(Error::EventFrozen)` - A moderator froze the event
+        #[ink(message, payable)]
+        pub fn upgrade_ticket(&mut self, ticket_id: u64, new_tier: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+
+            let mut ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            if ticket.owner != caller {
+                return Err(Error::NotTicketOwner);
+            }
+            if ticket.is_refunded {
+                return Err(Error::TicketAlreadyRefunded);
+            }
+            if ticket.uses_remaining == 0 {
+                return Err(Error::TicketAlreadyUsed);
+            }
+
+            let mut event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+            if event.status == EventStatus::Paused {
+                return Err(Error::SalesPaused);
+            }
+            if event.frozen {
+                return Err(Error::EventFrozen);
+            }
+            if ticket.tier_id == Some(new_tier) {
+                return Err(Error::InvalidInput);
+            }
+
+            // Only upgrades in face value; the holder pays the difference
+            let face_value = match ticket.tier_id {
+                Some(tier_id) => Self::tier(&event, tier_id)?.price,
+                None => event.price,
+            };
+            let target = Self::tier(&event, new_tier)?;
+            if target.available == 0 {
+                return Err(Error::SoldOut);
+            }
+            let difference = target
+                .price
+                .checked_sub(face_value)
+                .filter(|difference| *difference > 0)
+                .ok_or(Error::InvalidInput)?;
+            // Check-ins already made count against the new tier's uses
+            let uses_remaining = target.uses_per_ticket.saturating_sub(ticket.times_used);
+            if uses_remaining == 0 {
+                return Err(Error::TicketAlreadyUsed);
+            }
+            self.collect_payment(&event, caller, difference)?;
+
+            // Swap availability between the pools
+            if let Some(old_tier) = ticket.tier_id {
+                let tier = Self::tier_mut(&mut event, old_tier)?;
+                tier.available = tier.available.saturating_add(1);
+            }
+            let tier = Self::tier_mut(&mut event, new_tier)?;
+            tier.available = tier.available.saturating_sub(1);
+            self.events.insert(event.id, &event);
+
+            let fee = self.collect_sale(&event, ticket_id, difference);
+            let from_tier = ticket.tier_id;
+            ticket.tier_id = Some(new_tier);
+            ticket.price_paid = ticket.price_paid.saturating_add(difference);
+            ticket.fee_paid = ticket.fee_paid.saturating_add(fee);
+            ticket.uses_remaining = uses_remaining;
+            ticket.is_comp = false;
+            self.tickets.insert(ticket_id, &ticket);
+            self.record_spent(caller, event.payment_token, difference);
+
+            self.env().emit_event(TicketUpgraded {
+                ticket_id,
+                event_id: event.id,
+                from_tier,
+                to_tier: new_tier,
+                amount_paid: difference,
+            });
+            self.ticket_state_changed(&ticket, TicketState::Upgraded);
+
+            Ok(())
+        }
+
+        /// Transfer a ticket with the owner's off-chain signature
+        ///
+        /// Lets a relayer pay the fees for an owner without native tokens.
+        /// The owner signs the blake2-256 hash of the SCALE-encoded
+        /// `(contract_address, ticket_id, to, deadline, nonce)` with the
+        /// ECDSA key behind their account, where `nonce` is their current
+        /// `get_permit_nonce`. Anyone can submit the permit.
+        ///
+        /// # Arguments
+        /// * `ticket_id` - ID of the ticket to transfer
+        /// * `to` - Account ID of the recipient
+        /// * `deadline` - Last block timestamp at which the permit is valid
+        /// * `signature` - 65-byte recoverable ECDSA signature
+        ///
+        /// # Returns
+        /// - `Err(Error::PermitExpired)` - Deadline has passed
+        /// - `Err(Error::InvalidSignature)` - Not signed by the owner for this nonce
+        #[ink(message)]
+        pub fn transfer_with_permit(
+            &mut self,
+            ticket_id: u64,
+            to: AccountId,
+            deadline: u64,
+            signature: [u8; 65],
+        ) -> Result<(), Error> {
+            if self.env().block_timestamp() > deadline {
+                return Err(Error::PermitExpired);
+            }
+
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            let nonce = self.permit_nonces.get(ticket.owner).unwrap_or(0);
+            self.verify_signature(&(ticket_id, to, deadline, nonce), &signature, ticket.owner)?;
+
+            // Consume the nonce so the permit can't be replayed
+            let owner = ticket.owner;
+            self.move_ticket(ticket, to, None)?;
+            self.permit_nonces.insert(owner, &nonce.saturating_add(1));
+
+            Ok(())
+        }
+
+        /// Get the nonce an account's next permit must be signed with
+        #[ink(message)]
+        pub fn get_permit_nonce(&self, account: AccountId) -> u64 {
+            self.permit_nonces.get(account).unwrap_or(0)
+        }
+
+        /// Approve an operator to transfer one of the caller's tickets
+        ///
+        /// Only one operator per ticket; `None` clears the approval. The
+        /// approval is dropped when the ticket changes hands.
+        #[ink(message)]
+        pub fn approve_ticket(&mut self, ticket_id: u64, operator: Option<AccountId>) -> Result<(), Error> {
+            match operator {
+                Some(operator) => self.set_ticket_approval(ticket_id, operator, true),
+                None => match self.ticket_approvals.get(ticket_id) {
+                    Some(current) => self.set_ticket_approval(ticket_id, current, false),
+                    None => Ok(()),
+                },
+            }
+        }
+
+        /// Approve or revoke an operator for all of the caller's tickets
+        #[ink(message)]
+        pub fn set_approval_for_all(&mut self, operator: AccountId, approved: bool) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if operator == caller {
+                return Err(Error::SelfApproval);
+            }
+
+            if approved {
+                self.operator_approvals.insert((caller, operator), &());
+            } else {
+                self.operator_approvals.remove((caller, operator));
+            }
+
+            self.env().emit_event(Approval {
+                owner: caller,
+                operator,
+                id: None,
+                approved,
+            });
+
+            Ok(())
+        }
+
+        /// Get the operator approved for a single ticket
+        #[ink(message)]
+        pub fn get_approved(&self, ticket_id: u64) -> Option<AccountId> {
+            self.ticket_approvals.get(ticket_id)
+        }
+
+        /// Check whether an operator may transfer all of an owner's tickets
+        #[ink(message)]
+        pub fn is_approved_for_all(&self, owner: AccountId, operator: AccountId) -> bool {
+            self.operator_approvals.contains((owner, operator))
+        }
+
+        /// Transfer several tickets to the same user at once
+        ///
+        /// Either every ticket moves or none does. At most
+        /// `MAX_BATCH_SIZE` tickets per call, each listed once.
+        ///
+        /// # Arguments
+        /// * `ticket_ids` - IDs of the caller's tickets to transfer
+        /// * `to` - Account ID of the recipient
+        #[ink(message)]
+        pub fn transfer_tickets(&mut self, ticket_ids: Vec<u64>, to: AccountId) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            if ticket_ids.is_empty() || ticket_ids.len() > MAX_BATCH_SIZE {
+                return Err(Error::InvalidInput);
+            }
+            let unique: BTreeSet<u64> = ticket_ids.iter().copied().collect();
+            if unique.len() != ticket_ids.len() {
+                return Err(Error::InvalidInput);
+            }
+
+            // Verify ownership of the whole batch before moving anything
+            let mut tickets = Vec::with_capacity(ticket_ids.len());
+            for ticket_id in &ticket_ids {
+                let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+                if ticket.owner != caller {
+                    return Err(Error::NotTicketOwner);
+                }
+                tickets.push(ticket);
+            }
+
+            for ticket in tickets {
+                self.move_ticket(ticket, to, None)?;
+            }
+
+            self.env().emit_event(TicketsBatchTransferred {
+                from: caller,
+                to,
+                ticket_ids,
+            });
+
+            Ok(())
+        }
+
+        /// List a ticket for resale on the built-in marketplace
+        ///
+        /// Listing again updates the price. The listing is dropped
+        /// automatically if the ticket is transferred, used, or refunded.
+        ///
+        /// # Arguments
+        /// * `ticket_id` - ID of the ticket to sell
+        /// * `price` - Asking price in native token
+        #[ink(message)]
+        pub fn list_ticket_for_sale(&mut self, ticket_id: u64, price: Balance) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+
+            if price < self.state.config.min_ticket_price {
+                return Err(Error::InvalidInput);
+            }
+
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            if ticket.owner != caller {
+                return Err(Error::NotTicketOwner);
+            }
+            if ticket.times_used > 0 {
+                return Err(Error::TicketAlreadyUsed);
+            }
+            if ticket.is_refunded {
+                return Err(Error::TicketAlreadyRefunded);
+            }
+
+            // Only tickets for upcoming events can be resold
+            let event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+            if !event.transferable {
+                return Err(Error::TicketNotTransferable);
+            }
+
+            // Enforce the organizer's anti-scalping cap
+            if let Some(max_price) = Self::max_resale_price(&event, &ticket) {
+                if price > max_price {
+                    return Err(Error::ResalePriceTooHigh);
+                }
+            }
+
+            self.listings.insert(
+                ticket_id,
+                &Listing {
+                    ticket_id,
+                    seller: caller,
+                    price,
+                    listed_at: self.env().block_timestamp(),
+                },
+            );
+
+            self.env().emit_event(TicketListed {
+                ticket_id,
+                seller: caller,
+                price,
+            });
+
+            Ok(())
+        }
+
+        /// Remove a ticket from the resale marketplace
+        #[ink(message)]
+        pub fn delist_ticket(&mut self, ticket_id: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            let listing = self.listings.get(ticket_id).ok_or(Error::NotListed)?;
+            if listing.seller != caller {
+                return Err(Error::NotTicketOwner);
+            }
+
+            self.listings.remove(ticket_id);
+            self.env().emit_event(TicketDelisted { ticket_id });
+
+            Ok(())
+        }
+
+        /// Buy a ticket listed on the resale marketplace
+        ///
+        /// The exact listing price must be attached. The event's royalty is
+        /// kept in escrow for the organizer, the rest is forwarded to the
+        /// seller, and the ticket moves to the caller in the same call.
+        ///
+        /// # Returns
+        /// - `Err(Error::NotListed)` - Ticket isn't listed
+        /// - `Err(Error::InsufficientPayment)` - Payment != listing price
+        #[ink(message, payable)]
+        pub fn buy_listed_ticket(&mut self, ticket_id: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            let listing = self.listings.get(ticket_id).ok_or(Error::NotListed)?;
+            if caller == listing.seller {
+                return Err(Error::InvalidInput);
+            }
+
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            let event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+
+            // Resales settle in the event's payment asset
+            let payment = listing.price;
+            self.collect_payment(&event, caller, payment)?;
+
+            // Move the ticket first (this also clears the listing)
+            let seller = listing.seller;
+            self.move_ticket(ticket, caller, Some(payment))?;
+            self.settle_resale(&event, ticket_id, seller, caller, payment)
+        }
+
+        /// Make an offer on any ticket, escrowing the transferred value
+        ///
+        /// The holder can take it with `accept_offer` whether or not the
+        /// ticket is listed. Making a new offer on the same ticket refunds
+        /// the previous one. Offers are in the native token, so tickets for
+        /// PSP22-priced events don't take offers.
+        ///
+        /// # Returns
+        /// - `Err(Error::ResalePriceTooHigh)` - Offer is above the event's resale cap
+        #[ink(message, payable)]
+        pub fn make_offer(&mut self, ticket_id: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            if ticket.owner == caller {
+                return Err(Error::InvalidInput);
+            }
+            if ticket.times_used > 0 {
+                return Err(Error::TicketAlreadyUsed);
+            }
+            if ticket.is_refunded {
+                return Err(Error::TicketAlreadyRefunded);
+            }
+
+            let event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+            if !event.transferable {
+                return Err(Error::TicketNotTransferable);
+            }
+
+            let amount = self.env().transferred_value();
+            if amount == 0 || event.payment_token.is_some() {
+                return Err(Error::InvalidInput);
+            }
+            if Self::max_resale_price(&event, &ticket).is_some_and(|max_price| amount > max_price) {
+                return Err(Error::ResalePriceTooHigh);
+            }
+
+            // Replace any earlier offer from the caller
+            if let Some(previous) = self.offers.get((ticket_id, caller)) {
+                self.pay_out(None, caller, previous.amount)?;
+            }
+            self.offers.insert(
+                (ticket_id, caller),
+                &Offer {
+                    amount,
+                    made_at: self.env().block_timestamp(),
+                },
+            );
+
+            self.env().emit_event(OfferMade {
+                ticket_id,
+                buyer: caller,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Withdraw an offer and get its escrow back
+        #[ink(message)]
+        pub fn cancel_offer(&mut self, ticket_id: u64) -> Result<Balance, Error> {
+            let caller = self.env().caller();
+
+            let offer = self
+                .offers
+                .get((ticket_id, caller))
+                .ok_or(Error::OfferNotFound)?;
+            self.offers.remove((ticket_id, caller));
+            self.pay_out(None, caller, offer.amount)?;
+
+            self.env().emit_event(OfferCancelled {
+                ticket_id,
+                buyer: caller,
+            });
+
+            Ok(offer.amount)
+        }
+
+        /// Sell a ticket to `buyer` for their offer
+        /// Only the ticket holder can accept offers
+        ///
+        /// Settles like `buy_listed_ticket`: the event's royalty is kept in
+        /// escrow for the organizer and the rest goes to the holder.
+        ///
+        /// # Returns
+        /// - `Err(Error::OfferNotFound)` - `buyer` has no offer on the ticket
+        #[ink(message)]
+        pub fn accept_offer(&mut self, ticket_id: u64, buyer: AccountId) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            if ticket.owner != caller {
+                return Err(Error::NotTicketOwner);
+            }
+            let offer = self
+                .offers
+                .get((ticket_id, buyer))
+                .ok_or(Error::OfferNotFound)?;
+
+            let event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+            if Self::max_resale_price(&event, &ticket)
+                .is_some_and(|max_price| offer.amount > max_price)
+            {
+                return Err(Error::ResalePriceTooHigh);
+            }
+
+            self.offers.remove((ticket_id, buyer));
+            self.move_ticket(ticket, buyer, Some(offer.amount))?;
+            self.settle_resale(&event, ticket_id, caller, buyer, offer.amount)
+        }
+
+        /// Get an account's open offer on a ticket
+        #[ink(message)]
+        pub fn get_offer(&self, ticket_id: u64, buyer: AccountId) -> Option<Offer> {
+            self.offers.get((ticket_id, buyer))
+        }
+
+        /// Get the active resale listing of a ticket
+        #[ink(message)]
+        pub fn get_listing(&self, ticket_id: u64) -> Option<Listing> {
+            self.listings.get(ticket_id)
+        }
+
+        /// Create a season pass for some or all of the caller's events
+        ///
+        /// Holders can check in once at each covered event until the pass
+        /// expires. Sales are held in escrow until then.
+        ///
+        /// # Arguments
+        /// * `price` - Price of one pass, in native token
+        /// * `supply` - Number of passes for sale
+        /// * `scope` - The caller's events the pass admits to
+        /// * `valid_until` - When the pass expires and earnings unlock
+        ///
+        /// # Returns
+        /// - `Ok(pass_id)` - ID of the created pass
+        /// - `Err(Error::NotOrganizer)` - A listed event isn't the caller's
+        #[ink(message)]
+        pub fn create_pass(
+            &mut self,
+            name: String,
+            metadata_cid: String,
+            price: Balance,
+            supply: u32,
+            scope: PassScope,
+            valid_until: u64,
+        ) -> Result<u64, Error> {
+            self.ensure_not_paused()?;
+            let caller = self.env().caller();
+            if self.banned_organizers.contains(caller) {
+                return Err(Error::OrganizerBanned);
+            }
+
+            // Same limits as events
+            if name.is_empty() || name.len() > self.state.config.max_event_name_length as usize {
+                return Err(Error::InvalidInput);
+            }
+            if metadata_cid.is_empty() || metadata_cid.len() > self.state.config.max_metadata_cid_length as usize {
+                return Err(Error::InvalidInput);
+            }
+            if price < self.state.config.min_ticket_price
+                || supply == 0
+                || supply > self.state.config.max_tickets_per_event
+                || valid_until <= self.env().block_timestamp()
+            {
+                return Err(Error::InvalidInput);
+            }
+
+            // Listed events must be distinct, the caller's, and still running
+            if let PassScope::Events(event_ids) = &scope {
+                if event_ids.is_empty() || event_ids.len() > MAX_PASS_EVENTS {
+                    return Err(Error::InvalidInput);
+                }
+                for (index, event_id) in event_ids.iter().enumerate() {
+                    if event_ids[..index].contains(event_id) {
+                        return Err(Error::InvalidInput);
+                    }
+                    let event = self.ensure_organizer(*event_id)?;
+                    if event.is_cancelled() {
+                        return Err(Error::EventCancelled);
+                    }
+                    if event.is_completed() {
+                        return Err(Error::EventCompleted);
+                    }
+                }
+            }
+
+            let pass_id = self.state.pass_counter;
+            self.passes.insert(
+                pass_id,
+                &Pass {
+                    id: pass_id,
+                    organizer: caller,
+                    name,
+                    metadata_cid,
+                    price,
+                    supply,
+                    sold: 0,
+                    scope,
+                    first_event_id: self.event_counter,
+                    valid_until,
+                },
+            );
+            self.state.pass_counter = self.state.pass_counter.saturating_add(1);
+
+            self.env().emit_event(PassCreated {
+                pass_id,
+                organizer: caller,
+                price,
+                supply,
+            });
+
+            Ok(pass_id)
+        }
+
+        /// Buy a season pass, paying its price in native token
+        ///
+        /// Any overpayment is sent back to the caller.
+        ///
+        /// # Returns
+        /// - `Ok(pass_token_id)` - ID of the pass NFT minted to the caller
+        /// - `Err(Error::InsufficientPayment)` - Attached value is below the price
+        #[ink(message, payable)]
+        pub fn buy_pass(&mut self, pass_id: u64) -> Result<u64, Error> {
+            self.ensure_not_paused()?;
+            let caller = self.env().caller();
+            let mut pass = self.passes.get(pass_id).ok_or(Error::PassNotFound)?;
+            if self.env().block_timestamp() >= pass.valid_until {
+                return Err(Error::PassExpired);
+            }
+            if pass.sold >= pass.supply {
+                return Err(Error::SoldOut);
+            }
+            self.collect_native_payment(caller, pass.price)?;
+
+            // Split off the platform fee and hold the rest until expiry
+            let fee = bps_share(pass.price, self.state.platform_fee_bps);
+            let earnings = pass.price.saturating_sub(fee);
+            self.state.platform_fees = self.state.platform_fees.saturating_add(fee);
+            let escrow = self.pass_escrow.get(pass_id).unwrap_or(0);
+            self.pass_escrow.insert(pass_id, &escrow.saturating_add(earnings));
+            self.state.total_escrowed = self.state.total_escrowed.saturating_add(earnings);
+
+            pass.sold = pass.sold.saturating_add(1);
+            self.passes.insert(pass_id, &pass);
+
+            let pass_token_id = self.state.pass_token_counter;
+            self.pass_tokens.insert(
+                pass_token_id,
+                &PassToken {
+                    id: pass_token_id,
+                    pass_id,
+                    owner: caller,
+                    purchase_time: self.env().block_timestamp(),
+                },
+            );
+            self.state.pass_token_counter = self.state.pass_token_counter.saturating_add(1);
+            self.record_spent(caller, None, pass.price);
+
+            self.env().emit_event(PassPurchased {
+                pass_token_id,
+                pass_id,
+                buyer: caller,
+                price: pass.price,
+                fee,
+            });
+
+            Ok(pass_token_id)
+        }
+
+        /// Check a pass in at an event (called by event organizer, check-in staff, or an admin)
+        ///
+        /// Each pass admits once per event.
+        ///
+        /// # Returns
+        /// - `Err(Error::PassNotValidForEvent)` - The event isn't in the pass's scope
+        /// - `Err(Error::TicketAlreadyUsed)` - The pass was already checked in here
+        #[ink(message)]
+        pub fn use_pass(&mut self, pass_token_id: u64, event_id: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+
+            let token = self.pass_tokens.get(pass_token_id).ok_or(Error::PassNotFound)?;
+            let pass = self.passes.get(token.pass_id).ok_or(Error::PassNotFound)?;
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+
+            // Same gatekeepers as ticket check-in
+            if caller != event.organizer
+                && !self.co_organizers.contains((event.id, caller))
+                && !self.checkin_staff.contains((event.id, caller))
+                && !self.has_role(Role::Admin, caller)
+            {
+                return Err(Error::NotTicketOwner);
+            }
+
+            if !pass.covers(&event) {
+                return Err(Error::PassNotValidForEvent);
+            }
+            if self.env().block_timestamp() >= pass.valid_until {
+                return Err(Error::PassExpired);
+            }
+            if self.pass_uses.contains((pass_token_id, event_id)) {
+                return Err(Error::TicketAlreadyUsed);
+            }
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.frozen {
+                return Err(Error::EventFrozen);
+            }
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+            event.schedule.check_event_window(self.env().block_timestamp())?;
+
+            self.pass_uses.insert((pass_token_id, event_id), &());
+
+            // Count each event once towards the holder's summary
+            if !self.attended_events.contains((token.owner, event_id)) {
+                self.attended_events.insert((token.owner, event_id), &());
+                self.update_summary(token.owner, |summary| {
+                    summary.events_attended = summary.events_attended.saturating_add(1);
+                });
+            }
+
+            self.env().emit_event(PassUsed {
+                pass_token_id,
+                event_id,
+            });
+
+            Ok(())
+        }
+
+        /// Withdraw a pass's sales once it has expired
+        /// Only the organizer who created the pass can withdraw
+        #[ink(message)]
+        pub fn withdraw_pass_earnings(&mut self, pass_id: u64) -> Result<Balance, Error> {
+            self.ensure_not_paused()?;
+            let pass = self.passes.get(pass_id).ok_or(Error::PassNotFound)?;
+            if self.env().caller() != pass.organizer {
+                return Err(Error::NotOrganizer);
+            }
+            if self.env().block_timestamp() < pass.valid_until {
+                return Err(Error::WithdrawalLocked);
+            }
+
+            let amount = self.pass_escrow.take(pass_id).unwrap_or(0);
+            self.state.total_escrowed = self.state.total_escrowed.saturating_sub(amount);
+            self.pay_out(None, pass.organizer, amount)?;
+
+            self.env().emit_event(PassEarningsWithdrawn {
+                pass_id,
+                organizer: pass.organizer,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Get pass details
+        #[ink(message)]
+        pub fn get_pass(&self, pass_id: u64) -> Option<Pass> {
+            self.passes.get(pass_id)
+        }
+
+        /// Get a bought pass and its holder
+        #[ink(message)]
+        pub fn get_pass_token(&self, pass_token_id: u64) -> Option<PassToken> {
+            self.pass_tokens.get(pass_token_id)
+        }
+
+        /// Check whether a pass has been checked in at an event
+        #[ink(message)]
+        pub fn is_pass_used(&self, pass_token_id: u64, event_id: u64) -> bool {
+            self.pass_uses.contains((pass_token_id, event_id))
+        }
+
+        /// Cancel an event and enable refunds for all ticket holders
+        /// Only the event organizer can cancel
+        ///
+        /// Holders have `REFUND_CLAIM_PERIOD` to claim their refund; after
+        /// that, whatever is left can be swept with `sweep_unclaimed`.
+        #[ink(message)]
+        pub fn cancel_event(&mut self, event_id: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            // Get event
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+
+            // Only organizer can cancel
+            if caller != event.organizer {
+                return Err(Error::NotOrganizer);
+            }
+
+            // Can't cancel a cancelled or completed event
+            event.check_transition(EventStatus::Cancelled)?;
+
+            self.close_for_refunds(&mut event, self.state.bond_slash_bps)?;
+
+            // Emit event
+            self.env().emit_event(EventCancelled {
+                event_id,
+                organizer: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Cancel every remaining session of a series
+        /// Only the organizer can cancel
+        ///
+        /// Sessions already cancelled or completed are skipped; ticket holders
+        /// of the others can claim refunds as with `cancel_event`.
+        ///
+        /// # Returns
+        /// - `Ok(count)` - Number of sessions cancelled
+        #[ink(message)]
+        pub fn cancel_series(&mut self, series_id: u64) -> Result<u32, Error> {
+            let caller = self.env().caller();
+            let series = self.series.get(series_id).ok_or(Error::SeriesNotFound)?;
+            if caller != series.organizer {
+                return Err(Error::NotOrganizer);
+            }
+
+            let mut cancelled = 0u32;
+            for event_id in series.event_ids {
+                let Some(mut event) = self.events.get(event_id) else {
+                    continue;
+                };
+                if event.is_cancelled() || event.is_completed() {
+                    continue;
+                }
+                self.close_for_refunds(&mut event, self.state.bond_slash_bps)?;
+                self.env().emit_event(EventCancelled {
+                    event_id,
+                    organizer: caller,
+                });
+                cancelled = cancelled.saturating_add(1);
+            }
+
+            Ok(cancelled)
+        }
+
+        /// Freeze or unfreeze a suspicious event
+        ///
+        /// A frozen event can't sell tickets, check tickets in, or pay out
+        /// to its organizer until a moderator unfreezes or force-cancels it.
+        /// Only moderators (and admins) can freeze events.
+        #[ink(message)]
+        pub fn freeze_event(&mut self, event_id: u64, frozen: bool) -> Result<(), Error> {
+            let caller = self.ensure_role(Role::Moderator)?;
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            self.set_event_frozen(&mut event, frozen, caller);
+            Ok(())
+        }
+
+        /// Ban an account from creating events
+        /// Only admins can ban organizers
+        ///
+        /// # Arguments
+        /// * `account` - The organizer to ban
+        /// * `freeze_events` - The organizer's active events to freeze at the
+        ///   same time (at most `MAX_BATCH_SIZE`)
+        #[ink(message)]
+        pub fn ban_organizer(&mut self, account: AccountId, freeze_events: Vec<u64>) -> Result<(), Error> {
+            let caller = self.ensure_role(Role::Admin)?;
+            if freeze_events.len() > MAX_BATCH_SIZE {
+                return Err(Error::InvalidInput);
+            }
+
+            // Check every event before freezing any
+            let mut events = Vec::with_capacity(freeze_events.len());
+            for event_id in freeze_events {
+                let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+                if event.organizer != account {
+                    return Err(Error::NotOrganizer);
+                }
+                events.push(event);
+            }
+
+            self.banned_organizers.insert(account, &());
+            for mut event in events {
+                self.set_event_frozen(&mut event, true, caller);
+            }
+
+            self.env().emit_event(OrganizerBanned {
+                organizer: account,
+                admin: caller,
+                banned: true,
+            });
+
+            Ok(())
+        }
+
+        /// Lift an organizer's ban; their frozen events stay frozen
+        /// Only admins can unban organizers
+        #[ink(message)]
+        pub fn unban_organizer(&mut self, account: AccountId) -> Result<(), Error> {
+            let caller = self.ensure_role(Role::Admin)?;
+            self.banned_organizers.remove(account);
+
+            self.env().emit_event(OrganizerBanned {
+                organizer: account,
+                admin: caller,
+                banned: false,
+            });
+
+            Ok(())
+        }
+
+        /// Check whether an account is banned from creating events
+        #[ink(message)]
+        pub fn is_organizer_banned(&self, account: AccountId) -> bool {
+            self.banned_organizers.contains(account)
+        }
+
+        /// Cancel a fraudulent event on the organizer's behalf
+        ///
+        /// Works like `cancel_event`, enabling refunds for every holder, but
+        /// is recorded as a moderation action. Only moderators (and admins)
+        /// can force-cancel events.
+        #[ink(message)]
+        pub fn force_cancel_event(&mut self, event_id: u64) -> Result<(), Error> {
+            let caller = self.ensure_role(Role::Moderator)?;
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            event.check_transition(EventStatus::Cancelled)?;
+
+            self.close_for_refunds(&mut event, BPS_DENOMINATOR as u16)?;
+
+            self.env().emit_event(EventForceCancelled {
+                event_id,
+                moderator: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Mark an event as completed (no more tickets can be used)
+        /// Only the event organizer (or a co-organizer) can mark as completed
+        #[ink(message)]
+        pub fn complete_event(&mut self, event_id: u64) -> Result<(), Error> {
+            // Only organizer or co-organizers can mark as completed
+            let mut event = self.ensure_event_manager(event_id)?;
+
+            // Only events on sale or paused can be completed
+            event.check_transition(EventStatus::Completed)?;
+
+            // Frozen events keep their bond until moderation is resolved
+            if event.frozen {
+                return Err(Error::EventFrozen);
+            }
+
+            self.close_completed(&mut event)
+        }
+
+        /// Complete an event whose organizer hasn't, once it is well over
+        ///
+        /// Anyone can call this after `event_end` plus the finalize grace
+        /// period, so withdrawals and disputes don't wait on the organizer.
+        /// Events without an end time can only be completed by the organizer.
+        ///
+        /// # Returns
+        /// - `Err(Error::EventNotEnded)` - No end time, or the grace period is still running
+        #[ink(message)]
+        pub fn finalize_event(&mut self, event_id: u64) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            event.check_transition(EventStatus::Completed)?;
+            if event.frozen {
+                return Err(Error::EventFrozen);
+            }
+            let deadline = event
+                .schedule
+                .event_end
+                .saturating_add(self.state.finalize_grace_period);
+            if event.schedule.event_end == 0 || self.env().block_timestamp() < deadline {
+                return Err(Error::EventNotEnded);
+            }
+
+            self.close_completed(&mut event)
+        }
+
+        /// Set how long after an event ends anyone can finalize it
+        /// Only admins can change this
+        #[ink(message)]
+        pub fn set_finalize_grace_period(&mut self, grace_period: u64) -> Result<(), Error> {
+            self.ensure_role(Role::Admin)?;
+            self.state.finalize_grace_period = grace_period;
+            Ok(())
+        }
+
+        /// Get how long after an event ends anyone can finalize it, in milliseconds
+        #[ink(message)]
+        pub fn get_finalize_grace_period(&self) -> u64 {
+            self.state.finalize_grace_period
+        }
+
+        /// Set the price oracle for USD-priced events and how fresh its quotes must be
+        /// Only admins can change this
+        #[ink(message)]
+        pub fn set_price_oracle(
+            &mut self,
+            oracle: Option<AccountId>,
+            max_staleness: u64,
+        ) -> Result<(), Error> {
+            self.ensure_role(Role::Admin)?;
+            if max_staleness == 0 {
+                return Err(Error::InvalidInput);
+            }
+            self.state.price_oracle = oracle;
+            self.state.max_oracle_staleness = max_staleness;
+            Ok(())
+        }
+
+        /// Get the price oracle and the oldest quote it may give, in milliseconds
+        #[ink(message)]
+        pub fn get_price_oracle(&self) -> (Option<AccountId>, u64) {
+            (self.state.price_oracle, self.state.max_oracle_staleness)
+        }
+
+        /// Set the registry attesting which accounts belong to verified humans
+        /// Only admins can change this
+        #[ink(message)]
+        pub fn set_personhood_registry(
+            &mut self,
+            registry: Option<AccountId>,
+        ) -> Result<(), Error> {
+            self.ensure_role(Role::Admin)?;
+            self.state.personhood_registry = registry;
+            Ok(())
+        }
+
+        /// Get the personhood registry
+        #[ink(message)]
+        pub fn get_personhood_registry(&self) -> Option<AccountId> {
+            self.state.personhood_registry
+        }
+
+        /// Present a credential for a credential-gated event, e.g. proof of
+        /// being over 18
+        ///
+        /// The credential is checked now, and again at every purchase and
+        /// check-in, so a revoked credential stops working. Ticket
+        /// recipients present their own credential before check-in.
+        ///
+        /// # Returns
+        /// - `Err(Error::InvalidInput)` - Event doesn't require a credential
+        /// - `Err(Error::InvalidCredential)` - Credential isn't from the event's
+        ///   attester, or the attester doesn't vouch for it
+        #[ink(message)]
+        pub fn present_credential(
+            &mut self,
+            event_id: u64,
+            credential: Credential,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if !self.events.contains(event_id) {
+                return Err(Error::EventNotFound);
+            }
+            let attester = self
+                .credential_attesters
+                .get(event_id)
+                .ok_or(Error::InvalidInput)?;
+            if credential.attester != attester
+                || !attestation::verify(attester, caller, credential.claim_hash)
+            {
+                return Err(Error::InvalidCredential);
+            }
+
+            self.credentials.insert((event_id, caller), &credential);
+            self.env().emit_event(CredentialPresented {
+                event_id,
+                account: caller,
+                attester,
+                claim_hash: credential.claim_hash,
+            });
+            Ok(())
+        }
+
+        /// Get the attester an event's attendees need a credential from
+        #[ink(message)]
+        pub fn get_credential_attester(&self, event_id: u64) -> Option<AccountId> {
+            self.credential_attesters.get(event_id)
+        }
+
+        /// Get the credential an account presented for an event
+        #[ink(message)]
+        pub fn get_credential(&self, event_id: u64, account: AccountId) -> Option<Credential> {
+            self.credentials.get((event_id, account))
+        }
+
+        /// Get the claim hash checked when a ticket was first admitted
+        #[ink(message)]
+        pub fn get_checked_credential(&self, ticket_id: u64) -> Option<Hash> {
+            self.checked_credentials.get(ticket_id)
+        }
+
+        /// Get how many tickets each person may buy for an event (0 = no
+        /// personhood attestation needed)
+        #[ink(message)]
+        pub fn get_tickets_per_person(&self, event_id: u64) -> u32 {
+            self.person_quotas.get(event_id).unwrap_or(0)
+        }
+
+        /// Get the native token price of an event's general admission ticket
+        ///
+        /// For USD-priced events this converts at the oracle's current quote,
+        /// so the price moves with the exchange rate; buyers cap their
+        /// slippage by the value they attach, and any surplus is refunded.
+        ///
+        /// # Returns
+        /// - `Err(Error::OracleUnavailable)` - No oracle set, or it didn't answer
+        /// - `Err(Error::StalePrice)` - The oracle's quote is too old
+        #[ink(message)]
+        pub fn get_price_in_native(&self, event_id: u64) -> Result<Balance, Error> {
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            self.base_price(&event, self.env().block_timestamp(), 0)
+        }
+
+        /// Get an event's price in USD cents, if it is priced in USD
+        #[ink(message)]
+        pub fn get_usd_price(&self, event_id: u64) -> Option<u64> {
+            self.usd_prices.get(event_id)
+        }
+
+        /// Move an event to new dates
+        ///
+        /// Holders who can't make the new dates get `RESCHEDULE_REFUND_WINDOW`
+        /// to return unused tickets with `refund_ticket`, as if the event had
+        /// been cancelled for them. Only the event organizer can reschedule.
+        ///
+        /// # Arguments
+        /// * `event_id` - ID of the event
+        /// * `new_start` - New check-in window start (0 = unbounded)
+        /// * `new_end` - New check-in window end (0 = unbounded)
+        #[ink(message)]
+        pub fn reschedule_event(&mut self, event_id: u64, new_start: u64, new_end: u64) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            let mut event = self.ensure_organizer(event_id)?;
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+
+            let old_schedule = event.schedule;
+            event.schedule.event_start = new_start;
+            event.schedule.event_end = new_end;
+            if !event.schedule.is_valid() {
+                return Err(Error::InvalidInput);
+            }
+            self.events.insert(event_id, &event);
+
+            // Open the opt-out window
+            let refund_deadline = self.env().block_timestamp().saturating_add(RESCHEDULE_REFUND_WINDOW);
+            self.reschedule_refund_deadlines.insert(event_id, &refund_deadline);
+
+            self.env().emit_event(EventRescheduled {
+                event_id,
+                old_start: old_schedule.event_start,
+                old_end: old_schedule.event_end,
+                new_start,
+                new_end,
+                refund_deadline,
+            });
+            self.notify(&event, NotificationKind::EventRescheduled);
+
+            Ok(())
+        }
+
+        /// Fix an event's name or IPFS metadata CID
+        ///
+        /// Only allowed while no tickets are sold, so buyers always get the
+        /// event they paid for. Only the event organizer (or a co-organizer) can update it.
+        ///
+        /// # Returns
+        /// - `Err(Error::InvalidInput)` - Invalid name/CID, or tickets already sold
+        #[ink(message)]
+        pub fn update_event_metadata(
+            &mut self,
+            event_id: u64,
+            name: String,
+            metadata_cid: String,
+        ) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            let mut event = self.ensure_event_manager(event_id)?;
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+            if event.available_tickets != event.total_tickets {
+                return Err(Error::InvalidInput);
+            }
+
+            // Same validations as create_event
+            if name.is_empty() || name.len() > self.state.config.max_event_name_length as usize {
+                return Err(Error::InvalidInput);
+            }
+            if metadata_cid.is_empty() || metadata_cid.len() > self.state.config.max_metadata_cid_length as usize {
+                return Err(Error::InvalidInput);
+            }
+
+            event.name = name.clone();
+            event.metadata_cid = metadata_cid.clone();
+            self.events.insert(event_id, &event);
+
+            self.env().emit_event(EventMetadataUpdated {
+                event_id,
+                name,
+                metadata_cid,
+            });
+
+            Ok(())
+        }
+
+        /// Fix an event's base ticket price
+        ///
+        /// Only allowed while no tickets are sold or before sales open, so
+        /// nobody pays a different price than others got. Only the event
+        /// organizer can change the price.
+        ///
+        /// # Returns
+        /// - `Err(Error::InvalidInput)` - Price below the minimum, or tickets already sold
+        #[ink(message)]
+        pub fn update_price(&mut self, event_id: u64, new_price: Balance) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            let mut event = self.ensure_organizer(event_id)?;
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+            let before_sales = self.env().block_timestamp() < event.schedule.sale_start;
+            if event.available_tickets != event.total_tickets && !before_sales {
+                return Err(Error::InvalidInput);
+            }
+            if new_price < self.state.config.min_ticket_price {
+                return Err(Error::InvalidInput);
+            }
+
+            let old_price = event.price;
+            event.price = new_price;
+            self.events.insert(event_id, &event);
+
+            self.env().emit_event(PriceUpdated {
+                event_id,
+                old_price,
+                new_price,
+            });
+
+            Ok(())
+        }
+
+        /// Put a draft event on sale
+        /// Only the event organizer (or a co-organizer) can publish
+        #[ink(message)]
+        pub fn publish_event(&mut self, event_id: u64) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            let mut event = self.ensure_event_manager(event_id)?;
+            if event.status != EventStatus::Draft {
+                return Err(Error::InvalidStatusTransition);
+            }
+            event.transition(EventStatus::OnSale)?;
+            self.events.insert(event_id, &event);
+
+            self.env().emit_event(EventPublished { event_id });
+
+            Ok(())
+        }
+
+        /// Temporarily halt ticket sales, e.g. for venue issues or repricing
+        ///
+        /// Unlike cancelling, nothing but buying is affected: tickets sold
+        /// stay valid and can still be transferred, resold, or refunded.
+        /// Only the event organizer (or a co-organizer) can pause sales.
+        #[ink(message)]
+        pub fn pause_sales(&mut self, event_id: u64) -> Result<(), Error> {
+            self.set_sales_paused(event_id, true)
+        }
+
+        /// Resume ticket sales halted with `pause_sales`
+        /// Only the event organizer (or a co-organizer) can resume sales
+        #[ink(message)]
+        pub fn resume_sales(&mut self, event_id: u64) -> Result<(), Error> {
+            self.set_sales_paused(event_id, false)
+        }
+
+        /// Give a single ticket its own IPFS metadata CID
+        ///
+        /// An empty CID clears it, so the ticket falls back to its tier's (or
+        /// the event's) metadata. Only the event organizer can set it, and only
+        /// before the event starts.
+        ///
+        /// # Returns
+        /// - `Err(Error::EventStarted)` - The event's start time has passed
+        /// - `Err(Error::InvalidInput)` - CID is too long
+        #[ink(message)]
+        pub fn set_ticket_metadata(
+            &mut self,
+            ticket_id: u64,
+            metadata_cid: String,
+        ) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            let event = self.ensure_organizer(ticket.event_id)?;
+            if ticket.is_refunded {
+                return Err(Error::TicketAlreadyRefunded);
+            }
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+            let start = event.schedule.event_start;
+            if start != 0 && self.env().block_timestamp() >= start {
+                return Err(Error::EventStarted);
+            }
+            if metadata_cid.len() > self.state.config.max_metadata_cid_length as usize {
+                return Err(Error::InvalidInput);
+            }
+
+            if metadata_cid.is_empty() {
+                self.ticket_metadata.remove(ticket_id);
+            } else {
+                self.ticket_metadata.insert(ticket_id, &metadata_cid);
+            }
+
+            self.env().emit_event(TicketMetadataUpdated {
+                ticket_id,
+                event_id: event.id,
+                metadata_cid,
+            });
+
+            Ok(())
+        }
+
+        /// Get the IPFS metadata CID of a ticket
+        ///
+        /// Falls back to the ticket's tier CID, then to the event's CID, when
+        /// the ticket has none of its own.
+        #[ink(message)]
+        pub fn get_ticket_metadata(&self, ticket_id: u64) -> Option<String> {
+            let ticket = self.tickets.get(ticket_id)?;
+            self.ticket_metadata_cid(&ticket)
+        }
+
+        /// Change the total number of tickets of an event
+        ///
+        /// Capacity can grow freely (up to the seat layout, for seated
+        /// events) but can only shrink by unsold general admission tickets;
+        /// tickets already sold, reserved for tiers, or offered to the
+        /// waitlist are kept. Only the event organizer can change capacity.
+        ///
+        /// # Returns
+        /// - `Err(Error::InvalidInput)` - Below what's sold or reserved, or above limits
+        #[ink(message)]
+        pub fn update_capacity(&mut self, event_id: u64, new_total: u32) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            let mut event = self.ensure_organizer(event_id)?;
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+            if new_total == 0 || new_total > self.state.config.max_tickets_per_event {
+                return Err(Error::InvalidInput);
+            }
+            if let Some(layout) = self.seat_layouts.get(event_id) {
+                if new_total > layout.seat_count() {
+                    return Err(Error::InvalidInput);
+                }
+            }
+
+            // Only unreserved general admission tickets can be removed
+            let old_total = event.total_tickets;
+            if new_total < old_total && old_total - new_total > self.unreserved_available(&event) {
+                return Err(Error::InvalidInput);
+            }
+
+            // Keep the sold count unchanged
+            let sold = event.total_tickets.saturating_sub(event.available_tickets);
+            event.total_tickets = new_total;
+            event.available_tickets = new_total.saturating_sub(sold);
+            self.events.insert(event_id, &event);
+
+            self.env().emit_event(CapacityChanged {
+                event_id,
+                old_total,
+                new_total,
+            });
+
+            Ok(())
+        }
+
+        /// Get the refund a ticket would get if cancelled right now
+        ///
+        /// Returns `None` for missing or already refunded tickets.
+        #[ink(message)]
+        pub fn compute_refund(&self, ticket_id: u64) -> Option<Balance> {
+            let ticket = self.tickets.get(ticket_id).filter(|ticket| !ticket.is_refunded)?;
+            let event = self.events.get(ticket.event_id)?;
+            Some(self.refund_due(&event, &ticket))
+        }
+
+        /// Move refunds nobody claimed before the deadline to the treasury
+        ///
+        /// Sweeps exactly what's left in the cancelled event's escrow.
+        /// Callable by the event organizer or an admin.
+        ///
+        /// # Returns
+        /// The amount swept, in the event's payment asset
+        #[ink(message)]
+        pub fn sweep_unclaimed(&mut self, event_id: u64) -> Result<Balance, Error> {
+            let caller = self.env().caller();
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            if caller != event.organizer && !self.has_role(Role::Admin, caller) {
+                return Err(Error::NotOrganizer);
+            }
+            if !event.is_cancelled() {
+                return Err(Error::EventNotActive);
+            }
+            if !self.refund_period_ended(event_id) {
+                return Err(Error::RefundPeriodOpen);
+            }
+
+            let amount = self.escrow_balance.get(event_id).unwrap_or(0);
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+            self.debit_escrow(event_id, amount)?;
+            self.pay_out(event.payment_token, self.state.treasury, amount)?;
+
+            self.env().emit_event(UnclaimedFundsSwept {
+                event_id,
+                treasury: self.state.treasury,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Get until when holders of a cancelled event can claim their refund
+        #[ink(message)]
+        pub fn get_refund_deadline(&self, event_id: u64) -> Option<u64> {
+            self.refund_deadlines.get(event_id)
+        }
+
+        /// Get until when holders of a rescheduled event can opt out for a refund
+        #[ink(message)]
+        pub fn get_reschedule_refund_deadline(&self, event_id: u64) -> Option<u64> {
+            self.reschedule_refund_deadlines.get(event_id)
+        }
+
+        /// Refund a ticket for a cancelled event
+        /// Only works if the event has been cancelled and ticket hasn't been refunded yet
+        ///
+        /// After a reschedule, unused tickets can also be refunded until the
+        /// opt-out deadline; they go back on sale like with `cancel_ticket`.
+        /// 
+        /// # Security
+        /// - Immediately removes ticket ID from owner's list for efficient queries
+        /// - Prevents double refunds with is_refunded flag
+        #[ink(message)]
+        pub fn refund_ticket(&mut self, ticket_id: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            // Get ticket
+            let mut ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+
+            // Only ticket owner can request refund
+            if ticket.owner != caller {
+                return Err(Error::NotTicketOwner);
+            }
+
+            // Check if already refunded
+            if ticket.is_refunded {
+                return Err(Error::TicketAlreadyRefunded);
+            }
+
+            // Get event
+            let event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+
+            // Can only refund if event is cancelled, or rescheduled and
+            // the holder opts out in time
+            if !event.is_cancelled() {
+                if self.reschedule_opt_out_open(event.id) {
+                    return self.cancel_ticket(ticket_id);
+                }
+                return Err(Error::EventNotActive);
+            }
+
+            // Refunds can only be claimed until the deadline
+            if self.refund_period_ended(event.id) {
+                return Err(Error::RefundPeriodEnded);
+            }
+
+            let refund_amount = self.refund_due(&event, &ticket);
+            self.refund_holder(&event, ticket, refund_amount)?;
+            Ok(())
+        }
+
+        /// Push refunds for a cancelled event to ticket holders
+        ///
+        /// Lets the organizer (or an admin) refund holders who haven't claimed
+        /// themselves, in batches of at most `MAX_BATCH_SIZE` to stay within
+        /// gas limits. Already refunded tickets are skipped, so a batch can
+        /// safely be retried.
+        ///
+        /// # Arguments
+        /// * `event_id` - The cancelled event
+        /// * `ticket_ids` - Tickets of that event to refund
+        ///
+        /// # Returns
+        /// The number of tickets refunded
+        #[ink(message)]
+        pub fn refund_batch(&mut self, event_id: u64, ticket_ids: Vec<u64>) -> Result<u32, Error> {
+            let caller = self.env().caller();
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            if caller != event.organizer && !self.has_role(Role::Admin, caller) {
+                return Err(Error::NotOrganizer);
+            }
+            if !event.is_cancelled() {
+                return Err(Error::EventNotActive);
+            }
+            if self.refund_period_ended(event_id) {
+                return Err(Error::RefundPeriodEnded);
+            }
+            if ticket_ids.len() > MAX_BATCH_SIZE {
+                return Err(Error::InvalidInput);
+            }
+
+            let mut refunded: u32 = 0;
+            for ticket_id in ticket_ids {
+                let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+                if ticket.event_id != event_id {
+                    return Err(Error::TicketNotFound);
+                }
+                if ticket.is_refunded {
+                    continue;
+                }
+                let refund_amount = self.refund_due(&event, &ticket);
+                self.refund_holder(&event, ticket, refund_amount)?;
+                refunded = refunded.saturating_add(1);
+            }
+
+            Ok(refunded)
+        }
+
+        /// Cancel a ticket and get refund (before event starts/completes)
+        /// This makes the ticket available for sale again
+        ///
+        /// The refund follows the event's refund policy (see `compute_refund`);
+        /// whatever isn't refunded stays in the event's escrow.
+        /// 
+        /// # Security
+        /// - Immediately removes ticket ID from owner's list for efficient queries
+        /// - Returns ticket to available pool
+        #[ink(message)]
+        pub fn cancel_ticket(&mut self, ticket_id: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            // Get ticket
+            let mut ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+
+            // Only ticket owner can cancel
+            if ticket.owner != caller {
+                return Err(Error::NotTicketOwner);
+            }
+
+            // Check if already refunded
+            if ticket.is_refunded {
+                return Err(Error::TicketAlreadyRefunded);
+            }
+
+            // Check if ticket already used (even once)
+            if ticket.times_used > 0 {
+                return Err(Error::TicketAlreadyUsed);
+            }
+
+            // Get event
+            let mut event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+
+            // Can't cancel ticket for cancelled event (use refund_ticket instead)
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+
+            // Can't cancel ticket for completed event
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+
+            // Mark ticket as refunded
+            ticket.is_refunded = true;
+            self.tickets.insert(ticket_id, &ticket);
+
+            // Increase available tickets count (and the tier's, if any)
+            let was_sold_out = event.available_tickets == 0;
+            event.available_tickets = event.available_tickets.saturating_add(1);
+            if let Some(tier_id) = ticket.tier_id {
+                let tier = Self::tier_mut(&mut event, tier_id)?;
+                tier.available = tier.available.saturating_add(1);
+            }
+            self.events.insert(ticket.event_id, &event);
+
+            // IMMEDIATELY remove ticket from owner's list (O(1) swap-remove)
+            self.remove_owned_ticket(caller, ticket_id);
+            self.burn_ticket(&ticket)?;
+
+            // Transfer the policy refund to ticket owner, in the event's
+            // payment asset; the rest stays in escrow (the platform fee is
+            // not refundable)
+            let refund_amount = self.refund_due(&event, &ticket);
+            self.debit_escrow(event.id, refund_amount)?;
+            self.pay_out(event.payment_token, caller, refund_amount)?;
+            self.record_refund(caller, event.payment_token, refund_amount);
+            self.record_event_refund(event.id, refund_amount);
+
+            // Emit event
+            self.env().emit_event(TicketCancelled {
+                ticket_id,
+                event_id: ticket.event_id,
+                owner: caller,
+                refund_amount,
+            });
+
+            // Hold general admission tickets for the waitlist before
+            // announcing them to everyone
+            let offered = ticket.tier_id.is_none() && self.offer_to_waitlist(event.id);
+            if was_sold_out && !offered {
+                self.notify(&event, NotificationKind::TicketsAvailable);
+            }
+
+            Ok(())
+        }
+
+        /// Withdraw earnings from a completed event
+        /// Only the organizer (or a payee of the event's split) can withdraw
+        /// after event is marked as completed
+        ///
+        /// Newly vested earnings are divided according to the payout split,
+        /// and the caller is paid their own share; the other payees pull
+        /// theirs by calling this themselves.
+        /// 
+        /// # Security
+        /// - Only organizer and payees can withdraw
+        /// - Event must be completed first, and its dispute window over
+        /// - Blocked while open disputes exceed the platform's threshold
+        /// - Pays out exactly the event's escrow balance (sales net of fees
+        ///   and refunds, plus resale royalties), never other events' funds
+        /// - Only releases funds not yet withdrawn; can be called again as
+        ///   new funds (e.g. royalties) reach the escrow
+        #[ink(message)]
+        pub fn withdraw_earnings(&mut self, event_id: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            
+            // Get event
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            
+            // Only organizer and payees can withdraw
+            let split = self.payout_splits.get(event_id).unwrap_or_default();
+            if caller != event.organizer && !split.iter().any(|share| share.payee == caller) {
+                return Err(Error::NotOrganizer);
+            }
+            
+            // Event must be completed
+            if !event.is_completed() {
+                return Err(Error::EventNotCompleted);
+            }
+
+            // Frozen events' funds are held until moderation is resolved
+            if event.frozen {
+                return Err(Error::EventFrozen);
+            }
+
+            // Attendees get the dispute window to report the event
+            let unlock = self.withdrawal_unlocks.get(event_id).unwrap_or(0);
+            if self.env().block_timestamp() < unlock {
+                return Err(Error::WithdrawalLocked);
+            }
+            let sold = event.total_tickets.saturating_sub(event.available_tickets);
+            let open_disputes = self.open_disputes.get(event_id).unwrap_or(0);
+            let threshold_bps = Balance::from(self.state.dispute_threshold_bps);
+            if Balance::from(open_disputes).saturating_mul(BPS_DENOMINATOR)
+                > Balance::from(sold).saturating_mul(threshold_bps)
+            {
+                return Err(Error::TooManyDisputes);
+            }
+            
+            // Release the vested part of what's left in the event's escrow,
+            // which already excludes everything withdrawn before
+            let released = self.vested_earnings(&event);
+            let mut total_withdrawn = self.total_withdrawn.get(event_id).unwrap_or(0);
+            if released > 0 {
+                self.debit_escrow(event_id, released)?;
+                total_withdrawn = total_withdrawn.saturating_add(released);
+                self.total_withdrawn.insert(event_id, &total_withdrawn);
+
+                // Split among payees, the organizer keeping the remainder
+                let mut remainder = released;
+                for share in &split {
+                    let amount = bps_share(released, share.share_bps);
+                    remainder = remainder.saturating_sub(amount);
+                    self.credit_payout(event_id, share.payee, amount);
+                }
+                self.credit_payout(event_id, event.organizer, remainder);
+            }
+
+            // Transfer the caller's share, in the event's payment asset
+            let earnings = self.pending_payouts.take((event_id, caller)).unwrap_or(0);
+            if earnings == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+            self.pay_out(event.payment_token, caller, earnings)?;
+
+            self.env().emit_event(EarningsWithdrawn {
+                event_id,
+                organizer: caller,
+                amount: earnings,
+                total_withdrawn,
+            });
+            
+            Ok(())
+        }
+
+        /// Donate to an event, e.g. a community or charity event
+        ///
+        /// The donation goes into the event's escrow, so the organizer gets
+        /// it through `withdraw_earnings`; no platform fee is taken. Only
+        /// native-token events accept donations.
+        #[ink(message, payable)]
+        pub fn donate_to_event(&mut self, event_id: u64) -> Result<(), Error> {
+            self.receive_donation(event_id, false)
+        }
+
+        /// Tip the organizer of an event
+        ///
+        /// Like `donate_to_event`, but counted as a tip in the event's stats.
+        #[ink(message, payable)]
+        pub fn tip_organizer(&mut self, event_id: u64) -> Result<(), Error> {
+            self.receive_donation(event_id, true)
+        }
+
+        /// Share an event's earnings with venues, artists, or promoters
+        /// Only the event organizer can set the split, before any earnings are released
+        ///
+        /// # Arguments
+        /// * `event_id` - The event
+        /// * `split` - Payees and their shares; the organizer keeps whatever
+        ///   isn't assigned (empty = organizer gets everything)
+        #[ink(message)]
+        pub fn set_payout_split(&mut self, event_id: u64, split: Vec<PayoutShare>) -> Result<(), Error> {
+            let event = self.ensure_organizer(event_id)?;
+            if self.total_withdrawn.get(event_id).unwrap_or(0) > 0 {
+                return Err(Error::PayoutsStarted);
+            }
+
+            // Distinct payees other than the organizer, at most 100% in total
+            let total_bps: Balance = split.iter().map(|share| Balance::from(share.share_bps)).sum();
+            if split.len() > MAX_PAYEES
+                || total_bps > BPS_DENOMINATOR
+                || split.iter().enumerate().any(|(i, share)| {
+                    share.payee == event.organizer
+                        || split[..i].iter().any(|other| other.payee == share.payee)
+                })
+            {
+                return Err(Error::InvalidInput);
+            }
+
+            self.payout_splits.insert(event_id, &split);
+            Ok(())
+        }
+
+        /// Get an event's payout split (empty = organizer gets everything)
+        #[ink(message)]
+        pub fn get_payout_split(&self, event_id: u64) -> Vec<PayoutShare> {
+            self.payout_splits.get(event_id).unwrap_or_default()
+        }
+
+        /// Get released earnings a payee hasn't pulled yet
+        #[ink(message)]
+        pub fn get_pending_payout(&self, event_id: u64, payee: AccountId) -> Balance {
+            self.pending_payouts.get((event_id, payee)).unwrap_or(0)
+        }
+
+        /// Get the earnings an organizer could withdraw right now
+        ///
+        /// Ignores the dispute window and open disputes; follows the event's
+        /// payout schedule.
+        #[ink(message)]
+        pub fn get_vested_earnings(&self, event_id: u64) -> Balance {
+            self.events
+                .get(event_id)
+                .filter(|event| event.is_completed())
+                .map_or(0, |event| self.vested_earnings(&event))
+        }
+
+        /// Get the total earnings already withdrawn for an event
+        #[ink(message)]
+        pub fn get_total_withdrawn(&self, event_id: u64) -> Balance {
+            self.total_withdrawn.get(event_id).unwrap_or(0)
+        }
+
+        /// Dispute a completed event the holder couldn't attend
+        ///
+        /// Holders of unused tickets can file one dispute per ticket until
+        /// the event's withdrawal unlock. Open disputes above the platform's
+        /// threshold block the organizer's withdrawal.
+        ///
+        /// # Arguments
+        /// * `ticket_id` - The unused ticket
+        /// * `reason_cid` - IPFS CID of the holder's statement and evidence
+        ///
+        /// # Returns
+        /// The ID of the new dispute
+        #[ink(message)]
+        pub fn file_dispute(&mut self, ticket_id: u64, reason_cid: String) -> Result<u64, Error> {
+            let caller = self.env().caller();
+            if reason_cid.is_empty() || reason_cid.len() > self.state.config.max_metadata_cid_length as usize {
+                return Err(Error::InvalidInput);
+            }
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            if ticket.owner != caller {
+                return Err(Error::NotTicketOwner);
+            }
+            if ticket.is_refunded {
+                return Err(Error::TicketAlreadyRefunded);
+            }
+            if ticket.times_used > 0 {
+                return Err(Error::TicketAlreadyUsed);
+            }
+            if self.ticket_disputes.contains(ticket_id) {
+                return Err(Error::AlreadyDisputed);
+            }
+
+            let event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+            if !event.is_completed() {
+                return Err(Error::EventNotCompleted);
+            }
+            let unlock = self.withdrawal_unlocks.get(event.id).unwrap_or(0);
+            if self.env().block_timestamp() >= unlock {
+                return Err(Error::DisputeWindowClosed);
+            }
+
+            let dispute_id = self.state.dispute_counter;
+            self.state.dispute_counter = self.state.dispute_counter.saturating_add(1);
+            self.disputes.insert(
+                dispute_id,
+                &Dispute {
+                    id: dispute_id,
+                    event_id: event.id,
+                    ticket_id,
+                    holder: caller,
+                    reason_cid: reason_cid.clone(),
+                    status: DisputeStatus::Open,
+                    filed_at: self.env().block_timestamp(),
+                },
+            );
+            self.ticket_disputes.insert(ticket_id, &dispute_id);
+            let open_disputes = self.open_disputes.get(event.id).unwrap_or(0);
+            self.open_disputes.insert(event.id, &open_disputes.saturating_add(1));
+
+            self.env().emit_event(DisputeFiled {
+                dispute_id,
+                event_id: event.id,
+                holder: caller,
+                ticket_id,
+                reason_cid,
+            });
+
+            Ok(dispute_id)
+        }
+
+        /// Arbitrate an open dispute
+        /// Only admins can resolve disputes
+        ///
+        /// Upholding a dispute refunds the ticket's price (minus the platform
+        /// fee) from the event's escrow and burns it; this fails if the
+        /// organizer has already withdrawn the funds.
+        ///
+        /// # Arguments
+        /// * `dispute_id` - The open dispute
+        /// * `refund` - Uphold and refund (`true`) or dismiss (`false`)
+        #[ink(message)]
+        pub fn resolve_dispute(&mut self, dispute_id: u64, refund: bool) -> Result<(), Error> {
+            self.ensure_role(Role::Admin)?;
+            let mut dispute = self.disputes.get(dispute_id).ok_or(Error::DisputeNotFound)?;
+            if dispute.status != DisputeStatus::Open {
+                return Err(Error::DisputeNotOpen);
+            }
+
+            let mut refund_amount = 0;
+            if refund {
+                let ticket = self.tickets.get(dispute.ticket_id).ok_or(Error::TicketNotFound)?;
+                if ticket.is_refunded {
+                    return Err(Error::TicketAlreadyRefunded);
+                }
+                let event = self.events.get(dispute.event_id).ok_or(Error::EventNotFound)?;
+                refund_amount = ticket.price_paid.saturating_sub(ticket.fee_paid);
+                self.refund_holder(&event, ticket, refund_amount)?;
+            }
+
+            dispute.status = if refund {
+                DisputeStatus::Refunded
+            } else {
+                DisputeStatus::Dismissed
+            };
+            self.disputes.insert(dispute_id, &dispute);
+            let open_disputes = self.open_disputes.get(dispute.event_id).unwrap_or(0);
+            self.open_disputes.insert(dispute.event_id, &open_disputes.saturating_sub(1));
+
+            self.env().emit_event(DisputeResolved {
+                dispute_id,
+                event_id: dispute.event_id,
+                status: dispute.status,
+                refund: refund_amount,
+            });
+
+            Ok(())
+        }
+
+        /// Get a dispute by ID
+        #[ink(message)]
+        pub fn get_dispute(&self, dispute_id: u64) -> Option<Dispute> {
+            self.disputes.get(dispute_id)
+        }
+
+        /// Get the dispute filed for a ticket, if any
+        #[ink(message)]
+        pub fn get_ticket_dispute(&self, ticket_id: u64) -> Option<u64> {
+            self.ticket_disputes.get(ticket_id)
+        }
+
+        /// Set how long attendees can dispute events and how many open disputes block withdrawals
+        /// Only admins can change this
+        ///
+        /// # Arguments
+        /// * `window` - Dispute window after an event ends, in milliseconds
+        /// * `threshold_bps` - Open disputes, as a share of tickets sold, tolerated
+        ///   before withdrawals are blocked
+        #[ink(message)]
+        pub fn set_dispute_policy(&mut self, window: u64, threshold_bps: u16) -> Result<(), Error> {
+            self.ensure_role(Role::Admin)?;
+            if Balance::from(threshold_bps) > BPS_DENOMINATOR {
+                return Err(Error::InvalidInput);
+            }
+            self.state.dispute_window = window;
+            self.state.dispute_threshold_bps = threshold_bps;
+            Ok(())
+        }
+
+        /// Get the dispute window (milliseconds) and blocking threshold (basis points)
+        #[ink(message)]
+        pub fn get_dispute_policy(&self) -> (u64, u16) {
+            (self.state.dispute_window, self.state.dispute_threshold_bps)
+        }
+
+        /// Get when a completed event's earnings can first be withdrawn
+        #[ink(message)]
+        pub fn get_withdrawal_unlock(&self, event_id: u64) -> Option<u64> {
+            self.withdrawal_unlocks.get(event_id)
+        }
+
+        /// Get the number of open disputes against an event
+        #[ink(message)]
+        pub fn get_open_disputes(&self, event_id: u64) -> u32 {
+            self.open_disputes.get(event_id).unwrap_or(0)
+        }
+
+        /// Add a ticket tier to an event
+        ///
+        /// The tier's capacity is reserved out of the event's unsold general
+        /// admission tickets, and each of its tickets can be checked in
+        /// `uses_per_ticket` times. Only the event organizer can add tiers.
+        ///
+        /// # Returns
+        /// - `Ok(tier_id)` - The ID of the new tier within the event
+        /// - `Err(Error::InvalidInput)` - Invalid tier or not enough unsold tickets
+        #[ink(message)]
+        pub fn add_ticket_tier(
+            &mut self,
+            event_id: u64,
+            name: String,
+            price: Balance,
+            capacity: u32,
+            metadata_cid: String,
+            uses_per_ticket: u32,
+        ) -> Result<u32, Error> {
+            let caller = self.env().caller();
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+
+            if caller != event.organizer {
+                return Err(Error::NotOrganizer);
+            }
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+
+            // Same validations as create_event
+            if name.is_empty() || name.len() > self.state.config.max_event_name_length as usize {
+                return Err(Error::InvalidInput);
+            }
+            if metadata_cid.len() > self.state.config.max_metadata_cid_length as usize {
+                return Err(Error::InvalidInput);
+            }
+            if price < self.state.config.min_ticket_price || capacity == 0 {
+                return Err(Error::InvalidInput);
+            }
+            if uses_per_ticket == 0 || uses_per_ticket > MAX_USES_PER_TICKET {
+                return Err(Error::InvalidInput);
+            }
+            if event.tiers.len() >= MAX_TIERS_PER_EVENT
+                || capacity > self.unreserved_available(&event)
+            {
+                return Err(Error::InvalidInput);
+            }
+
+            let tier_id = event.tiers.len() as u32;
+            event.tiers.push(TicketTier {
+                id: tier_id,
+                name,
+                price,
+                capacity,
+                available: capacity,
+                metadata_cid,
+                uses_per_ticket,
+            });
+            self.events.insert(event_id, &event);
+
+            Ok(tier_id)
+        }
+
+        /// Add a product ticket holders can buy on top of their ticket
+        /// Only the event organizer (or a co-organizer) can add add-ons
+        ///
+        /// # Arguments
+        /// * `price` - Price in the event's payment asset
+        /// * `supply` - Number for sale (0 = unlimited)
+        ///
+        /// # Returns
+        /// - `Ok(addon_id)` - ID of the add-on within the event
+        #[ink(message)]
+        pub fn add_addon(
+            &mut self,
+            event_id: u64,
+            name: String,
+            price: Balance,
+            supply: u32,
+        ) -> Result<u32, Error> {
+            self.ensure_not_paused()?;
+            let event = self.ensure_event_manager(event_id)?;
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+            if name.is_empty() || name.len() > MAX_DISPLAY_NAME_LENGTH {
+                return Err(Error::InvalidInput);
+            }
+            if price < self.state.config.min_ticket_price {
+                return Err(Error::InvalidInput);
+            }
+            let addon_id = self.addon_count.get(event_id).unwrap_or(0);
+            if addon_id >= MAX_ADDONS_PER_EVENT {
+                return Err(Error::InvalidInput);
+            }
+
+            self.addons.insert(
+                (event_id, addon_id),
+                &AddOn {
+                    id: addon_id,
+                    name,
+                    price,
+                    supply,
+                    sold: 0,
+                },
+            );
+            self.addon_count.insert(event_id, &addon_id.saturating_add(1));
+
+            Ok(addon_id)
+        }
+
+        /// Buy an add-on for one of the caller's tickets
+        ///
+        /// Paid like the ticket (native token or the event's PSP22 token) and
+        /// held in the event's escrow. Refunded with the ticket if the event
+        /// is cancelled.
+        #[ink(message, payable)]
+        pub fn buy_addon(&mut self, ticket_id: u64, addon_id: u32) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            let caller = self.env().caller();
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            if ticket.owner != caller {
+                return Err(Error::NotTicketOwner);
+            }
+            if ticket.is_refunded {
+                return Err(Error::TicketAlreadyRefunded);
+            }
+            let event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.frozen {
+                return Err(Error::EventFrozen);
+            }
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+            let mut addon = self
+                .addons
+                .get((event.id, addon_id))
+                .ok_or(Error::AddOnNotFound)?;
+            if self.ticket_addons.contains((ticket_id, addon_id)) {
+                return Err(Error::AddOnAlreadyPurchased);
+            }
+            if addon.supply != 0 && addon.sold >= addon.supply {
+                return Err(Error::SoldOut);
+            }
+
+            self.collect_payment(&event, caller, addon.price)?;
+            let fee = self.collect_sale(&event, ticket_id, addon.price);
+            self.record_spent(caller, event.payment_token, addon.price);
+
+            addon.sold = addon.sold.saturating_add(1);
+            self.addons.insert((event.id, addon_id), &addon);
+            self.ticket_addons.insert((ticket_id, addon_id), &false);
+            let spend = self.addon_spend.get(ticket_id).unwrap_or(0);
+            self.addon_spend
+                .insert(ticket_id, &spend.saturating_add(addon.price.saturating_sub(fee)));
+
+            self.env().emit_event(AddOnPurchased {
+                ticket_id,
+                event_id: event.id,
+                addon_id,
+                price: addon.price,
+            });
+
+            Ok(())
+        }
+
+        /// Mark a ticket's add-on as handed out (by event organizer, check-in staff, or an admin)
+        #[ink(message)]
+        pub fn redeem_addon(&mut self, ticket_id: u64, addon_id: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            let event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+
+            // Same gatekeepers as ticket check-in
+            if caller != event.organizer
+                && !self.co_organizers.contains((event.id, caller))
+                && !self.checkin_staff.contains((event.id, caller))
+                && !self.has_role(Role::Admin, caller)
+            {
+                return Err(Error::NotTicketOwner);
+            }
+            if ticket.is_refunded {
+                return Err(Error::TicketAlreadyRefunded);
+            }
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            match self.ticket_addons.get((ticket_id, addon_id)) {
+                None => return Err(Error::AddOnNotPurchased),
+                Some(true) => return Err(Error::AddOnAlreadyRedeemed),
+                Some(false) => {}
+            }
+
+            self.ticket_addons.insert((ticket_id, addon_id), &true);
+
+            self.env().emit_event(AddOnRedeemed {
+                ticket_id,
+                event_id: event.id,
+                addon_id,
+            });
+
+            Ok(())
+        }
+
+        /// Get an add-on of an event
+        #[ink(message)]
+        pub fn get_addon(&self, event_id: u64, addon_id: u32) -> Option<AddOn> {
+            self.addons.get((event_id, addon_id))
+        }
+
+        /// Get all add-ons of an event
+        #[ink(message)]
+        pub fn get_event_addons(&self, event_id: u64) -> Vec<AddOn> {
+            let count = self.addon_count.get(event_id).unwrap_or(0);
+            (0..count)
+                .filter_map(|addon_id| self.addons.get((event_id, addon_id)))
+                .collect()
+        }
+
+        /// Check a ticket's add-on: `None` if not bought, else whether it was redeemed
+        #[ink(message)]
+        pub fn get_ticket_addon(&self, ticket_id: u64, addon_id: u32) -> Option<bool> {
+            self.ticket_addons.get((ticket_id, addon_id))
+        }
+
+        /// Set the purchase rate limits that apply to every event
+        /// Only admins can change this
+        #[ink(message)]
+        pub fn set_rate_limits(&mut self, limits: RateLimits) -> Result<(), Error> {
+            self.ensure_role(Role::Admin)?;
+            self.state.rate_limits = limits;
+            Ok(())
+        }
+
+        /// Get the purchase rate limits
+        #[ink(message)]
+        pub fn get_rate_limits(&self) -> RateLimits {
+            self.state.rate_limits
+        }
+
+        /// Get an event's ticket transfer limits
+        #[ink(message)]
+        pub fn get_transfer_limits(&self, event_id: u64) -> TransferLimits {
+            self.transfer_limits.get(event_id).unwrap_or_default()
+        }
+
+        /// Get an account's loyalty point balance
+        #[ink(message)]
+        pub fn get_points(&self, account: AccountId) -> u64 {
+            self.points.get(account).unwrap_or(0)
+        }
+
+        /// Set the loyalty points earned per paid ticket and per first check-in
+        /// Only admins can change this
+        #[ink(message)]
+        pub fn set_loyalty_rewards(
+            &mut self,
+            per_purchase: u64,
+            per_checkin: u64,
+        ) -> Result<(), Error> {
+            self.ensure_role(Role::Admin)?;
+            self.state.points_per_purchase = per_purchase;
+            self.state.points_per_checkin = per_checkin;
+            Ok(())
+        }
+
+        /// Get the loyalty points earned per paid ticket and per first check-in
+        #[ink(message)]
+        pub fn get_loyalty_rewards(&self) -> (u64, u64) {
+            (self.state.points_per_purchase, self.state.points_per_checkin)
+        }
+
+        /// Gate an event on loyalty points, or discount it for points
+        /// Only the event organizer (or a co-organizer) can set the terms
+        ///
+        /// # Arguments
+        /// * `terms` - Minimum points to buy, and the points-for-discount offer;
+        ///   a discount needs both `redeem_points` and `discount_bps`
+        #[ink(message)]
+        pub fn set_loyalty_terms(
+            &mut self,
+            event_id: u64,
+            terms: LoyaltyTerms,
+        ) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            self.ensure_event_manager(event_id)?;
+            if terms.discount_bps as Balance > BPS_DENOMINATOR
+                || (terms.redeem_points == 0) != (terms.discount_bps == 0)
+            {
+                return Err(Error::InvalidInput);
+            }
+
+            self.loyalty_terms.insert(event_id, &terms);
+
+            Ok(())
+        }
+
+        /// Get an event's loyalty point terms
+        #[ink(message)]
+        pub fn get_loyalty_terms(&self, event_id: u64) -> LoyaltyTerms {
+            self.loyalty_terms.get(event_id).unwrap_or_default()
+        }
+
+        /// Open a poll for an event's ticket holders, e.g. to pick a setlist
+        /// Only the event organizer can open polls
+        ///
+        /// Each ticket holds one vote, cast with `vote` until `closes_at`.
+        ///
+        /// # Arguments
+        /// * `options` - Between 2 and `MAX_POLL_OPTIONS` answers
+        /// * `closes_at` - Timestamp voting ends at
+        ///
+        /// # Returns
+        /// - `Ok(poll_id)` - ID of the poll within the event
+        #[ink(message)]
+        pub fn create_poll(
+            &mut self,
+            event_id: u64,
+            question: String,
+            options: Vec<String>,
+            closes_at: u64,
+        ) -> Result<u32, Error> {
+            self.ensure_not_paused()?;
+            let event = self.ensure_organizer(event_id)?;
+            if !event.is_live() {
+                return Err(Error::EventNotActive);
+            }
+            if question.is_empty()
+                || question.len() > self.state.config.max_event_name_length as usize
+            {
+                return Err(Error::InvalidInput);
+            }
+            if options.len() < 2
+                || options.len() > MAX_POLL_OPTIONS
+                || options
+                    .iter()
+                    .any(|option| option.is_empty() || option.len() > MAX_DISPLAY_NAME_LENGTH)
+            {
+                return Err(Error::InvalidInput);
+            }
+            if closes_at <= self.env().block_timestamp() {
+                return Err(Error::InvalidInput);
+            }
+            let poll_id = self.poll_count.get(event_id).unwrap_or(0);
+            if poll_id >= MAX_POLLS_PER_EVENT {
+                return Err(Error::InvalidInput);
+            }
+
+            let votes = ink::prelude::vec![0; options.len()];
+            self.polls.insert(
+                (event_id, poll_id),
+                &Poll {
+                    id: poll_id,
+                    question,
+                    options,
+                    votes,
+                    closes_at,
+                },
+            );
+            self.poll_count.insert(event_id, &poll_id.saturating_add(1));
+
+            self.env().emit_event(PollCreated {
+                event_id,
+                poll_id,
+                closes_at,
+            });
+
+            Ok(poll_id)
+        }
+
+        /// Vote in a poll of the ticket's event
+        ///
+        /// Each ticket votes once per poll, so a ticket that changes hands
+        /// after voting can't vote again. Only the ticket owner can vote.
+        ///
+        /// # Arguments
+        /// * `option` - Index into the poll's options
+        #[ink(message)]
+        pub fn vote(&mut self, ticket_id: u64, poll_id: u32, option: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            if ticket.owner != caller {
+                return Err(Error::NotTicketOwner);
+            }
+            if ticket.is_refunded {
+                return Err(Error::TicketAlreadyRefunded);
+            }
+
+            let event_id = ticket.event_id;
+            let mut poll = self.polls.get((event_id, poll_id)).ok_or(Error::PollNotFound)?;
+            if self.env().block_timestamp() >= poll.closes_at {
+                return Err(Error::PollClosed);
+            }
+            if self.poll_votes.contains((event_id, poll_id, ticket_id)) {
+                return Err(Error::AlreadyVoted);
+            }
+            let tally = poll.votes.get_mut(option as usize).ok_or(Error::InvalidInput)?;
+            *tally = tally.saturating_add(1);
+
+            self.polls.insert((event_id, poll_id), &poll);
+            self.poll_votes.insert((event_id, poll_id, ticket_id), &option);
+
+            self.env().emit_event(VoteCast {
+                ticket_id,
+                event_id,
+                poll_id,
+                option,
+            });
+
+            Ok(())
+        }
+
+        /// Get a poll of an event, with its current tally
+        #[ink(message)]
+        pub fn get_poll(&self, event_id: u64, poll_id: u32) -> Option<Poll> {
+            self.polls.get((event_id, poll_id))
+        }
+
+        /// Get all polls of an event
+        #[ink(message)]
+        pub fn get_event_polls(&self, event_id: u64) -> Vec<Poll> {
+            let count = self.poll_count.get(event_id).unwrap_or(0);
+            (0..count)
+                .filter_map(|poll_id| self.polls.get((event_id, poll_id)))
+                .collect()
+        }
+
+        /// Get the option a ticket voted for in a poll, if it has voted
+        #[ink(message)]
+        pub fn get_ticket_vote(&self, event_id: u64, poll_id: u32, ticket_id: u64) -> Option<u32> {
+            self.poll_votes.get((event_id, poll_id, ticket_id))
+        }
+
+        /// Allow accounts to buy during an event's presale
+        /// Only the event organizer (or a co-organizer) can manage the allowlist
+        #[ink(message)]
+        pub fn add_to_allowlist(&mut self, event_id: u64, accounts: Vec<AccountId>) -> Result<(), Error> {
+            self.ensure_event_manager(event_id)?;
+            if accounts.len() > MAX_BATCH_SIZE {
+                return Err(Error::InvalidInput);
+            }
+
+            for account in accounts {
+                self.allowlist.insert((event_id, account), &());
+            }
+
+            Ok(())
+        }
+
+        /// Remove accounts from an event's presale allowlist
+        /// Only the event organizer (or a co-organizer) can manage the allowlist
+        #[ink(message)]
+        pub fn remove_from_allowlist(
+            &mut self,
+            event_id: u64,
+            accounts: Vec<AccountId>,
+        ) -> Result<(), Error> {
+            self.ensure_event_manager(event_id)?;
+            if accounts.len() > MAX_BATCH_SIZE {
+                return Err(Error::InvalidInput);
+            }
+
+            for account in accounts {
+                self.allowlist.remove((event_id, account));
+            }
+
+            Ok(())
+        }
+
+        /// Register or replace a promo code for an event
+        ///
+        /// Only the blake2-256 hash of the code is stored, so codes can't be
+        /// read back from storage. Only the event organizer (or a co-organizer) can manage codes.
+        ///
+        /// # Arguments
+        /// * `event_id` - ID of the event
+        /// * `code_hash` - blake2-256 hash of the code's UTF-8 bytes
+        /// * `discount_bps` - Discount on the base price, in basis points
+        /// * `max_uses` - Number of tickets the code can be used for
+        #[ink(message)]
+        pub fn add_promo_code(
+            &mut self,
+            event_id: u64,
+            code_hash: Hash,
+            discount_bps: u16,
+            max_uses: u32,
+        ) -> Result<(), Error> {
+            self.ensure_event_manager(event_id)?;
+            if discount_bps == 0 || discount_bps as Balance > BPS_DENOMINATOR || max_uses == 0 {
+                return Err(Error::InvalidInput);
+            }
+
+            self.promo_codes.insert(
+                (event_id, code_hash),
+                &PromoCode {
+                    discount_bps,
+                    remaining_uses: max_uses,
+                    times_used: 0,
+                    total_discount: 0,
+                },
+            );
+
+            Ok(())
+        }
+
+        /// Remove a promo code from an event
+        /// Only the event organizer (or a co-organizer) can manage codes
+        #[ink(message)]
+        pub fn remove_promo_code(&mut self, event_id: u64, code_hash: Hash) -> Result<(), Error> {
+            self.ensure_event_manager(event_id)?;
+            self.promo_codes
+                .take((event_id, code_hash))
+                .map(|_| ())
+                .ok_or(Error::PromoCodeNotFound)
+        }
+
+        /// Get a promo code's discount and usage
+        #[ink(message)]
+        pub fn get_promo_code(&self, event_id: u64, code_hash: Hash) -> Option<PromoCode> {
+            self.promo_codes.get((event_id, code_hash))
+        }
+
+        /// Enable or disable attendance NFTs for an event
+        ///
+        /// While set, every holder's first check-in mints a soulbound
+        /// attendance NFT with this metadata CID. Only the event organizer
+        /// (or a co-organizer) can change it.
+        #[ink(message)]
+        pub fn set_attendance_nft(
+            &mut self,
+            event_id: u64,
+            metadata_cid: Option<String>,
+        ) -> Result<(), Error> {
+            self.ensure_event_manager(event_id)?;
+
+            match metadata_cid {
+                Some(metadata_cid) => {
+                    if metadata_cid.is_empty() || metadata_cid.len() > self.state.config.max_metadata_cid_length as usize {
+                        return Err(Error::InvalidInput);
+                    }
+                    self.attendance_nft_cids.insert(event_id, &metadata_cid);
+                }
+                None => self.attendance_nft_cids.remove(event_id),
+            }
+
+            Ok(())
+        }
+
+        /// Get the metadata CID of an event's attendance NFT
+        #[ink(message)]
+        pub fn get_attendance_nft_cid(&self, event_id: u64) -> Option<String> {
+            self.attendance_nft_cids.get(event_id)
+        }
+
+        /// Get the attendance NFT an account received for an event
+        #[ink(message)]
+        pub fn get_attendance_nft(&self, event_id: u64, account: AccountId) -> Option<AttendanceToken> {
+            self.attendance.get((event_id, account))
+        }
+
+        /// Share operational control of an event with a team member
+        ///
+        /// Co-organizers can complete the event, check tickets in, and manage
+        /// metadata, allowlists, promo codes, and check-in staff. Cancelling,
+        /// rescheduling, and anything touching the event's funds stay with
+        /// the organizer. Only the event organizer can manage co-organizers.
+        #[ink(message)]
+        pub fn add_co_organizer(&mut self, event_id: u64, account: AccountId) -> Result<(), Error> {
+            let event = self.ensure_organizer(event_id)?;
+            if account == event.organizer {
+                return Err(Error::InvalidInput);
+            }
+            self.co_organizers.insert((event_id, account), &());
+            Ok(())
+        }
+
+        /// Revoke an account's co-organizer rights for an event
+        /// Only the event organizer can manage co-organizers
+        #[ink(message)]
+        pub fn remove_co_organizer(&mut self, event_id: u64, account: AccountId) -> Result<(), Error> {
+            self.ensure_organizer(event_id)?;
+            self.co_organizers.remove((event_id, account));
+            Ok(())
+        }
+
+        /// Check whether an account is a co-organizer of an event
+        #[ink(message)]
+        pub fn is_co_organizer(&self, event_id: u64, account: AccountId) -> bool {
+            self.co_organizers.contains((event_id, account))
+        }
+
+        /// Let an account check tickets in for an event (e.g. a gate scanner)
+        /// Only the event organizer (or a co-organizer) can manage check-in staff
+        #[ink(message)]
+        pub fn add_checkin_staff(&mut self, event_id: u64, account: AccountId) -> Result<(), Error> {
+            self.ensure_event_manager(event_id)?;
+            self.checkin_staff.insert((event_id, account), &());
+            Ok(())
+        }
+
+        /// Revoke an account's check-in access for an event
+        /// Only the event organizer (or a co-organizer) can manage check-in staff
+        #[ink(message)]
+        pub fn remove_checkin_staff(&mut self, event_id: u64, account: AccountId) -> Result<(), Error> {
+            self.ensure_event_manager(event_id)?;
+            self.checkin_staff.remove((event_id, account));
+            Ok(())
+        }
+
+        /// Refuse an account at an event
+        ///
+        /// Blocked accounts can't buy, win, or be sent tickets for the event.
+        /// Unused tickets they already hold are refunded (minus the platform
+        /// fee) and burned. Only the event organizer can block attendees.
+        ///
+        /// # Returns
+        /// The number of tickets refunded
+        #[ink(message)]
+        pub fn block_attendee(&mut self, event_id: u64, account: AccountId) -> Result<u32, Error> {
+            let event = self.ensure_organizer(event_id)?;
+
+            // Refund the account's unused tickets for the event
+            let held: Vec<Ticket> = self
+                .owned_tickets(account)
+                .into_iter()
+                .filter_map(|ticket_id| self.tickets.get(ticket_id))
+                .filter(|ticket| {
+                    ticket.event_id == event_id && !ticket.is_refunded && ticket.times_used == 0
+                })
+                .collect();
+            let mut refunded: u32 = 0;
+            for ticket in held {
+                let refund_amount = ticket.price_paid.saturating_sub(ticket.fee_paid);
+                self.refund_holder(&event, ticket, refund_amount)?;
+                refunded = refunded.saturating_add(1);
+            }
+
+            self.blocked_attendees.insert((event_id, account), &());
+            Ok(refunded)
+        }
+
+        /// Let a blocked account get tickets for an event again
+        /// Only the event organizer can unblock attendees
+        #[ink(message)]
+        pub fn unblock_attendee(&mut self, event_id: u64, account: AccountId) -> Result<(), Error> {
+            self.ensure_organizer(event_id)?;
+            self.blocked_attendees.remove((event_id, account));
+            Ok(())
+        }
+
+        /// Check whether an account is blocked from an event
+        #[ink(message)]
+        pub fn is_attendee_blocked(&self, event_id: u64, account: AccountId) -> bool {
+            self.blocked_attendees.contains((event_id, account))
+        }
+
+        /// Check whether an account can check tickets in for an event
+        #[ink(message)]
+        pub fn is_checkin_staff(&self, event_id: u64, account: AccountId) -> bool {
+            self.checkin_staff.contains((event_id, account))
+        }
+
+        /// Check whether an account is on an event's presale allowlist
+        #[ink(message)]
+        pub fn is_allowlisted(&self, event_id: u64, account: AccountId) -> bool {
+            self.allowlist.contains((event_id, account))
+        }
+
+        /// Set or clear the Merkle root of an event's presale allowlist
+        ///
+        /// Leaves are the blake2-256 hash of each SCALE-encoded account and
+        /// pairs are hashed in sorted order. Buyers prove membership with
+        /// `buy_ticket_with_proof`, so the list itself never touches storage.
+        /// Only the event organizer (or a co-organizer) can set the root.
+        #[ink(message)]
+        pub fn set_allowlist_root(&mut self, event_id: u64, root: Option<Hash>) -> Result<(), Error> {
+            self.ensure_event_manager(event_id)?;
+
+            match root {
+                Some(root) => {
+                    self.allowlist_roots.insert(event_id, &root);
+                }
+                None => self.allowlist_roots.remove(event_id),
+            }
+
+            Ok(())
+        }
+
+        /// Get the Merkle root of an event's presale allowlist
+        #[ink(message)]
+        pub fn get_allowlist_root(&self, event_id: u64) -> Option<Hash> {
+            self.allowlist_roots.get(event_id)
+        }
+
+        /// Turn an event into a reserved-seating event
+        ///
+        /// Must be done before any ticket is sold, and the layout must have
+        /// a seat for every ticket. Only the event organizer can set it.
+        #[ink(message)]
+        pub fn set_seat_layout(&mut self, event_id: u64, layout: SeatLayout) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+
+            if caller != event.organizer {
+                return Err(Error::NotOrganizer);
+            }
+            if event.available_tickets != event.total_tickets {
+                return Err(Error::InvalidInput);
+            }
+
+            let seat_count = layout.seat_count();
+            if seat_count < event.total_tickets || seat_count > MAX_SEATS_PER_EVENT {
+                return Err(Error::InvalidInput);
+            }
+
+            self.seat_layouts.insert(event_id, &layout);
+
+            Ok(())
+        }
+
+        /// Get the seat layout of a reserved-seating event
+        #[ink(message)]
+        pub fn get_seat_layout(&self, event_id: u64) -> Option<SeatLayout> {
+            self.seat_layouts.get(event_id)
+        }
+
+        /// Get the seats of an event that are still free, in layout order
+        #[ink(message)]
+        pub fn get_available_seats(&self, event_id: u64) -> Vec<u32> {
+            match self.seat_layouts.get(event_id) {
+                Some(layout) => layout
+                    .seats()
+                    .into_iter()
+                    .filter(|seat_id| !self.booked_seats.contains((event_id, *seat_id)))
+                    .filter(|seat_id| !self.seat_auctions.contains((event_id, *seat_id)))
+                    .collect(),
+                None => Vec::new(),
+            }
+        }
+
+        /// Auction a seat of a reserved-seating event to the highest bidder
+        /// Only the event organizer can auction seats
+        ///
+        /// The seat is held back from regular sales until `settle_auction`.
+        /// Bids are in the native token, so PSP22-priced events can't
+        /// auction seats.
+        ///
+        /// # Arguments
+        /// * `reserve` - Lowest acceptable bid
+        /// * `end_time` - Timestamp bidding closes at
+        #[ink(message)]
+        pub fn create_seat_auction(
+            &mut self,
+            event_id: u64,
+            seat_id: u32,
+            reserve: Balance,
+            end_time: u64,
+        ) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            let event = self.ensure_organizer(event_id)?;
+            if !event.is_live() {
+                return Err(Error::EventNotActive);
+            }
+            if event.payment_token.is_some() || end_time <= self.env().block_timestamp() {
+                return Err(Error::InvalidInput);
+            }
+            let layout = self.seat_layouts.get(event_id).ok_or(Error::InvalidSeat)?;
+            if !layout.contains(seat_id) {
+                return Err(Error::InvalidSeat);
+            }
+            if self.booked_seats.contains((event_id, seat_id))
+                || self.seat_auctions.contains((event_id, seat_id))
+            {
+                return Err(Error::SeatTaken);
+            }
+
+            self.seat_auctions.insert(
+                (event_id, seat_id),
+                &SeatAuction {
+                    reserve,
+                    end_time,
+                    highest_bidder: None,
+                    highest_bid: 0,
+                },
+            );
+
+            self.env().emit_event(SeatAuctionCreated {
+                event_id,
+                seat_id,
+                reserve,
+                end_time,
+            });
+
+            Ok(())
+        }
+
+        /// Bid the transferred value on a seat auction
+        ///
+        /// Bidders must meet the event's purchase restrictions and
+        /// per-person quota, like buyers. The previous highest bidder's bid
+        /// is credited back to them, to pull with `withdraw_bid_refunds`:
+        /// paying it out here would let a bidder whose account rejects
+        /// transfers block everyone from outbidding them.
+        ///
+        /// # Returns
+        /// - `Err(Error::AuctionEnded)` - Bidding has closed
+        /// - `Err(Error::BidTooLow)` - Bid is below the reserve or doesn't
+        ///   beat the highest bid
+        #[ink(message, payable)]
+        pub fn bid(&mut self, event_id: u64, seat_id: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            if !event.is_live() {
+                return Err(Error::EventNotActive);
+            }
+            let mut auction = self
+                .seat_auctions
+                .get((event_id, seat_id))
+                .ok_or(Error::AuctionNotFound)?;
+            if self.env().block_timestamp() >= auction.end_time {
+                return Err(Error::AuctionEnded);
+            }
+            if self.blocked_attendees.contains((event_id, caller)) {
+                return Err(Error::AttendeeBlocked);
+            }
+            self.ensure_eligible(&event, caller)?;
+            self.check_person_quota(event_id, caller, 1)?;
+
+            let amount = self.env().transferred_value();
+            let min_bid = match auction.highest_bidder {
+                Some(_) => auction.highest_bid.saturating_add(1),
+                None => auction.reserve.max(1),
+            };
+            if amount < min_bid {
+                return Err(Error::BidTooLow { min_bid });
+            }
+
+            // Refund the bid being beaten
+            if let Some(outbid) = auction.highest_bidder {
+                self.credit_bid_refund(outbid, auction.highest_bid);
+            }
+
+            auction.highest_bidder = Some(caller);
+            auction.highest_bid = amount;
+            self.seat_auctions.insert((event_id, seat_id), &auction);
+
+            self.env().emit_event(BidPlaced {
+                event_id,
+                bidder: caller,
+                seat_id,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Close a seat auction once bidding has ended, minting the seat's
+        /// ticket to the winner for their bid
+        ///
+        /// Anyone can settle. If nobody bid, the seat is released to regular
+        /// sales. If the winner can't get the ticket (the event was cancelled,
+        /// completed, frozen, or sold out in the meantime, or the winner was
+        /// blocked, no longer meets the purchase restrictions, or reached
+        /// their ticket or per-person limit), the bid is credited back to the
+        /// winner instead, like an outbid bid.
+        ///
+        /// # Returns
+        /// - `Ok(Some(ticket_id))` - The winner's ticket
+        /// - `Ok(None)` - No ticket was minted
+        #[ink(message)]
+        pub fn settle_auction(
+            &mut self,
+            event_id: u64,
+            seat_id: u32,
+        ) -> Result<Option<u64>, Error> {
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            let auction = self
+                .seat_auctions
+                .get((event_id, seat_id))
+                .ok_or(Error::AuctionNotFound)?;
+            if self.env().block_timestamp() < auction.end_time {
+                return Err(Error::AuctionNotEnded);
+            }
+            self.seat_auctions.remove((event_id, seat_id));
+
+            let Some(winner) = auction.highest_bidder else {
+                self.env().emit_event(SeatAuctionSettled {
+                    event_id,
+                    seat_id,
+                    winner: None,
+                    ticket_id: None,
+                    price: 0,
+                });
+                return Ok(None);
+            };
+
+            // A failed mint must not lock the winning bid in the contract.
+            // Restrictions may have changed since the bid, so check again.
+            let sellable = event.is_live()
+                && !event.frozen
+                && self.unreserved_available(&event) > 0
+                && self.ensure_eligible(&event, winner).is_ok();
+            let person = if sellable {
+                self.claim_person_quota(event_id, winner, 1).ok()
+            } else {
+                None
+            };
+            let ticket_id = match person {
+                Some(person) => {
+                    let price = auction.highest_bid;
+                    match self.mint_ticket(&mut event, winner, None, Some(seat_id), price) {
+                        Ok(ticket_id) => {
+                            self.record_ticket_person(ticket_id, person);
+                            Some(ticket_id)
+                        }
+                        Err(_) => {
+                            self.release_person_quota(event_id, person);
+                            None
+                        }
+                    }
+                }
+                None => None,
+            };
+            if ticket_id.is_none() {
+                self.credit_bid_refund(winner, auction.highest_bid);
+            }
+
+            self.env().emit_event(SeatAuctionSettled {
+                event_id,
+                seat_id,
+                winner: auction.highest_bidder,
+                ticket_id,
+                price: auction.highest_bid,
+            });
+
+            Ok(ticket_id)
+        }
+
+        /// Withdraw the caller's outbid and refunded auction bids
+        #[ink(message)]
+        pub fn withdraw_bid_refunds(&mut self) -> Result<Balance, Error> {
+            let caller = self.env().caller();
+
+            let amount = self.bid_refunds.take(caller).unwrap_or(0);
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+            self.pay_out(None, caller, amount)?;
+
+            Ok(amount)
+        }
+
+        /// Get the auction bids an account can withdraw
+        #[ink(message)]
+        pub fn get_bid_refund(&self, account: AccountId) -> Balance {
+            self.bid_refunds.get(account).unwrap_or(0)
+        }
+
+        /// Get the open auction for a seat
+        #[ink(message)]
+        pub fn get_seat_auction(&self, event_id: u64, seat_id: u32) -> Option<SeatAuction> {
+            self.seat_auctions.get((event_id, seat_id))
+        }
+
+        /// Subscribe to notifications for an event or an organizer
+        ///
+        /// The endpoint hash is opaque to the contract; off-chain notifiers
+        /// resolve it to a delivery channel (email, push, webhook).
+        /// Subscribing again to the same target replaces the endpoint hash.
+        ///
+        /// # Returns
+        /// - `Err(Error::EventNotFound)` - Target event doesn't exist
+        /// - `Err(Error::TooManySubscriptions)` - Subscriber reached the limit
+        #[ink(message)]
+        pub fn subscribe(
+            &mut self,
+            target: SubscriptionTarget,
+            endpoint_hash: Hash,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            if let SubscriptionTarget::Event(event_id) = target {
+                if !self.events.contains(event_id) {
+                    return Err(Error::EventNotFound);
+                }
+            }
+
+            // Only count new subscriptions against the limit
+            if !self.subscriptions.contains((caller, target)) {
+                let count = self.subscription_count.get(caller).unwrap_or(0);
+                if count >= MAX_SUBSCRIPTIONS_PER_USER {
+                    return Err(Error::TooManySubscriptions);
+                }
+                self.subscription_count.insert(caller, &count.saturating_add(1));
+            }
+            self.subscriptions.insert((caller, target), &endpoint_hash);
+
+            self.env().emit_event(Subscribed {
+                subscriber: caller,
+                target,
+                endpoint_hash,
+            });
+
+            Ok(())
+        }
+
+        /// Remove a notification subscription
+        #[ink(message)]
+        pub fn unsubscribe(&mut self, target: SubscriptionTarget) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            if self.subscriptions.take((caller, target)).is_none() {
+                return Err(Error::SubscriptionNotFound);
+            }
+            let count = self.subscription_count.get(caller).unwrap_or(0);
+            self.subscription_count.insert(caller, &count.saturating_sub(1));
+
+            self.env().emit_event(Unsubscribed {
+                subscriber: caller,
+                target,
+            });
+
+            Ok(())
+        }
+
+        /// Get the endpoint hash of a subscription, if any
+        #[ink(message)]
+        pub fn get_subscription(
+            &self,
+            subscriber: AccountId,
+            target: SubscriptionTarget,
+        ) -> Option<Hash> {
+            self.subscriptions.get((subscriber, target))
+        }
+
+        /// Submit a rating and review for a completed event
+        ///
+        /// Only the holder of a checked-in ticket can review, once per event.
+        ///
+        /// # Arguments
+        /// * `ticket_id` - A used ticket proving attendance
+        /// * `rating` - Rating from 1 to 5
+        /// * `review_cid` - IPFS CID of the review text (may be empty)
+        #[ink(message)]
+        pub fn submit_review(
+            &mut self,
+            ticket_id: u64,
+            rating: u8,
+            review_cid: String,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            if !(MIN_RATING..=MAX_RATING).contains(&rating)
+                || review_cid.len() > self.state.config.max_metadata_cid_length as usize
+            {
+                return Err(Error::InvalidInput);
+            }
+
+            // Get ticket and verify attendance
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            if ticket.owner != caller {
+                return Err(Error::NotTicketOwner);
+            }
+            if ticket.times_used == 0 {
+                return Err(Error::TicketNotUsed);
+            }
+
+            // Reviews open once the event is completed
+            let event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+            if !event.is_completed() {
+                return Err(Error::EventNotCompleted);
+            }
+
+            // One review per account per event
+            if self.reviews.contains((event.id, caller)) {
+                return Err(Error::AlreadyReviewed);
+            }
+
+            let review = Review {
+                event_id: event.id,
+                reviewer: caller,
+                rating,
+                review_cid: review_cid.clone(),
+                submitted_at: self.env().block_timestamp(),
+            };
+            self.reviews.insert((event.id, caller), &review);
+
+            // Update aggregates
+            let mut event_rating = self.event_ratings.get(event.id).unwrap_or_default();
+            event_rating.add(rating);
+            self.event_ratings.insert(event.id, &event_rating);
+
+            let mut organizer_rating = self.organizer_ratings.get(event.organizer).unwrap_or_default();
+            organizer_rating.add(rating);
+            self.organizer_ratings.insert(event.organizer, &organizer_rating);
+
+            self.env().emit_event(ReviewSubmitted {
+                event_id: event.id,
+                reviewer: caller,
+                rating,
+                review_cid,
+            });
+
+            Ok(())
+        }
+
+        /// Get a reviewer's review of an event
+        #[ink(message)]
+        pub fn get_review(&self, event_id: u64, reviewer: AccountId) -> Option<Review> {
+            self.reviews.get((event_id, reviewer))
+        }
+
+        /// Get aggregated ratings for an event
+        #[ink(message)]
+        pub fn get_event_rating(&self, event_id: u64) -> RatingSummary {
+            self.event_ratings.get(event_id).unwrap_or_default()
+        }
+
+        /// Get aggregated ratings for an organizer across all their events
+        #[ink(message)]
+        pub fn get_organizer_rating(&self, organizer: AccountId) -> RatingSummary {
+            self.organizer_ratings.get(organizer).unwrap_or_default()
+        }
+
+        /// Register a referral code for the caller
+        ///
+        /// Codes are first-come-first-served and can't be reassigned.
+        #[ink(message)]
+        pub fn register_referral_code(&mut self, code: String) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            if code.is_empty() || code.len() > MAX_REFERRAL_CODE_LENGTH {
+                return Err(Error::InvalidInput);
+            }
+            if self.referral_codes.contains(&code) {
+                return Err(Error::ReferralCodeTaken);
+            }
+
+            self.referral_codes.insert(&code, &caller);
+
+            self.env().emit_event(ReferralCodeRegistered {
+                referrer: caller,
+                code,
+            });
+
+            Ok(())
+        }
+
+        /// Opt an event in or out of the referral program
+        /// Only the event organizer can change this
+        #[ink(message)]
+        pub fn set_referrals_enabled(&mut self, event_id: u64, enabled: bool) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+
+            if caller != event.organizer {
+                return Err(Error::NotOrganizer);
+            }
+
+            event.referrals_enabled = enabled;
+            self.events.insert(event_id, &event);
+
+            Ok(())
+        }
+
+        /// Mint a free complimentary ticket for press, sponsors, or staff
+        ///
+        /// Comp tickets use regular capacity but count against the event's
+        /// comp quota and carry no payment, so they never affect earnings.
+        /// Only the event organizer can issue them.
+        ///
+        /// # Returns
+        /// - `Err(Error::CompQuotaExceeded)` - Event's comp quota is used up
+        #[ink(message)]
+        pub fn mint_comp_ticket(&mut self, event_id: u64, recipient: AccountId) -> Result<u64, Error> {
+            self.ensure_not_paused()?;
+            let mut event = self.ensure_organizer(event_id)?;
+            if !event.is_live() {
+                return Err(Error::EventNotActive);
+            }
+            if event.comps_issued >= event.comp_quota {
+                return Err(Error::CompQuotaExceeded);
+            }
+
+            event.comps_issued = event.comps_issued.saturating_add(1);
+            self.mint_free_ticket(&mut event, recipient)
+        }
+
+        /// Mint one free ticket to each recipient, e.g. for giveaways
+        ///
+        /// Airdropped tickets are flagged `is_comp` like comp tickets but
+        /// don't count against the comp quota. Only the event organizer can
+        /// airdrop, at most `MAX_BATCH_SIZE` recipients per call.
+        ///
+        /// # Returns
+        /// - `Ok(Vec<u64>)` - IDs of the minted tickets, in recipient order
+        /// - `Err(Error::SoldOut)` - Not enough tickets left for every recipient
+        #[ink(message)]
+        pub fn airdrop_tickets(
+            &mut self,
+            event_id: u64,
+            recipients: Vec<AccountId>,
+        ) -> Result<Vec<u64>, Error> {
+            self.ensure_not_paused()?;
+            let mut event = self.ensure_organizer(event_id)?;
+            if !event.is_live() {
+                return Err(Error::EventNotActive);
+            }
+            if recipients.is_empty() || recipients.len() > MAX_BATCH_SIZE {
+                return Err(Error::InvalidInput);
+            }
+
+            let mut ticket_ids = Vec::with_capacity(recipients.len());
+            for recipient in recipients {
+                ticket_ids.push(self.mint_free_ticket(&mut event, recipient)?);
+            }
+
+            Ok(ticket_ids)
+        }
+
+        /// Redeem an organizer-signed voucher for a free ticket
+        ///
+        /// The organizer hands out vouchers off-chain (e.g. by email or QR
+        /// code) by signing the blake2-256 hash of the SCALE-encoded
+        /// `(contract_address, event_id, nonce, recipient)` with the ECDSA key
+        /// behind their account. Only `recipient` can redeem the voucher, so
+        /// a leaked voucher can't be claimed by whoever sees it first. Each
+        /// nonce can be redeemed once per event.
+        ///
+        /// Only ECDSA-controlled organizer accounts can sign vouchers;
+        /// organizers with sr25519 or ed25519 keys should use
+        /// `airdrop_tickets` or `mint_comp_ticket` instead.
+        ///
+        /// # Arguments
+        /// * `event_id` - ID of the event
+        /// * `nonce` - Voucher number chosen by the organizer
+        /// * `signature` - 65-byte recoverable ECDSA signature
+        ///
+        /// # Returns
+        /// - `Err(Error::InvalidSignature)` - Not signed by the organizer
+        /// - `Err(Error::VoucherAlreadyRedeemed)` - Nonce was used before
+        #[ink(message)]
+        pub fn redeem_voucher(
+            &mut self,
+            event_id: u64,
+            nonce: u64,
+            signature: [u8; 65],
+        ) -> Result<u64, Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            if !event.is_live() {
+                return Err(Error::EventNotActive);
+            }
+            if self.redeemed_vouchers.contains((event_id, nonce)) {
+                return Err(Error::VoucherAlreadyRedeemed);
+            }
+
+            // Check the organizer signed this voucher for the caller
+            self.verify_signature(&(event_id, nonce, caller), &signature, event.organizer)?;
+
+            self.redeemed_vouchers.insert((event_id, nonce), &());
+            self.mint_free_ticket(&mut event, caller)
+        }
+
+        /// Check in a ticket whose holder signed the scanner's challenge
+        ///
+        /// The scanner shows a fresh random `challenge`; the holder's wallet
+        /// signs the blake2-256 hash of the SCALE-encoded
+        /// `(contract_address, ticket_id, challenge)` with the ECDSA key
+        /// behind the owning account. This proves the holder is present,
+        /// so a screenshot of someone else's QR code isn't enough. The
+        /// caller must be allowed to `use_ticket`, and each challenge works
+        /// once per ticket.
+        ///
+        /// # Returns
+        /// - `Err(Error::InvalidSignature)` - Not signed by the ticket owner
+        /// - `Err(Error::ChallengeAlreadyUsed)` - Challenge was used before
+        #[ink(message)]
+        pub fn verify_checkin(
+            &mut self,
+            ticket_id: u64,
+            challenge: [u8; 32],
+            signature: [u8; 65],
+        ) -> Result<(), Error> {
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            if self.used_challenges.contains((ticket_id, challenge)) {
+                return Err(Error::ChallengeAlreadyUsed);
+            }
+
+            // Check the current owner signed this challenge
+            self.verify_signature(&(ticket_id, challenge), &signature, ticket.owner)?;
+
+            self.use_ticket(ticket_id)?;
+            self.used_challenges.insert((ticket_id, challenge), &());
+
+            Ok(())
+        }
+
+        /// Check whether a voucher nonce has been redeemed for an event
+        #[ink(message)]
+        pub fn is_voucher_redeemed(&self, event_id: u64, nonce: u64) -> bool {
+            self.redeemed_vouchers.contains((event_id, nonce))
+        }
+
+        /// Set how many complimentary tickets an event can issue
+        /// Only the event organizer can change this
+        #[ink(message)]
+        pub fn set_comp_quota(&mut self, event_id: u64, quota: u32) -> Result<(), Error> {
+            let mut event = self.ensure_organizer(event_id)?;
+            if quota > event.total_tickets {
+                return Err(Error::InvalidInput);
+            }
+
+            event.comp_quota = quota;
+            self.events.insert(event_id, &event);
+
+            Ok(())
+        }
+
+        /// Set the share of each referred sale paid to the referrer
+        /// Only the event organizer can change this
+        #[ink(message)]
+        pub fn set_referral_reward_bps(&mut self, event_id: u64, bps: u16) -> Result<(), Error> {
+            let mut event = self.ensure_organizer(event_id)?;
+            if bps > MAX_REFERRAL_REWARD_BPS {
+                return Err(Error::InvalidInput);
+            }
+
+            event.referral_reward_bps = bps;
+            self.events.insert(event_id, &event);
+
+            Ok(())
+        }
+
+        /// Get referral rewards an account can claim (`None` for native token)
+        #[ink(message)]
+        pub fn get_referral_rewards(&self, account: AccountId, token: Option<AccountId>) -> Balance {
+            self.referral_rewards.get((account, token)).unwrap_or(0)
+        }
+
+        /// Set the share of the platform fee paid to referrers on referred sales
+        /// Only fee managers (and admins) can change this
+        ///
+        /// # Arguments
+        /// * `share_bps` - Share of the sale's fee in basis points, at most 100%
+        #[ink(message)]
+        pub fn set_referral_fee_share(&mut self, share_bps: u16) -> Result<(), Error> {
+            self.ensure_role(Role::FeeManager)?;
+            if share_bps as Balance > BPS_DENOMINATOR {
+                return Err(Error::InvalidInput);
+            }
+            self.state.referral_fee_share_bps = share_bps;
+            Ok(())
+        }
+
+        /// Get the share of the platform fee paid to referrers on referred sales
+        #[ink(message)]
+        pub fn get_referral_fee_share(&self) -> u16 {
+            self.state.referral_fee_share_bps
+        }
+
+        /// Get the account a referral code belongs to
+        #[ink(message)]
+        pub fn get_referrer(&self, code: String) -> Option<AccountId> {
+            self.referral_codes.get(&code)
+        }
+
+        /// Set the maximum number of tickets per `buy_tickets` call
+        /// Only the admin can change this
+        #[ink(message)]
+        pub fn set_max_tickets_per_purchase(&mut self, max: u32) -> Result<(), Error> {
+            self.ensure_role(Role::Admin)?;
+            if max == 0 || max > MAX_TICKETS_PER_PURCHASE_LIMIT {
+                return Err(Error::InvalidInput);
+            }
+            self.state.max_tickets_per_purchase = max;
+            Ok(())
+        }
+
+        /// Get the maximum number of tickets per `buy_tickets` call
+        #[ink(message)]
+        pub fn get_max_tickets_per_purchase(&self) -> u32 {
+            self.state.max_tickets_per_purchase
+        }
+
+        /// Set the platform limits on new events
+        /// Only the admin can change this
+        ///
+        /// Limits only apply to later calls; existing events keep their
+        /// settings. Every limit must stay within its hard bound.
+        #[ink(message)]
+        pub fn set_config(&mut self, config: Config) -> Result<(), Error> {
+            self.ensure_role(Role::Admin)?;
+            if !config.is_valid() {
+                return Err(Error::InvalidInput);
+            }
+            self.state.config = config;
+            Ok(())
+        }
+
+        /// Get the platform limits on new events
+        #[ink(message)]
+        pub fn get_config(&self) -> Config {
+            self.state.config.clone()
+        }
+
+        /// Get a ticket's last `MAX_TICKET_HISTORY` owner changes, oldest first
+        ///
+        /// The first record has no `from` if the ticket's minting is still
+        /// within the window.
+        #[ink(message)]
+        pub fn get_ticket_history(&self, ticket_id: u64) -> Vec<OwnershipRecord> {
+            let count = self.ticket_history_count.get(ticket_id).unwrap_or(0);
+            (count.saturating_sub(MAX_TICKET_HISTORY)..count)
+                .filter_map(|index| {
+                    self.ticket_history.get((ticket_id, index % MAX_TICKET_HISTORY))
+                })
+                .collect()
+        }
+
+        /// Look up several events at once
+        ///
+        /// Results line up with `ids`, with `None` for unknown events. Only
+        /// the first `MAX_PAGE_SIZE` IDs are looked up.
+        #[ink(message)]
+        pub fn get_events_by_ids(&self, ids: Vec<u64>) -> Vec<Option<Event>> {
+            ids.into_iter()
+                .take(MAX_PAGE_SIZE as usize)
+                .map(|event_id| self.events.get(event_id))
+                .collect()
+        }
+
+        /// Look up several tickets at once
+        ///
+        /// Results line up with `ids`, with `None` for unknown tickets. Only
+        /// the first `MAX_PAGE_SIZE` IDs are looked up.
+        #[ink(message)]
+        pub fn get_tickets(&self, ids: Vec<u64>) -> Vec<Option<Ticket>> {
+            ids.into_iter()
+                .take(MAX_PAGE_SIZE as usize)
+                .map(|ticket_id| self.tickets.get(ticket_id))
+                .collect()
+        }
+
+        /// Get a page of events by ID, oldest first
+        ///
+        /// # Arguments
+        /// * `offset` - ID of the first event to return
+        /// * `limit` - Maximum number of events (capped at `MAX_PAGE_SIZE`)
+        #[ink(message)]
+        pub fn get_events(&self, offset: u64, limit: u32) -> Vec<Event> {
+            self.events_page(offset, limit).collect()
+        }
+
+        /// Get the events still selling tickets from a page of event IDs
+        ///
+        /// Scans the same IDs as `get_events` and skips events that aren't on
+        /// sale (drafts, paused, cancelled, or completed) or are unlisted
+        /// private events, so pages can come back shorter than `limit`.
+        #[ink(message)]
+        pub fn get_active_events(&self, offset: u64, limit: u32) -> Vec<Event> {
+            self.events_page(offset, limit)
+                .filter(|event| event.status == EventStatus::OnSale)
+                .filter(|event| !self.unlisted_events.contains(event.id))
+                .collect()
+        }
+
+        /// Get all tickets owned by an account
+        /// 
+        /// Returns only valid (non-refunded) tickets.
+        /// Refunded tickets are immediately removed from this list when cancelled/refunded,
+        /// so no runtime filtering is needed.
+        /// 
+        /// # Performance
+        /// - One storage read per ticket held; use `get_my_tickets_page` for
+        ///   large holdings
+        /// - No iteration through refunded tickets
+        /// - Tickets are in purchase order until one is removed, which moves
+        ///   the last ticket into its place
+        #[ink(message)]
+        pub fn get_my_tickets(&self, owner: AccountId) -> Vec<u64> {
+            // Since we immediately remove refunded tickets from the index,
+            // we can simply return the list without filtering
+            self.owned_tickets(owner)
+        }
+
+        /// Get a page of the tickets owned by an account
+        ///
+        /// # Arguments
+        /// * `offset` - Index of the first ticket to return
+        /// * `limit` - Maximum number of tickets (capped at `MAX_PAGE_SIZE`)
+        #[ink(message)]
+        pub fn get_my_tickets_page(&self, owner: AccountId, offset: u32, limit: u32) -> Vec<u64> {
+            let count = self.owner_ticket_count.get(owner).unwrap_or(0);
+            let end = offset
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(count);
+            (offset..end)
+                .filter_map(|index| self.owner_ticket_at.get((owner, index)))
+                .collect()
+        }
+
+        /// Get a page of an account's tickets together with their events
+        ///
+        /// Same tickets and order as `get_my_tickets`, so a wallet can
+        /// render its ticket list in one call.
+        ///
+        /// # Arguments
+        /// * `offset` - Index of the first ticket to return
+        /// * `limit` - Maximum number of tickets (capped at `MAX_PAGE_SIZE`)
+        #[ink(message)]
+        pub fn get_my_tickets_detailed(
+            &self,
+            owner: AccountId,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(Ticket, EventSummary)> {
+            self.get_my_tickets_page(owner, offset, limit)
+                .into_iter()
+                .filter_map(|ticket_id| {
+                    let ticket = self.tickets.get(ticket_id)?;
+                    let event = self.events.get(ticket.event_id)?;
+                    Some((ticket, event.summary()))
+                })
+                .collect()
+        }
+
+        /// Get the IDs of the events an account organizes, oldest first
+        ///
+        /// # Arguments
+        /// * `offset` - Index of the first event to return
+        /// * `limit` - Maximum number of events (capped at `MAX_PAGE_SIZE`)
+        #[ink(message)]
+        pub fn get_events_by_organizer(&self, account: AccountId, offset: u32, limit: u32) -> Vec<u64> {
+            let count = self.organizer_event_count.get(account).unwrap_or(0);
+            let end = offset
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(count);
+            (offset..end)
+                .filter_map(|index| self.organizer_events.get((account, index)))
+                .collect()
+        }
+
+        /// Get the IDs of the events in a category, oldest first
+        ///
+        /// # Arguments
+        /// * `offset` - Index of the first event to return
+        /// * `limit` - Maximum number of events (capped at `MAX_PAGE_SIZE`)
+        #[ink(message)]
+        pub fn get_events_by_category(&self, category: u8, offset: u32, limit: u32) -> Vec<u64> {
+            let count = self.category_event_count.get(category).unwrap_or(0);
+            let end = offset
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(count);
+            (offset..end)
+                .filter_map(|index| self.category_events.get((category, index)))
+                .collect()
+        }
+
+        /// Get the IDs of the events with a tag, oldest first
+        ///
+        /// # Arguments
+        /// * `offset` - Index of the first event to return
+        /// * `limit` - Maximum number of events (capped at `MAX_PAGE_SIZE`)
+        #[ink(message)]
+        pub fn get_events_by_tag(&self, tag: String, offset: u32, limit: u32) -> Vec<u64> {
+            let count = self.tag_event_count.get(&tag).unwrap_or(0);
+            let end = offset
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(count);
+            (offset..end)
+                .filter_map(|index| self.tag_events.get((tag.clone(), index)))
+                .collect()
+        }
+
+        /// Get the tickets minted for an event, with their current holders
+        ///
+        /// Refunded tickets stay in the list with `is_refunded` set, so
+        /// pages don't shift as tickets are returned.
+        ///
+        /// # Arguments
+        /// * `offset` - Index of the first ticket to return
+        /// * `limit` - Maximum number of tickets (capped at `MAX_PAGE_SIZE`)
+        #[ink(message)]
+        pub fn get_event_tickets(&self, event_id: u64, offset: u32, limit: u32) -> Vec<Ticket> {
+            let count = self.event_ticket_count.get(event_id).unwrap_or(0);
+            let end = offset
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(count);
+            (offset..end)
+                .filter_map(|index| self.event_tickets.get((event_id, index)))
+                .filter_map(|ticket_id| self.tickets.get(ticket_id))
+                .collect()
+        }
+
+        /// Get sales, refund, and attendance totals for an event
+        #[ink(message)]
+        pub fn get_event_stats(&self, event_id: u64) -> Option<EventStats> {
+            if !self.events.contains(event_id) {
+                return None;
+            }
+            let mut stats = self.event_stats.get(event_id).unwrap_or_default();
+            stats.net_escrow = self.escrow_balance.get(event_id).unwrap_or(0);
+            Some(stats)
+        }
+
+        /// Get the number of tickets ever minted for an event
+        #[ink(message)]
+        pub fn get_event_ticket_count(&self, event_id: u64) -> u32 {
+            self.event_ticket_count.get(event_id).unwrap_or(0)
+        }
+
+        /// Get the number of events an account has created
+        #[ink(message)]
+        pub fn get_organizer_event_count(&self, account: AccountId) -> u32 {
+            self.organizer_event_count.get(account).unwrap_or(0)
+        }
+
+        /// Get an account's attendance history, oldest first
+        ///
+        /// # Arguments
+        /// * `cursor` - Index of the first record to return
+        /// * `limit` - Maximum number of records (capped at `MAX_PAGE_SIZE`)
+        #[ink(message)]
+        pub fn get_attendance_history(
+            &self,
+            account: AccountId,
+            cursor: u32,
+            limit: u32,
+        ) -> Vec<AttendanceRecord> {
+            let count = self.attendance_count.get(account).unwrap_or(0);
+            let end = cursor
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(count);
+            (cursor..end)
+                .filter_map(|index| self.attendance_records.get((account, index)))
+                .collect()
+        }
+
+        /// Get the number of events an account has checked in to
+        #[ink(message)]
+        pub fn get_attendance_count(&self, account: AccountId) -> u32 {
+            self.attendance_count.get(account).unwrap_or(0)
+        }
+
+        /// Get lifetime purchase, refund, and attendance totals for an account
+        #[ink(message)]
+        pub fn get_user_summary(&self, account: AccountId) -> UserSummary {
+            self.user_summaries.get(account).unwrap_or_default()
+        }
+
+        /// Get lifetime amounts spent and refunded in a PSP22 token
+        #[ink(message)]
+        pub fn get_user_token_totals(&self, account: AccountId, token: AccountId) -> TokenTotals {
+            self.token_totals.get((account, token)).unwrap_or_default()
+        }
+
+        /// Get total number of events created
+        #[ink(message)]
+        pub fn get_event_count(&self) -> u64 {
+            self.event_counter
+        }
+
+        /// Get total number of tickets minted
+        #[ink(message)]
+        pub fn get_ticket_count(&self) -> u64 {
+            self.ticket_counter
+        }
+
+        /// Grant a role to an account
+        /// Only admins can grant roles
+        #[ink(message)]
+        pub fn grant_role(&mut self, role: Role, account: AccountId) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if !self.has_role(Role::Admin, caller) {
+                return Err(Error::MissingRole);
+            }
+
+            if !self.roles.contains((role, account)) {
+                self.roles.insert((role, account), &());
+                if role == Role::Admin {
+                    self.state.admin_count = self.state.admin_count.saturating_add(1);
+                }
+            }
+
+            self.env().emit_event(RoleGranted {
+                role,
+                account,
+                by: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Revoke a role from an account
+        /// Only admins can revoke roles, and the last admin can't be removed
+        #[ink(message)]
+        pub fn revoke_role(&mut self, role: Role, account: AccountId) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if !self.has_role(Role::Admin, caller) {
+                return Err(Error::MissingRole);
+            }
+            if !self.roles.contains((role, account)) {
+                return Err(Error::MissingRole);
+            }
+
+            if role == Role::Admin {
+                if self.state.admin_count <= 1 {
+                    return Err(Error::CannotRemoveLastAdmin);
+                }
+                self.state.admin_count = self.state.admin_count.saturating_sub(1);
+            }
+            self.roles.remove((role, account));
+
+            self.env().emit_event(RoleRevoked {
+                role,
+                account,
+                by: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Replace the contract code, keeping all events and tickets
+        ///
+        /// The new code must already be uploaded on-chain. If it changes
+        /// the storage layout, call `migrate` right after upgrading.
+        /// Only admins can upgrade.
+        #[ink(message)]
+        pub fn upgrade_contract(&mut self, code_hash: Hash) -> Result<(), Error> {
+            self.ensure_role(Role::Admin)?;
+
+            if self.env().set_code_hash(&code_hash).is_err() {
+                return Err(Error::UpgradeFailed);
+            }
+
+            self.env().emit_event(ContractUpgraded { code_hash });
+
+            Ok(())
+        }
+
+        /// Bring storage written by older code up to `STORAGE_VERSION`
+        ///
+        /// Each layout change adds a step here, keyed on the version it
+        /// upgrades from. Steps that rewrite every event or ticket convert
+        /// up to `MAX_BATCH_SIZE` per call; call again until the version is
+        /// current. Until then every call that changes tickets or events
+        /// fails with `MigrationPending`. Only admins can migrate; on
+        /// version 1 storage that is the single admin it recorded.
+        #[ink(message)]
+        pub fn migrate(&mut self) -> Result<(), Error> {
+            // Version 1 kept one admin account instead of role grants
+            if let Some(admin) = self.state.legacy_admin {
+                if self.env().caller() != admin {
+                    return Err(Error::MissingRole);
+                }
+                self.roles.insert((Role::Admin, admin), &());
+                self.state.admin_count = 1;
+                self.state.legacy_admin = None;
+            }
+            self.ensure_role(Role::Admin)?;
+
+            let from_version = self.state.storage_version;
+            if from_version >= STORAGE_VERSION {
+                return Err(Error::AlreadyMigrated);
+            }
+
+            // Version 2 replaced the event `active`/`cancelled`/`completed`
+            // flags with `status` and indexed tickets by owner and event.
+            // The cursor walks every event, then every ticket.
+            if self.state.storage_version == 1 {
+                let total = self.event_counter.saturating_add(self.ticket_counter);
+                let start = self.state.migration_cursor;
+                let end = start.saturating_add(MAX_BATCH_SIZE as u64).min(total);
+                for step in start..end {
+                    if step < self.event_counter {
+                        if let Some(legacy) = self.legacy_event(step) {
+                            self.events.insert(step, &Event::from(legacy));
+                        }
+                    } else {
+                        self.migrate_legacy_ticket(step.saturating_sub(self.event_counter));
+                    }
+                }
+                self.state.migration_cursor = end;
+                if end < total {
+                    return Ok(());
+                }
+                self.state.migration_cursor = 0;
+            }
+
+            self.state.storage_version = STORAGE_VERSION;
+
+            self.env().emit_event(StorageMigrated {
+                from_version,
+                to_version: STORAGE_VERSION,
+            });
+
+            Ok(())
+        }
+
+        /// Get the layout version of the data in storage
+        #[ink(message)]
+        pub fn get_storage_version(&self) -> u32 {
+            self.state.storage_version
+        }
+
+        /// Set the name, symbol, and base URI of the ticket collection
+        /// Only admins can change this
+        ///
+        /// # Returns
+        /// - `Err(Error::InvalidInput)` - Empty or too long name/symbol, or too long URI
+        #[ink(message)]
+        pub fn set_collection_metadata(
+            &mut self,
+            name: String,
+            symbol: String,
+            base_uri: String,
+        ) -> Result<(), Error> {
+            self.ensure_role(Role::Admin)?;
+            if name.is_empty() || name.len() > MAX_DISPLAY_NAME_LENGTH {
+                return Err(Error::InvalidInput);
+            }
+            if symbol.is_empty() || symbol.len() > MAX_COLLECTION_SYMBOL_LENGTH {
+                return Err(Error::InvalidInput);
+            }
+            if base_uri.len() > self.state.config.max_metadata_cid_length as usize {
+                return Err(Error::InvalidInput);
+            }
+
+            self.state.collection_metadata = CollectionMetadata {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                base_uri: base_uri.clone(),
+            };
+
+            self.env().emit_event(CollectionMetadataUpdated {
+                admin: self.env().caller(),
+                name,
+                symbol,
+                base_uri,
+            });
+
+            Ok(())
+        }
+
+        /// Get the name, symbol, and base URI of the ticket collection
+        #[ink(message)]
+        pub fn get_collection_metadata(&self) -> CollectionMetadata {
+            self.state.collection_metadata.clone()
+        }
+
+        /// Set the `pallet-nfts` collection tickets are exported to
+        /// Only admins can change this; the contract must be able to mint
+        /// into the collection. `None` disables new exports.
+        #[ink(message)]
+        pub fn set_nft_collection(&mut self, collection: Option<u32>) -> Result<(), Error> {
+            self.ensure_role(Role::Admin)?;
+            self.state.nft_collection = collection;
+            Ok(())
+        }
+
+        /// Get the `pallet-nfts` collection tickets are exported to
+        #[ink(message)]
+        pub fn get_nft_collection(&self) -> Option<u32> {
+            self.state.nft_collection
+        }
+
+        /// Mirror a ticket into the runtime's NFT collection
+        ///
+        /// Makes the ticket visible to runtime-level NFT infrastructure
+        /// (Asset Hub, wallets) under the ticket ID as item ID. The contract
+        /// stays the source of truth: the mirror follows every transfer of
+        /// the ticket and is burned when the ticket is refunded.
+        /// Only the ticket owner can export.
+        #[ink(message)]
+        pub fn export_ticket(&mut self, ticket_id: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            let collection = self.state.nft_collection.ok_or(Error::NftCollectionNotSet)?;
+
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            if ticket.owner != caller {
+                return Err(Error::NotTicketOwner);
+            }
+            if ticket.is_refunded {
+                return Err(Error::TicketAlreadyRefunded);
+            }
+            if self.exported_tickets.contains(ticket_id) {
+                return Err(Error::TicketAlreadyExported);
+            }
+
+            self.env()
+                .extension()
+                .mint(collection, ticket_id, caller)
+                .map_err(|_| Error::NftMirrorFailed)?;
+            self.exported_tickets.insert(ticket_id, &collection);
+
+            self.env().emit_event(TicketExported {
+                ticket_id,
+                owner: caller,
+                collection,
+            });
+
+            Ok(())
+        }
+
+        /// Check whether a ticket is mirrored into the NFT collection
+        #[ink(message)]
+        pub fn is_ticket_exported(&self, ticket_id: u64) -> bool {
+            self.exported_tickets.contains(ticket_id)
+        }
+
+        /// Trust or distrust an account to relay XCM purchases
+        /// Only admins can change this
+        #[ink(message)]
+        pub fn set_xcm_origin(&mut self, origin: AccountId, trusted: bool) -> Result<(), Error> {
+            self.ensure_role(Role::Admin)?;
+            if trusted {
+                self.xcm_origins.insert(origin, &());
+            } else {
+                self.xcm_origins.remove(origin);
+            }
+            Ok(())
+        }
+
+        /// Check whether an account is a trusted XCM origin
+        #[ink(message)]
+        pub fn is_xcm_origin(&self, origin: AccountId) -> bool {
+            self.xcm_origins.contains(origin)
+        }
+
+        /// Check whether an account holds a role
+        #[ink(message)]
+        pub fn has_role(&self, role: Role, account: AccountId) -> bool {
+            self.roles.contains((role, account))
+        }
+
+        /// Pause the contract (emergency circuit breaker)
+        ///
+        /// While paused, purchases, transfers, check-ins, listings, event
+        /// creation, and withdrawals fail with `Error::ContractPaused`.
+        /// Getters and refunds keep working so users can still exit.
+        /// Only pausers (and admins) can pause.
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<(), Error> {
+            let caller = self.ensure_role(Role::Pauser)?;
+            self.state.paused = true;
+            self.env().emit_event(Paused { by: caller });
+            Ok(())
+        }
+
+        /// Lift an emergency pause
+        /// Only pausers (and admins) can unpause
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<(), Error> {
+            let caller = self.ensure_role(Role::Pauser)?;
+            self.state.paused = false;
+            self.env().emit_event(Unpaused { by: caller });
+            Ok(())
+        }
+
+        /// Check whether the contract is paused
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.state.paused
+        }
+
+        /// Set the platform fee taken from every primary sale
+        /// Only fee managers (and admins) can change this
+        ///
+        /// # Arguments
+        /// * `fee_bps` - Fee in basis points (e.g. 250 for 2.5%), at most 10%
+        #[ink(message)]
+        pub fn set_platform_fee(&mut self, fee_bps: u16) -> Result<(), Error> {
+            self.ensure_role(Role::FeeManager)?;
+            if fee_bps > MAX_PLATFORM_FEE_BPS {
+                return Err(Error::InvalidInput);
+            }
+            self.state.platform_fee_bps = fee_bps;
+            Ok(())
+        }
+
+        /// Set the bond organizers lock per new event
+        /// Only fee managers (and admins) can change this
+        ///
+        /// Existing events keep the bond they were created with.
+        ///
+        /// # Arguments
+        /// * `bond` - Bond in native token (0 = no bond)
+        /// * `slash_bps` - Share slashed when an organizer cancels; force-cancelled
+        ///   events are always slashed in full
+        #[ink(message)]
+        pub fn set_event_bond(&mut self, bond: Balance, slash_bps: u16) -> Result<(), Error> {
+            self.ensure_role(Role::FeeManager)?;
+            if Balance::from(slash_bps) > BPS_DENOMINATOR {
+                return Err(Error::InvalidInput);
+            }
+            self.state.event_bond = bond;
+            self.state.bond_slash_bps = slash_bps;
+            Ok(())
+        }
+
+        /// Get the bond required per new event and its slash share in basis points
+        #[ink(message)]
+        pub fn get_event_bond_requirement(&self) -> (Balance, u16) {
+            (self.state.event_bond, self.state.bond_slash_bps)
+        }
+
+        /// Get the bond still locked for an event
+        #[ink(message)]
+        pub fn get_event_bond(&self, event_id: u64) -> Balance {
+            self.event_bonds.get(event_id).unwrap_or(0)
+        }
+
+        /// Set the account receiving withdrawn platform fees
+        /// Only fee managers (and admins) can change this
+        #[ink(message)]
+        pub fn set_treasury(&mut self, treasury: AccountId) -> Result<(), Error> {
+            self.ensure_role(Role::FeeManager)?;
+            self.state.treasury = treasury;
+            Ok(())
+        }
+
+        /// Send accumulated native platform fees to the treasury
+        /// Only fee managers (and admins) can withdraw
+        #[ink(message)]
+        pub fn withdraw_platform_fees(&mut self) -> Result<Balance, Error> {
+            self.ensure_role(Role::FeeManager)?;
+
+            let amount = self.state.platform_fees;
+            self.state.platform_fees = 0;
+            self.pay_out(None, self.state.treasury, amount)?;
+
+            self.env().emit_event(PlatformFeesWithdrawn {
+                treasury: self.state.treasury,
+                token: None,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Send accumulated platform fees in a PSP22 token to the treasury
+        /// Only fee managers (and admins) can withdraw
+        #[ink(message)]
+        pub fn withdraw_platform_token_fees(&mut self, token: AccountId) -> Result<Balance, Error> {
+            self.ensure_role(Role::FeeManager)?;
+
+            let amount = self.platform_token_fees.take(token).unwrap_or(0);
+            self.pay_out(Some(token), self.state.treasury, amount)?;
+
+            self.env().emit_event(PlatformFeesWithdrawn {
+                treasury: self.state.treasury,
+                token: Some(token),
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Get the platform fee in basis points
+        #[ink(message)]
+        pub fn get_platform_fee(&self) -> u16 {
+            self.state.platform_fee_bps
+        }
+
+        /// Get the treasury account
+        #[ink(message)]
+        pub fn get_treasury(&self) -> AccountId {
+            self.state.treasury
+        }
+
+        /// Get the contract's native token balance
+        ///
+        /// For auditing: this should always cover `get_total_escrowed`, plus
+        /// native platform fees, locked bonds, and the deposits held outside
+        /// event escrow: raffle entries, purchase commitments, open seat
+        /// auction bids, unwithdrawn bid refunds, and open ticket offers.
+        #[ink(message)]
+        pub fn get_contract_balance(&self) -> Balance {
+            self.env().balance()
+        }
+
+        /// Get the total held in escrow across all native-token events
+        ///
+        /// This is the most the contract could owe in refunds and organizer
+        /// payouts for tickets already sold. Deposits for tickets not yet
+        /// sold (raffle entries, purchase commitments, seat auction bids and
+        /// bid refunds, and ticket offers) are tracked separately and not
+        /// included. PSP22 escrows are held by their token contracts and are
+        /// not included either.
+        #[ink(message)]
+        pub fn get_total_escrowed(&self) -> Balance {
+            self.state.total_escrowed
+        }
+
+        /// Get the funds held for an event, in its payment asset
+        #[ink(message)]
+        pub fn get_event_escrow(&self, event_id: u64) -> Balance {
+            self.escrow_balance.get(event_id).unwrap_or(0)
+        }
+
+        /// Get platform fees not yet withdrawn (`None` for native token)
+        #[ink(message)]
+        pub fn get_platform_fees(&self, token: Option<AccountId>) -> Balance {
+            match token {
+                None => self.state.platform_fees,
+                Some(token) => self.platform_token_fees.get(token).unwrap_or(0),
+            }
+        }
+
+        /// Validate payment and mint a ticket for the caller
+        ///
+        /// `tier_id` selects a ticket tier; `None` buys general admission.
+        /// `seat_id` picks a seat at reserved-seating events.
+        fn purchase_ticket(
+            &mut self,
+            event_id: u64,
+            tier_id: Option<u32>,
+            seat_id: Option<u32>,
+        ) -> Result<u64, Error> {
+            self.purchase_ticket_for(event_id, self.env().caller(), tier_id, seat_id)
+        }
+
+        /// Buy a ticket paid for by the caller and minted to `beneficiary`
+        fn purchase_ticket_for(
+            &mut self,
+            event_id: u64,
+            beneficiary: AccountId,
+            tier_id: Option<u32>,
+            seat_id: Option<u32>,
+        ) -> Result<u64, Error> {
+            let caller = self.env().caller();
+
+            // Get event or return error
+            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            self.ensure_on_sale(&event, beneficiary, 1)?;
+            let person = self.claim_person_quota(event_id, beneficiary, 1)?;
+
+            // Validate exact payment amount to prevent confusion
+            // User must pay exactly the ticket (or tier) price, with
+            // the base price following the event's price phases
+            let price = match tier_id {
+                Some(tier_id) => Self::tier(&event, tier_id)?.price,
+                None => self.base_price(&event, self.env().block_timestamp(), 0)?,
+            };
+            self.collect_payment(&event, caller, price)?;
+
+            // NOTE: Payment is held in contract as escrow
+            // Organizer can withdraw earnings after event is completed
+            // This enables automatic refunds if event is cancelled
+            let ticket_id = self.mint_ticket(&mut event, beneficiary, tier_id, seat_id, price)?;
+            self.record_ticket_person(ticket_id, person);
+            Ok(ticket_id)
+        }
+
+        /// Base price at `now`, in the payment asset, of the ticket `offset`
+        /// sales after the next one
+        ///
+        /// USD-priced events convert at the oracle's quote; the rest follow
+        /// `Event::nth_price_at`.
+        fn base_price(&self, event: &Event, now: u64, offset: u32) -> Result<Balance, Error> {
+            let Some(usd_price_cents) = self.usd_prices.get(event.id) else {
+                return Ok(event.nth_price_at(now, offset));
+            };
+            let oracle = self.state.price_oracle.ok_or(Error::OracleUnavailable)?;
+            let (usd_price, updated_at) =
+                oracle::latest_price(oracle).ok_or(Error::OracleUnavailable)?;
+            if now.saturating_sub(updated_at) > self.state.max_oracle_staleness {
+                return Err(Error::StalePrice);
+            }
+            // Round up so a ticket never sells below its USD price
+            Balance::from(usd_price_cents)
+                .checked_mul(usd_price)
+                .map(|total| total.div_ceil(100))
+                .ok_or(Error::InvalidInput)
+        }
+
+        /// Take `amount` from `payer` in the event's payment asset
+        ///
+        /// Native payments must attach at least `amount` to the call; any
+        /// overpayment (wallet dust, rounding, or a Dutch auction's falling
+        /// price) is sent straight back to the payer. PSP22 payments are
+        /// pulled with `transfer_from` into the contract.
+        fn collect_payment(&self, event: &Event, payer: AccountId, amount: Balance) -> Result<(), Error> {
+            match event.payment_token {
+                None => self.collect_native_payment(payer, amount)?,
+                Some(token) => {
+                    if self.env().transferred_value() != 0 {
+                        return Err(Error::UnexpectedNativePayment);
+                    }
+                    if amount > 0
+                        && !psp22::transfer_from(token, payer, self.env().account_id(), amount)
+                    {
+                        return Err(Error::InsufficientPayment {
+                            expected: amount,
+                            got: 0,
+                        });
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        /// Keep `amount` of the native value attached to the call, sending any
+        /// surplus back to `payer`
+        fn collect_native_payment(&self, payer: AccountId, amount: Balance) -> Result<(), Error> {
+            let payment = self.env().transferred_value();
+            let overpayment = payment.checked_sub(amount).ok_or(Error::InsufficientPayment {
+                expected: amount,
+                got: payment,
+            })?;
+            self.pay_out(None, payer, overpayment)
+        }
+
+        /// Split a resale's `price`, already held by the contract, between the
+        /// organizer's royalty escrow and the seller
+        fn settle_resale(
+            &mut self,
+            event: &Event,
+            ticket_id: u64,
+            seller: AccountId,
+            buyer: AccountId,
+            price: Balance,
+        ) -> Result<(), Error> {
+            // Hold the organizer's royalty in escrow
+            let royalty = bps_share(price, event.royalty_bps);
+            if royalty > 0 {
+                self.credit_escrow(event.id, royalty);
+
+                self.env().emit_event(RoyaltyPaid {
+                    ticket_id,
+                    event_id: event.id,
+                    organizer: event.organizer,
+                    amount: royalty,
+                });
+            }
+
+            // Settle with the seller
+            self.pay_out(event.payment_token, seller, price.saturating_sub(royalty))?;
+
+            self.env().emit_event(TicketSold {
+                ticket_id,
+                seller,
+                buyer,
+                price,
+            });
+
+            Ok(())
+        }
+
+        /// Pay `amount` of the given asset (`None` for native) out of the contract
+        fn pay_out(&self, token: Option<AccountId>, to: AccountId, amount: Balance) -> Result<(), Error> {
+            if amount == 0 {
+                return Ok(());
+            }
+            let paid = match token {
+                None => self.env().transfer(to, amount).is_ok(),
+                Some(token) => psp22::transfer(token, to, amount),
+            };
+            if !paid {
+                return Err(Error::TransferFailed);
+            }
+            Ok(())
+        }
+
+        /// Check that `signer` made an ECDSA `signature` over `payload`
+        ///
+        /// The signed message is the blake2-256 hash of the SCALE-encoded
+        /// `(contract_address, payload)`, so signatures can't be replayed
+        /// against another deployment.
+        fn verify_signature<P: scale::Encode>(
+            &self,
+            payload: &P,
+            signature: &[u8; 65],
+            signer: AccountId,
+        ) -> Result<(), Error> {
+            let mut message_hash = [0u8; 32];
+            ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+                &(self.env().account_id(), payload),
+                &mut message_hash,
+            );
+            let mut public_key = [0u8; 33];
+            if self.env().ecdsa_recover(signature, &message_hash, &mut public_key).is_err()
+                || ecdsa_account(&public_key) != signer
+            {
+                return Err(Error::InvalidSignature);
+            }
+            Ok(())
+        }
+
+        /// Mark an event cancelled, open its refund claim period, and notify subscribers
+        ///
+        /// `slash_bps` of the organizer's bond is slashed, the rest returned.
+        fn close_for_refunds(&mut self, event: &mut Event, slash_bps: u16) -> Result<(), Error> {
+            event.transition(EventStatus::Cancelled)?;
+            self.events.insert(event.id, event);
+            let refund_deadline = self.env().block_timestamp().saturating_add(REFUND_CLAIM_PERIOD);
+            self.refund_deadlines.insert(event.id, &refund_deadline);
+            self.event_tickets_state_changed(event.id, TicketState::EventCancelled);
+            self.notify(event, NotificationKind::EventCancelled);
+            self.settle_bond(event, slash_bps)
+        }
+
+        /// Release an event's bond, slashing `slash_bps` of it
+        ///
+        /// The slashed part joins the refund pool of native-token events and
+        /// goes to platform fees otherwise; the rest goes back to the organizer.
+        fn settle_bond(&mut self, event: &Event, slash_bps: u16) -> Result<(), Error> {
+            let Some(bond) = self.event_bonds.take(event.id) else {
+                return Ok(());
+            };
+
+            let slashed = bps_share(bond, slash_bps);
+            if event.payment_token.is_none() {
+                self.credit_escrow(event.id, slashed);
+            } else {
+                self.state.platform_fees = self.state.platform_fees.saturating_add(slashed);
+            }
+            let returned = bond.saturating_sub(slashed);
+            self.pay_out(None, event.organizer, returned)?;
+
+            self.env().emit_event(BondSettled {
+                event_id: event.id,
+                organizer: event.organizer,
+                returned,
+                slashed,
+            });
+
+            Ok(())
+        }
+
+        /// Refund a ticket to its current owner
+        ///
+        /// Burns the ticket and pays back `refund_amount` out of the event's escrow.
+        fn refund_holder(
+            &mut self,
+            event: &Event,
+            mut ticket: Ticket,
+            refund_amount: Balance,
+        ) -> Result<(), Error> {
+            let owner = ticket.owner;
+
+            // Mark ticket as refunded
+            ticket.is_refunded = true;
+            self.tickets.insert(ticket.id, &ticket);
+
+            // IMMEDIATELY remove ticket from owner's list for efficient queries
+            self.remove_owned_ticket(owner, ticket.id);
+            self.burn_ticket(&ticket)?;
+
+            // Transfer refund to ticket owner, in the event's payment asset
+            self.debit_escrow(event.id, refund_amount)?;
+            self.pay_out(event.payment_token, owner, refund_amount)?;
+            self.record_refund(owner, event.payment_token, refund_amount);
+            self.record_event_refund(event.id, refund_amount);
+
+            // Emit event
+            self.env().emit_event(TicketRefunded {
+                ticket_id: ticket.id,
+                owner,
+                amount: refund_amount,
+            });
+
+            Ok(())
+        }
+
+        /// Amount a ticket's holder gets back if it's refunded or cancelled now
+        ///
+        /// Cancelled events and reschedule opt-outs refund in full (cancelled
+        /// events including add-ons); otherwise the event's refund policy
+        /// applies. The platform fee is never refunded.
+        fn refund_due(&self, event: &Event, ticket: &Ticket) -> Balance {
+            let refundable = ticket.price_paid.saturating_sub(ticket.fee_paid);
+            if event.is_cancelled() {
+                // Add-ons are only refunded along with a cancelled event
+                let addons = self.addon_spend.get(ticket.id).unwrap_or(0);
+                return refundable.saturating_add(addons);
+            }
+            if event.refund_policy.is_empty() || self.reschedule_opt_out_open(event.id) {
+                return refundable;
+            }
+
+            // First rule whose deadline hasn't passed
+            let time_before_start = event
+                .schedule
+                .event_start
+                .saturating_sub(self.env().block_timestamp());
+            let refund_bps = event
+                .refund_policy
+                .iter()
+                .find(|rule| time_before_start >= rule.min_time_before_start)
+                .map_or(0, |rule| rule.refund_bps);
+            bps_share(refundable, refund_bps)
+        }
+
+        /// Owe a returned auction bid to `bidder`
+        fn credit_bid_refund(&mut self, bidder: AccountId, amount: Balance) {
+            let pending = self.bid_refunds.get(bidder).unwrap_or(0);
+            self.bid_refunds.insert(bidder, &pending.saturating_add(amount));
+        }
+
+        /// Owe `amount` of an event's released earnings to `payee`
+        fn credit_payout(&mut self, event_id: u64, payee: AccountId, amount: Balance) {
+            if amount == 0 {
+                return;
+            }
+            let pending = self.pending_payouts.get((event_id, payee)).unwrap_or(0);
+            self.pending_payouts.insert((event_id, payee), &pending.saturating_add(amount));
+
+            self.env().emit_event(PayoutCredited {
+                event_id,
+                payee,
+                amount,
+            });
+        }
+
+        /// Escrowed earnings of a completed event vested so far, net of withdrawals
+        fn vested_earnings(&self, event: &Event) -> Balance {
+            let escrow = self.escrow_balance.get(event.id).unwrap_or(0);
+            if event.payout_schedule.is_empty() {
+                return escrow;
+            }
+
+            let unlock = self.withdrawal_unlocks.get(event.id).unwrap_or(0);
+            let now = self.env().block_timestamp();
+            let vested_bps: u16 = event
+                .payout_schedule
+                .iter()
+                .filter(|tranche| unlock.saturating_add(tranche.delay) <= now)
+                .map(|tranche| tranche.share_bps)
+                .sum();
+
+            let withdrawn = self.total_withdrawn.get(event.id).unwrap_or(0);
+            let vested = bps_share(escrow.saturating_add(withdrawn), vested_bps);
+            vested.saturating_sub(withdrawn).min(escrow)
+        }
+
+        /// Whether the refund claim period of a cancelled event is over
+        fn refund_period_ended(&self, event_id: u64) -> bool {
+            self.refund_deadlines
+                .get(event_id)
+                .is_some_and(|deadline| self.env().block_timestamp() >= deadline)
+        }
+
+        /// Whether holders of a rescheduled event can still opt out for a refund
+        fn reschedule_opt_out_open(&self, event_id: u64) -> bool {
+            self.reschedule_refund_deadlines
+                .get(event_id)
+                .is_some_and(|deadline| self.env().block_timestamp() < deadline)
+        }
+
+        /// Split the platform fee off a sale and escrow the rest
+        ///
+        /// Returns the fee taken.
+        fn collect_sale(&mut self, event: &Event, ticket_id: u64, amount: Balance) -> Balance {
+            let fee = bps_share(amount, self.state.platform_fee_bps);
+            if fee > 0 {
+                self.credit_platform_fees(event.payment_token, fee);
+                self.env().emit_event(FeeCollected {
+                    event_id: event.id,
+                    ticket_id,
+                    token: event.payment_token,
+                    amount: fee,
+                });
+            }
+
+            self.credit_escrow(event.id, amount.saturating_sub(fee));
+            self.update_event_stats(event.id, |stats| {
+                stats.gross_revenue = stats.gross_revenue.saturating_add(amount);
+            });
+            fee
+        }
+
+        /// Mint the event's attendance NFT to a ticket holder, if it has one
+        fn mint_attendance_nft(&mut self, ticket: &Ticket) {
+            let Some(metadata_cid) = self.attendance_nft_cids.get(ticket.event_id) else {
+                return;
+            };
+
+            let token_id = self.state.attendance_token_counter;
+            self.state.attendance_token_counter =
+                self.state.attendance_token_counter.saturating_add(1);
+            self.attendance.insert(
+                (ticket.event_id, ticket.owner),
+                &AttendanceToken {
+                    id: token_id,
+                    event_id: ticket.event_id,
+                    owner: ticket.owner,
+                    ticket_id: ticket.id,
+                    metadata_cid,
+                    minted_at: self.env().block_timestamp(),
+                },
+            );
+
+            self.env().emit_event(AttendanceNftMinted {
+                token_id,
+                event_id: ticket.event_id,
+                owner: ticket.owner,
+            });
+        }
+
+        /// Mint a zero-price ticket flagged as complimentary
+        fn mint_free_ticket(&mut self, event: &mut Event, recipient: AccountId) -> Result<u64, Error> {
+            let ticket_id = self.mint_ticket(event, recipient, None, None, 0)?;
+
+            // Flag the ticket so it's told apart from paid zero-price sales
+            let mut ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            ticket.is_comp = true;
+            self.tickets.insert(ticket_id, &ticket);
+
+            Ok(ticket_id)
+        }
+
+        /// Buy a ticket for the caller and credit `referrer` their rewards
+        fn purchase_with_referrer(
+            &mut self,
+            event_id: u64,
+            referrer: AccountId,
+        ) -> Result<u64, Error> {
+            let caller = self.env().caller();
+            if referrer == caller {
+                return Err(Error::SelfReferral);
+            }
+
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            if !event.referrals_enabled {
+                return Err(Error::ReferralsDisabled);
+            }
+
+            let ticket_id = self.purchase_ticket(event_id, None, None)?;
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+
+            // The organizer's reward comes out of their proceeds
+            let organizer_share = bps_share(
+                ticket.price_paid.saturating_sub(ticket.fee_paid),
+                event.referral_reward_bps,
+            );
+            self.debit_escrow(event_id, organizer_share)?;
+
+            // The platform's comes out of the fee this sale generated
+            let fee_share = bps_share(ticket.fee_paid, self.state.referral_fee_share_bps);
+            match event.payment_token {
+                None => {
+                    self.state.platform_fees = self.state.platform_fees.saturating_sub(fee_share)
+                }
+                Some(token) => {
+                    let collected = self.platform_token_fees.get(token).unwrap_or(0);
+                    self.platform_token_fees.insert(token, &collected.saturating_sub(fee_share));
+                }
+            }
+
+            let amount = organizer_share.saturating_add(fee_share);
+            if amount > 0 {
+                let key = (referrer, event.payment_token);
+                let balance = self.referral_rewards.get(key).unwrap_or(0);
+                self.referral_rewards.insert(key, &balance.saturating_add(amount));
+            }
+
+            self.env().emit_event(ReferralRewardEarned {
+                referrer,
+                event_id,
+                ticket_id,
+                buyer: caller,
+                token: event.payment_token,
+                amount,
+            });
+
+            Ok(ticket_id)
+        }
+
+        /// Pay out and clear the caller's referral rewards in one asset
+        fn claim_referral_rewards_in(&mut self, token: Option<AccountId>) -> Result<Balance, Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+
+            let amount = self.referral_rewards.take((caller, token)).unwrap_or(0);
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+            self.pay_out(token, caller, amount)?;
+
+            self.env().emit_event(ReferralRewardsClaimed {
+                referrer: caller,
+                token,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Add to the platform fees collected in a payment asset
+        fn credit_platform_fees(&mut self, token: Option<AccountId>, amount: Balance) {
+            match token {
+                None => self.state.platform_fees = self.state.platform_fees.saturating_add(amount),
+                Some(token) => {
+                    let collected = self.platform_token_fees.get(token).unwrap_or(0);
+                    self.platform_token_fees.insert(token, &collected.saturating_add(amount));
+                }
+            }
+        }
+
+        /// Add funds to an event's escrow
+        fn credit_escrow(&mut self, event_id: u64, amount: Balance) {
+            let balance = self.escrow_balance.get(event_id).unwrap_or(0);
+            self.escrow_balance.insert(event_id, &balance.saturating_add(amount));
+            if self.is_native_event(event_id) {
+                self.state.total_escrowed = self.state.total_escrowed.saturating_add(amount);
+            }
+        }
+
+        /// Remove funds from an event's escrow, failing if it holds too little
+        fn debit_escrow(&mut self, event_id: u64, amount: Balance) -> Result<(), Error> {
+            let balance = self.escrow_balance.get(event_id).unwrap_or(0);
+            let remaining = balance.checked_sub(amount).ok_or(Error::InsufficientBalance)?;
+            self.escrow_balance.insert(event_id, &remaining);
+            if self.is_native_event(event_id) {
+                self.state.total_escrowed = self.state.total_escrowed.saturating_sub(amount);
+            }
+            Ok(())
+        }
+
+        /// Whether an event is paid in native token (and so counts toward `total_escrowed`)
+        fn is_native_event(&self, event_id: u64) -> bool {
+            self.events.get(event_id).is_some_and(|event| event.payment_token.is_none())
+        }
+
+        /// Add to an account's lifetime spending in the given asset
+        fn record_spent(&mut self, account: AccountId, token: Option<AccountId>, amount: Balance) {
+            match token {
+                None => self.update_summary(account, |summary| {
+                    summary.total_spent = summary.total_spent.saturating_add(amount);
+                }),
+                Some(token) => {
+                    let mut totals = self.token_totals.get((account, token)).unwrap_or_default();
+                    totals.spent = totals.spent.saturating_add(amount);
+                    self.token_totals.insert((account, token), &totals);
+                }
+            }
+        }
+
+        /// Add to an account's lifetime refunds in the given asset
+        fn record_refund(&mut self, account: AccountId, token: Option<AccountId>, amount: Balance) {
+            match token {
+                None => self.update_summary(account, |summary| {
+                    summary.refunds_received = summary.refunds_received.saturating_add(amount);
+                }),
+                Some(token) => {
+                    let mut totals = self.token_totals.get((account, token)).unwrap_or_default();
+                    totals.refunded = totals.refunded.saturating_add(amount);
+                    self.token_totals.insert((account, token), &totals);
+                }
+            }
+        }
+
+        /// Events with IDs from `offset`, at most `limit` (capped at `MAX_PAGE_SIZE`)
+        fn events_page(&self, offset: u64, limit: u32) -> impl Iterator<Item = Event> + '_ {
+            let end = offset
+                .saturating_add(u64::from(limit.min(MAX_PAGE_SIZE)))
+                .min(self.event_counter);
+            (offset..end).filter_map(|event_id| self.events.get(event_id))
+        }
+
+        /// Require the caller to be the organizer of an event
+        fn ensure_organizer(&self, event_id: u64) -> Result<Event, Error> {
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            if self.env().caller() != event.organizer {
+                return Err(Error::NotOrganizer);
+            }
+            Ok(event)
+        }
+
+        /// Require the caller to be the organizer or a co-organizer of an event
+        fn ensure_event_manager(&self, event_id: u64) -> Result<Event, Error> {
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            let caller = self.env().caller();
+            if caller != event.organizer && !self.co_organizers.contains((event_id, caller)) {
+                return Err(Error::NotOrganizer);
+            }
+            Ok(event)
+        }
+
+        /// Freeze or unfreeze an event on behalf of `moderator`
+        fn set_event_frozen(&mut self, event: &mut Event, frozen: bool, moderator: AccountId) {
+            event.frozen = frozen;
+            self.events.insert(event.id, event);
+
+            self.env().emit_event(EventFrozen {
+                event_id: event.id,
+                moderator,
+                frozen,
+            });
+        }
+
+        /// Grant or revoke an organizer's verified badge
+        fn set_organizer_verified(&mut self, account: AccountId, verified: bool) -> Result<(), Error> {
+            let moderator = self.ensure_role(Role::Moderator)?;
+            let mut profile = self.organizers.get(account).ok_or(Error::OrganizerNotRegistered)?;
+            profile.is_verified = verified;
+            self.organizers.insert(account, &profile);
+
+            self.env().emit_event(OrganizerVerified {
+                organizer: account,
+                moderator,
+                verified,
+            });
+
+            Ok(())
+        }
+
+        /// Require the caller to hold `role` (admins hold every role implicitly)
+        fn ensure_role(&self, role: Role) -> Result<AccountId, Error> {
+            let caller = self.env().caller();
+            if self.has_role(role, caller) || self.has_role(Role::Admin, caller) {
+                Ok(caller)
+            } else {
+                Err(Error::MissingRole)
+            }
+        }
+
+        /// Reject state changes while the contract is paused
+        fn ensure_not_paused(&self) -> Result<(), Error> {
+            if self.state.paused {
+                return Err(Error::ContractPaused);
+            }
+            // Partly migrated storage mixes old and new layouts
+            if self.state.storage_version < STORAGE_VERSION {
+                return Err(Error::MigrationPending);
+            }
+            Ok(())
+        }
+
+        /// Check that an event is currently selling `quantity` tickets to `buyer`
+        fn ensure_on_sale(
+            &self,
+            event: &Event,
+            buyer: AccountId,
+            quantity: u32,
+        ) -> Result<(), Error> {
+            self.ensure_selling(event)?;
+            self.ensure_eligible(event, buyer)?;
+            self.ensure_within_rate_limits(event.id, buyer, quantity)
+        }
+
+        /// Check that `buyer` meets an event's purchase restrictions
+        fn ensure_eligible(&self, event: &Event, buyer: AccountId) -> Result<(), Error> {
+            let allowlisted = self.allowlist.contains((event.id, buyer));
+            self.ensure_eligible_as(event, buyer, allowlisted)
+        }
+
+        /// Check that `buyer` meets an event's purchase restrictions, given
+        /// whether they are on its presale allowlist (stored or proven)
+        fn ensure_eligible_as(
+            &self,
+            event: &Event,
+            buyer: AccountId,
+            allowlisted: bool,
+        ) -> Result<(), Error> {
+            self.ensure_event_access(event.id, buyer)?;
+
+            // During the presale only allowlisted accounts can buy
+            let now = self.env().block_timestamp();
+            if now < event.presale_end && !allowlisted {
+                return Err(Error::NotAllowlisted);
+            }
+
+            // Token-gated events only sell to holders
+            if event.token_gate.is_some_and(|gate| !gate.is_met_by(buyer)) {
+                return Err(Error::NotTokenHolder);
+            }
+
+            // Point-gated events only sell to loyal attendees
+            let terms = self.loyalty_terms.get(event.id).unwrap_or_default();
+            if self.get_points(buyer) < terms.min_points {
+                return Err(Error::InsufficientPoints);
+            }
+
+            // Credential-gated events only sell to attested buyers
+            self.check_credential(event.id, buyer)?;
+
+            Ok(())
+        }
+
+        /// Re-verify the credential `account` presented for an event, returning
+        /// its claim hash, or `None` if the event needs no credential
+        fn check_credential(
+            &self,
+            event_id: u64,
+            account: AccountId,
+        ) -> Result<Option<Hash>, Error> {
+            let Some(attester) = self.credential_attesters.get(event_id) else {
+                return Ok(None);
+            };
+            let credential = self
+                .credentials
+                .get((event_id, account))
+                .ok_or(Error::CredentialRequired)?;
+            if credential.attester != attester
+                || !attestation::verify(attester, account, credential.claim_hash)
+            {
+                return Err(Error::InvalidCredential);
+            }
+            Ok(Some(credential.claim_hash))
+        }
+
+        /// Check that `buyer` has given the access key of a private event
+        fn ensure_event_access(&self, event_id: u64, buyer: AccountId) -> Result<(), Error> {
+            if self.access_hashes.contains(event_id)
+                && !self.event_access.contains((event_id, buyer))
+            {
+                return Err(Error::AccessKeyRequired);
+            }
+            Ok(())
+        }
+
+        /// Check that `buyer` may buy `quantity` tickets for an event this block
+        fn ensure_within_rate_limits(
+            &self,
+            event_id: u64,
+            buyer: AccountId,
+            quantity: u32,
+        ) -> Result<(), Error> {
+            let block = self.env().block_number();
+            let limits = self.state.rate_limits;
+
+            // Accounts wait out the cooldown between purchases
+            if let Some(last) = self.last_purchase_block.get(buyer) {
+                let until_block = last.saturating_add(limits.purchase_cooldown);
+                if limits.purchase_cooldown != 0 && block < until_block {
+                    return Err(Error::PurchaseCooldown { until_block });
+                }
+            }
+
+            // Each event sells only so many tickets per block
+            if limits.max_mints_per_block != 0 {
+                let minted = match self.block_mints.get(event_id) {
+                    Some((minted_block, count)) if minted_block == block => count,
+                    _ => 0,
+                };
+                if minted.saturating_add(quantity) > limits.max_mints_per_block {
+                    return Err(Error::BlockMintLimitReached);
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Count `quantity` tickets against the quota of the person behind
+        /// `buyer`, for personhood-gated events
+        ///
+        /// Returns the person charged, if the event has a quota.
+        fn claim_person_quota(
+            &mut self,
+            event_id: u64,
+            buyer: AccountId,
+            quantity: u32,
+        ) -> Result<Option<Hash>, Error> {
+            let Some((person, total)) = self.check_person_quota(event_id, buyer, quantity)? else {
+                return Ok(None);
+            };
+            self.person_tickets.insert((event_id, person), &total);
+            Ok(Some(person))
+        }
+
+        /// Check that `buyer` can get `quantity` more tickets within an
+        /// event's per-person quota, returning their person and new total
+        fn check_person_quota(
+            &self,
+            event_id: u64,
+            buyer: AccountId,
+            quantity: u32,
+        ) -> Result<Option<(Hash, u32)>, Error> {
+            let Some(limit) = self.person_quotas.get(event_id) else {
+                return Ok(None);
+            };
+
+            // Without a registry nobody can prove they're human
+            let person = self
+                .state
+                .personhood_registry
+                .and_then(|registry| personhood::person_of(registry, buyer))
+                .ok_or(Error::NotVerifiedHuman)?;
+
+            let bought = self.person_tickets.get((event_id, person)).unwrap_or(0);
+            let total = bought.saturating_add(quantity);
+            if total > limit {
+                return Err(Error::PersonQuotaReached { limit });
+            }
+            Ok(Some((person, total)))
+        }
+
+        /// Remember the person a newly minted ticket was charged to
+        fn record_ticket_person(&mut self, ticket_id: u64, person: Option<Hash>) {
+            if let Some(person) = person {
+                self.ticket_persons.insert(ticket_id, &person);
+            }
+        }
+
+        /// Give one ticket back to the quota of the person it was charged to
+        fn release_person_quota(&mut self, event_id: u64, person: Option<Hash>) {
+            if let Some(person) = person {
+                let bought = self.person_tickets.get((event_id, person)).unwrap_or(0);
+                self.person_tickets.insert((event_id, person), &bought.saturating_sub(1));
+            }
+        }
+
+        /// Count a paid ticket against the purchase rate limits
+        fn record_paid_mint(&mut self, event_id: u64, buyer: AccountId) {
+            let block = self.env().block_number();
+            let minted = match self.block_mints.get(event_id) {
+                Some((minted_block, count)) if minted_block == block => count,
+                _ => 0,
+            };
+            self.block_mints.insert(event_id, &(block, minted.saturating_add(1)));
+            self.last_purchase_block.insert(buyer, &block);
+        }
+
+        /// Check that an event is currently selling tickets, ignoring the presale
+        fn ensure_selling(&self, event: &Event) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+
+            // Raffle events only allocate through the draw
+            if self.raffles.contains(event.id) {
+                return Err(Error::RaffleOnly);
+            }
+
+            // Commit-reveal events sell leftovers once the lottery is drawn
+            if let Some(draw) = self.commit_draws.get(event.id) {
+                if !draw.drawn {
+                    return Err(Error::SaleNotOpen {
+                        opens_at: draw.entry_end,
+                    });
+                }
+            }
+
+            self.ensure_sale_window_open(event)
+        }
+
+        /// Check that an event is on sale, unfrozen, and inside its sale window
+        fn ensure_sale_window_open(&self, event: &Event) -> Result<(), Error> {
+            // Validate event is on sale
+            match event.status {
+                EventStatus::OnSale => {}
+                EventStatus::Paused => return Err(Error::SalesPaused),
+                EventStatus::Draft | EventStatus::Cancelled | EventStatus::Completed => {
+                    return Err(Error::EventNotActive)
+                }
+            }
+
+            // Check if event is frozen
+            if event.frozen {
+                return Err(Error::EventFrozen);
+            }
+
+            // Check the sale window
+            let now = self.env().block_timestamp();
+            event.schedule.check_sale_window(now)?;
+
+            Ok(())
+        }
+
+        /// Mint a ticket for `owner`, updating availability, indexes, and stats
+        ///
+        /// Callers are responsible for event state and payment checks.
+        fn mint_ticket(
+            &mut self,
+            event: &mut Event,
+            owner: AccountId,
+            tier_id: Option<u32>,
+            seat_id: Option<u32>,
+            price_paid: Balance,
+        ) -> Result<u64, Error> {
+            // Blocked accounts can't get tickets for the event
+            if self.blocked_attendees.contains((event.id, owner)) {
+                return Err(Error::AttendeeBlocked);
+            }
+
+            // Reserved-seating events need a free seat from the layout
+            match (self.seat_layouts.get(event.id), seat_id) {
+                (Some(layout), Some(seat_id)) => {
+                    if !layout.contains(seat_id) {
+                        return Err(Error::InvalidSeat);
+                    }
+                    if self.booked_seats.contains((event.id, seat_id))
+                        || self.seat_auctions.contains((event.id, seat_id))
+                    {
+                        return Err(Error::SeatTaken);
+                    }
+                }
+                (Some(_), None) => return Err(Error::SeatRequired),
+                (None, Some(_)) => return Err(Error::InvalidSeat),
+                (None, None) => {}
+            }
+
+            // Check if tickets are available in the requested pool
+            match tier_id {
+                Some(tier_id) => {
+                    let tier = Self::tier_mut(event, tier_id)?;
+                    if tier.available == 0 {
+                        return Err(Error::SoldOut);
+                    }
+                    tier.available = tier.available.saturating_sub(1);
+                }
+                None => {
+                    if self.unreserved_available(event) == 0 {
+                        return Err(Error::SoldOut);
+                    }
+                }
+            }
+
+            // Check owner hasn't exceeded maximum tickets
+            if self.owner_ticket_count.get(owner).unwrap_or(0) >= MAX_TICKETS_PER_USER {
+                return Err(Error::TooManyTickets);
+            }
+
+            // Create ticket ID and NFT
+            let ticket_id = self.ticket_counter;
+
+            // Split the platform fee off the sale; the rest is held in escrow
+            let fee_paid = self.collect_sale(event, ticket_id, price_paid);
+
+            let ticket = Ticket {
+                id: ticket_id,
+                event_id: event.id,
+                owner,
+                purchase_time: self.env().block_timestamp(),
+                uses_remaining: match tier_id {
+                    Some(tier_id) => Self::tier(event, tier_id)?.uses_per_ticket,
+                    None => event.uses_per_ticket,
+                },
+                times_used: 0,
+                is_refunded: false,
+                tier_id,
+                price_paid,
+                seat_id,
+                fee_paid,
+                is_comp: false,
+            };
+
+            // Update event availability
+            event.available_tickets = event.available_tickets.saturating_sub(1);
+            self.events.insert(event.id, &*event);
+
+            // Store ticket
+            self.tickets.insert(ticket_id, &ticket);
+            self.ticket_counter = self.ticket_counter.saturating_add(1);
+            if let Some(seat_id) = seat_id {
+                self.booked_seats.insert((event.id, seat_id), &ticket_id);
+            }
+            self.record_ownership(ticket_id, None, owner, None);
+            let event_index = self.event_ticket_count.get(event.id).unwrap_or(0);
+            self.event_tickets.insert((event.id, event_index), &ticket_id);
+            self.event_ticket_count.insert(event.id, &event_index.saturating_add(1));
+            self.update_event_stats(event.id, |stats| {
+                stats.tickets_sold = stats.tickets_sold.saturating_add(1);
+            });
+
+            // Update owner's ticket list
+            self.add_owned_ticket(owner, ticket_id);
+
+            self.update_summary(owner, |summary| {
+                summary.tickets_bought = summary.tickets_bought.saturating_add(1);
+            });
+            self.record_spent(owner, event.payment_token, price_paid);
+            if price_paid > 0 {
+                self.award_points(owner, self.state.points_per_purchase);
+                self.record_paid_mint(event.id, owner);
+            }
+
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(owner),
+                id: Id::U64(ticket_id),
+            });
+
+            // Emit event
+            self.env().emit_event(TicketPurchased {
+                ticket_id,
+                event_id: event.id,
+                buyer: owner,
+                tier_id,
+                seat_id,
+                price_paid,
+            });
+            self.ticket_state_changed(&ticket, TicketState::Issued);
+
+            Ok(ticket_id)
+        }
+
+        /// Highest allowed resale price for a ticket, if the event caps resales
+        fn max_resale_price(event: &Event, ticket: &Ticket) -> Option<Balance> {
+            if event.max_resale_multiplier_bps == 0 {
+                return None;
+            }
+            let face_value = ticket
+                .tier_id
+                .and_then(|tier_id| Self::tier(event, tier_id).ok())
+                .map_or(event.price, |tier| tier.price);
+            Some(
+                face_value.saturating_mul(event.max_resale_multiplier_bps as Balance)
+                    / BPS_DENOMINATOR,
+            )
+        }
+
+        /// Look up a tier of an event
+        fn tier(event: &Event, tier_id: u32) -> Result<&TicketTier, Error> {
+            event
+                .tiers
+                .iter()
+                .find(|tier| tier.id == tier_id)
+                .ok_or(Error::TierNotFound)
+        }
+
+        /// Look up a tier of an event for modification
+        fn tier_mut(event: &mut Event, tier_id: u32) -> Result<&mut TicketTier, Error> {
+            event
+                .tiers
+                .iter_mut()
+                .find(|tier| tier.id == tier_id)
+                .ok_or(Error::TierNotFound)
+        }
+
+        /// Tickets available for general admission (not reserved by a tier)
+        fn general_available(event: &Event) -> u32 {
+            let reserved: u32 = event.tiers.iter().map(|tier| tier.available).sum();
+            event.available_tickets.saturating_sub(reserved)
+        }
+
+        /// General admission tickets not held back for waitlist offers or
+        /// raffle and commit-reveal winners
+        fn unreserved_available(&self, event: &Event) -> u32 {
+            let offered = self.waitlists.get(event.id).map_or(0, |waitlist| waitlist.open_offers);
+            let won = self.unclaimed_wins.get(event.id).unwrap_or(0);
+            Self::general_available(event)
+                .saturating_sub(offered)
+                .saturating_sub(won)
+        }
+
+        /// Return a raffle or commit-reveal winner's held-back ticket to the pool
+        fn release_unclaimed_win(&mut self, event_id: u64) {
+            let unclaimed = self.unclaimed_wins.get(event_id).unwrap_or(0);
+            self.unclaimed_wins.insert(event_id, &unclaimed.saturating_sub(1));
+        }
+
+        /// Check whether a raffle or commit-reveal winner could claim their
+        /// ticket right now; the claims check the same conditions
+        fn can_claim_raffle_win(&self, event: &Event, winner: AccountId) -> bool {
+            event.status == EventStatus::OnSale
+                && !event.frozen
+                && self.check_credential(event.id, winner).is_ok()
+                && !self.blocked_attendees.contains((event.id, winner))
+                && self.owner_ticket_count.get(winner).unwrap_or(0) < MAX_TICKETS_PER_USER
+        }
+
+        /// Reserve a returned ticket for the next account still waiting
+        ///
+        /// Returns whether an offer was made.
+        fn offer_to_waitlist(&mut self, event_id: u64) -> bool {
+            let mut waitlist = match self.waitlists.get(event_id) {
+                Some(waitlist) => waitlist,
+                None => return false,
+            };
+
+            // Skip slots freed by accounts that left the queue
+            let mut offered = false;
+            while waitlist.head < waitlist.tail && !offered {
+                let position = waitlist.head;
+                waitlist.head = waitlist.head.saturating_add(1);
+                let Some(account) = self.waitlist_queue.take((event_id, position)) else {
+                    continue;
+                };
+                if self.waitlisted.take((event_id, account)).is_none() {
+                    continue;
+                }
+
+                let expires_at = self.env().block_timestamp().saturating_add(WAITLIST_OFFER_DURATION);
+                self.waitlist_offers.insert((event_id, account), &expires_at);
+                waitlist.open_offers = waitlist.open_offers.saturating_add(1);
+                offered = true;
+
+                self.env().emit_event(WaitlistOffered {
+                    event_id,
+                    account,
+                    expires_at,
+                });
+            }
+
+            self.waitlists.insert(event_id, &waitlist);
+            offered
+        }
+
+        /// Drop an account's waitlist offer, returning its ticket to the pool
+        fn close_waitlist_offer(&mut self, event_id: u64, account: AccountId) {
+            self.waitlist_offers.remove((event_id, account));
+            let mut waitlist = self.waitlists.get(event_id).unwrap_or_default();
+            waitlist.open_offers = waitlist.open_offers.saturating_sub(1);
+            self.waitlists.insert(event_id, &waitlist);
+        }
+
+        /// Approve or revoke `operator` for one of the caller's tickets
+        fn set_ticket_approval(
+            &mut self,
+            ticket_id: u64,
+            operator: AccountId,
+            approved: bool,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if operator == caller {
+                return Err(Error::SelfApproval);
+            }
+
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            if ticket.owner != caller {
+                return Err(Error::NotTicketOwner);
+            }
+
+            if approved {
+                self.ticket_approvals.insert(ticket_id, &operator);
+            } else {
+                self.ticket_approvals.remove(ticket_id);
+            }
+
+            self.env().emit_event(Approval {
+                owner: caller,
+                operator,
+                id: Some(Id::U64(ticket_id)),
+                approved,
+            });
+
+            Ok(())
+        }
+
+        /// Whether `account` owns the ticket or is approved to move it
+        fn is_authorized(&self, ticket: &Ticket, account: AccountId) -> bool {
+            ticket.owner == account
+                || self.operator_approvals.contains((ticket.owner, account))
+                || self.ticket_approvals.get(ticket.id) == Some(account)
+        }
+
+        /// Move a ticket to a new owner once the caller is authorized
+        ///
+        /// `sale_price` is recorded in the ticket's history for resales.
+        fn move_ticket(
+            &mut self,
+            mut ticket: Ticket,
+            to: AccountId,
+            sale_price: Option<Balance>,
+        ) -> Result<(), Error> {
+            let ticket_id = ticket.id;
+            self.ensure_not_paused()?;
+
+            // Can't transfer used ticket (even partially)
+            if ticket.times_used > 0 {
+                return Err(Error::TicketAlreadyUsed);
+            }
+
+            // Can't transfer refunded ticket
+            if ticket.is_refunded {
+                return Err(Error::TicketAlreadyRefunded);
+            }
+
+            // Can't transfer soulbound ticket
+            let event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+            if !event.transferable {
+                return Err(Error::TicketNotTransferable);
+            }
+
+            // Anti-scalping limits; the ownership history starts at the mint
+            let limits = self.transfer_limits.get(event.id).unwrap_or_default();
+            let owners = self.ticket_history_count.get(ticket_id).unwrap_or(0);
+            if limits.max_transfers != 0 && owners.saturating_sub(1) >= limits.max_transfers {
+                return Err(Error::TransferLimitReached {
+                    limit: limits.max_transfers,
+                });
+            }
+            if limits.cooldown != 0 {
+                let held_since = self
+                    .ticket_history
+                    .get((ticket_id, owners.saturating_sub(1) % MAX_TICKET_HISTORY))
+                    .map_or(0, |record| record.timestamp);
+                let until = held_since.saturating_add(limits.cooldown);
+                if self.env().block_timestamp() < until {
+                    return Err(Error::TransferCooldown { until });
+                }
+            }
+
+            // Blocked accounts can't receive tickets for the event
+            if self.blocked_attendees.contains((event.id, to)) {
+                return Err(Error::AttendeeBlocked);
+            }
+
+            // Check recipient hasn't exceeded maximum tickets
+            if self.owner_ticket_count.get(to).unwrap_or(0) >= MAX_TICKETS_PER_USER {
+                return Err(Error::TooManyTickets);
+            }
+
+            // Keep an exported ticket's mirror with the ticket
+            if let Some(collection) = self.exported_tickets.get(ticket_id) {
+                self.env()
+                    .extension()
+                    .transfer(collection, ticket_id, to)
+                    .map_err(|_| Error::NftMirrorFailed)?;
+            }
+
+            // Update ticket owner
+            let old_owner = ticket.owner;
+            ticket.owner = to;
+            self.tickets.insert(ticket_id, &ticket);
+
+            // Move the ticket between the owners' lists
+            self.remove_owned_ticket(old_owner, ticket_id);
+            self.add_owned_ticket(to, ticket_id);
+
+            // Any single-ticket approval or listing ends with the transfer
+            self.ticket_approvals.remove(ticket_id);
+            self.listings.remove(ticket_id);
+            self.record_ownership(ticket_id, Some(old_owner), to, sale_price);
+
+            // Emit events
+            self.env().emit_event(TicketTransferred {
+                ticket_id,
+                from: old_owner,
+                to,
+            });
+            self.env().emit_event(Transfer {
+                from: Some(old_owner),
+                to: Some(to),
+                id: Id::U64(ticket_id),
+            });
+
+            Ok(())
+        }
+
+        /// Record a refunded ticket as burned, release its seat, and burn its mirror
+        fn burn_ticket(&mut self, ticket: &Ticket) -> Result<(), Error> {
+            if let Some(collection) = self.exported_tickets.get(ticket.id) {
+                self.env()
+                    .extension()
+                    .burn(collection, ticket.id)
+                    .map_err(|_| Error::NftMirrorFailed)?;
+                self.exported_tickets.remove(ticket.id);
+            }
+
+            self.state.burned_tickets = self.state.burned_tickets.saturating_add(1);
+            let person = self.ticket_persons.take(ticket.id);
+            self.release_person_quota(ticket.event_id, person);
+            self.ticket_approvals.remove(ticket.id);
+            self.listings.remove(ticket.id);
+            if let Some(seat_id) = ticket.seat_id {
+                self.booked_seats.remove((ticket.event_id, seat_id));
+            }
+
+            self.env().emit_event(Transfer {
+                from: Some(ticket.owner),
+                to: None,
+                id: Id::U64(ticket.id),
+            });
+            self.ticket_state_changed(ticket, TicketState::Refunded);
+
+            Ok(())
+        }
+
+        /// Credit loyalty points to an account
+        fn award_points(&mut self, account: AccountId, amount: u64) {
+            if amount == 0 {
+                return;
+            }
+            let balance = self.get_points(account).saturating_add(amount);
+            self.points.insert(account, &balance);
+
+            self.env().emit_event(PointsEarned {
+                account,
+                amount,
+                balance,
+            });
+        }
+
+        /// Tell indexers a ticket moved to `state`
+        fn ticket_state_changed(&self, ticket: &Ticket, state: TicketState) {
+            self.env().emit_event(TicketStateChanged {
+                ticket_id: Some(ticket.id),
+                event_id: ticket.event_id,
+                owner: Some(ticket.owner),
+                state,
+            });
+        }
+
+        /// Report a state change of every live ticket of an event
+        fn event_tickets_state_changed(&self, event_id: u64, state: TicketState) {
+            self.env().emit_event(TicketStateChanged {
+                ticket_id: None,
+                event_id,
+                owner: None,
+                state,
+            });
+        }
+
+        /// Resolve a PSP34 token ID to a live (non-refunded) ticket
+        fn ticket_for_id(&self, id: &Id) -> Option<Ticket> {
+            match id {
+                Id::U64(ticket_id) => self
+                    .tickets
+                    .get(ticket_id)
+                    .filter(|ticket| !ticket.is_refunded),
+                _ => None,
+            }
+        }
+
+        /// Append a ticket to its owner's list
+        fn add_owned_ticket(&mut self, owner: AccountId, ticket_id: u64) {
+            let count = self.owner_ticket_count.get(owner).unwrap_or(0);
+            self.owner_ticket_at.insert((owner, count), &ticket_id);
+            self.owner_ticket_index.insert(ticket_id, &count);
+            self.owner_ticket_count.insert(owner, &count.saturating_add(1));
+        }
+
+        /// Remove a ticket from its owner's list by moving the last ticket into its place
+        fn remove_owned_ticket(&mut self, owner: AccountId, ticket_id: u64) {
+            let Some(index) = self.owner_ticket_index.take(ticket_id) else {
+                return;
+            };
+            let last = self.owner_ticket_count.get(owner).unwrap_or(0).saturating_sub(1);
+            if index != last {
+                if let Some(moved) = self.owner_ticket_at.get((owner, last)) {
+                    self.owner_ticket_at.insert((owner, index), &moved);
+                    self.owner_ticket_index.insert(moved, &index);
+                }
+            }
+            self.owner_ticket_at.remove((owner, last));
+            self.owner_ticket_count.insert(owner, &last);
+        }
+
+        /// All tickets an owner holds, in list order
+        fn owned_tickets(&self, owner: AccountId) -> Vec<u64> {
+            let count = self.owner_ticket_count.get(owner).unwrap_or(0);
+            (0..count)
+                .filter_map(|index| self.owner_ticket_at.get((owner, index)))
+                .collect()
+        }
+
+        /// Read an event still stored in the version 1 layout
+        fn legacy_event(&self, event_id: u64) -> Option<EventV1> {
+            let key = (StorageKey::key(&self.events), event_id);
+            ink::env::get_contract_storage::<_, EventV1>(&key).ok().flatten()
+        }
+
+        /// Convert a ticket still stored in the version 1 layout and add it
+        /// to the owner, event, and escrow bookkeeping version 2 keeps
+        ///
+        /// Version 1 held sales in the contract without per-event escrow,
+        /// so tickets of events not yet completed are credited at the
+        /// event price to back their refunds.
+        fn migrate_legacy_ticket(&mut self, ticket_id: u64) {
+            let key = (StorageKey::key(&self.tickets), ticket_id);
+            let Some(legacy) = ink::env::get_contract_storage::<_, TicketV1>(&key).ok().flatten()
+            else {
+                return;
+            };
+            let Some(event) = self.events.get(legacy.event_id) else {
+                return;
+            };
+            let ticket = legacy.upgrade(event.price);
+            self.tickets.insert(ticket_id, &ticket);
+
+            let event_index = self.event_ticket_count.get(event.id).unwrap_or(0);
+            self.event_tickets.insert((event.id, event_index), &ticket_id);
+            self.event_ticket_count.insert(event.id, &event_index.saturating_add(1));
+            self.update_event_stats(event.id, |stats| {
+                stats.tickets_sold = stats.tickets_sold.saturating_add(1);
+                if ticket.is_refunded {
+                    stats.tickets_refunded = stats.tickets_refunded.saturating_add(1);
+                }
+                if ticket.times_used > 0 {
+                    stats.tickets_checked_in = stats.tickets_checked_in.saturating_add(1);
+                }
+            });
+            if ticket.is_refunded {
+                return;
+            }
+
+            self.add_owned_ticket(ticket.owner, ticket_id);
+            if !event.is_completed() {
+                self.credit_escrow(event.id, event.price);
+            }
+        }
+
+        /// Mark an event completed, for `complete_event` and `finalize_event`
+        fn close_completed(&mut self, event: &mut Event) -> Result<(), Error> {
+            // Mark event as completed
+            event.transition(EventStatus::Completed)?;
+            self.events.insert(event.id, &*event);
+
+            // Earnings unlock once attendees have had time to dispute
+            let ended = event.schedule.event_end.max(self.env().block_timestamp());
+            let unlock = ended.saturating_add(self.state.dispute_window);
+            self.withdrawal_unlocks.insert(event.id, &unlock);
+
+            // Return the organizer's bond in full
+            self.settle_bond(event, 0)?;
+
+            // Emit event
+            self.env().emit_event(EventCompleted {
+                event_id: event.id,
+            });
+            self.event_tickets_state_changed(event.id, TicketState::Expired);
+            self.notify(event, NotificationKind::EventCompleted);
+
+            Ok(())
+        }
+
+        /// Pause or resume an event's sales, for `pause_sales`/`resume_sales`
+        fn set_sales_paused(&mut self, event_id: u64, paused: bool) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            let mut event = self.ensure_event_manager(event_id)?;
+            event.transition(if paused {
+                EventStatus::Paused
+            } else {
+                EventStatus::OnSale
+            })?;
+            self.events.insert(event_id, &event);
+
+            self.env().emit_event(EventSalesPaused { event_id, paused });
+
+            Ok(())
+        }
+
+        /// Day of a multi-day event a ticket would check in for at `now`
+        ///
+        /// `None` for single-day events. Fails if no day has started yet or
+        /// the ticket was already checked in that day.
+        fn check_in_day(
+            &self,
+            event: &Event,
+            ticket_id: u64,
+            now: u64,
+        ) -> Result<Option<u32>, Error> {
+            if event.days.is_empty() {
+                return Ok(None);
+            }
+            let day = event.day_index(now).ok_or(Error::OutsideEventWindow)?;
+            if self.day_checkins.contains((ticket_id, day)) {
+                return Err(Error::TicketAlreadyUsed);
+            }
+            Ok(Some(day))
+        }
+
+        /// Resolve a ticket's metadata CID: its own, its tier's, or its event's
+        fn ticket_metadata_cid(&self, ticket: &Ticket) -> Option<String> {
+            if let Some(cid) = self.ticket_metadata.get(ticket.id) {
+                return Some(cid);
+            }
+            let event = self.events.get(ticket.event_id)?;
+            let tier_cid = ticket
+                .tier_id
+                .and_then(|tier_id| Self::tier(&event, tier_id).ok())
+                .map(|tier| tier.metadata_cid.clone())
+                .filter(|cid| !cid.is_empty());
+            Some(tier_cid.unwrap_or(event.metadata_cid))
+        }
+
+        /// Apply an update to an account's lifetime summary
+        fn update_summary(&mut self, account: AccountId, update: impl FnOnce(&mut UserSummary)) {
+            let mut summary = self.user_summaries.get(account).unwrap_or_default();
+            update(&mut summary);
+            self.user_summaries.insert(account, &summary);
+        }
+
+        /// Append an owner change to a ticket's history, overwriting the oldest when full
+        fn record_ownership(
+            &mut self,
+            ticket_id: u64,
+            from: Option<AccountId>,
+            to: AccountId,
+            price: Option<Balance>,
+        ) {
+            let count = self.ticket_history_count.get(ticket_id).unwrap_or(0);
+            self.ticket_history.insert(
+                (ticket_id, count % MAX_TICKET_HISTORY),
+                &OwnershipRecord {
+                    from,
+                    to,
+                    timestamp: self.env().block_timestamp(),
+                    price,
+                },
+            );
+            self.ticket_history_count.insert(ticket_id, &count.saturating_add(1));
+        }
+
+        /// Apply `update` to an event's stats
+        fn update_event_stats(&mut self, event_id: u64, update: impl FnOnce(&mut EventStats)) {
+            let mut stats = self.event_stats.get(event_id).unwrap_or_default();
+            update(&mut stats);
+            self.event_stats.insert(event_id, &stats);
+        }
+
+        /// Escrow a donation or tip sent with the call
+        fn receive_donation(&mut self, event_id: u64, tip: bool) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.frozen {
+                return Err(Error::EventFrozen);
+            }
+            if event.payment_token.is_some() {
+                return Err(Error::UnexpectedNativePayment);
+            }
+            let amount = self.env().transferred_value();
+            if amount == 0 {
+                return Err(Error::InvalidInput);
+            }
+
+            self.credit_escrow(event_id, amount);
+            self.update_event_stats(event_id, |stats| {
+                if tip {
+                    stats.tips = stats.tips.saturating_add(amount);
+                } else {
+                    stats.donations = stats.donations.saturating_add(amount);
+                }
+            });
+
+            self.env().emit_event(DonationReceived {
+                event_id,
+                donor: self.env().caller(),
+                amount,
+                tip,
+            });
+
+            Ok(())
+        }
+
+        /// Count a refunded ticket in its event's stats
+        fn record_event_refund(&mut self, event_id: u64, amount: Balance) {
+            self.update_event_stats(event_id, |stats| {
+                stats.tickets_refunded = stats.tickets_refunded.saturating_add(1);
+                stats.refunds_paid = stats.refunds_paid.saturating_add(amount);
+            });
+        }
+
+        /// Emit a notification for both the event and its organizer
+        fn notify(&self, event: &Event, kind: NotificationKind) {
+            self.env().emit_event(SubscriptionNotification {
+                target: SubscriptionTarget::Event(event.id),
+                event_id: event.id,
+                kind,
+            });
+            self.env().emit_event(SubscriptionNotification {
+                target: SubscriptionTarget::Organizer(event.organizer),
+                event_id: event.id,
+                kind,
+            });
+        }
+    }
+
+    impl From<Error> for PSP34Error {
+        fn from(error: Error) -> Self {
+            match error {
+                Error::TicketNotFound => PSP34Error::TokenNotExists,
+                Error::NotTicketOwner => PSP34Error::NotApproved,
+                Error::SelfApproval => PSP34Error::SelfApprove,
+                other => PSP34Error::Custom(ink::prelude::format!("{:?}", other)),
+            }
+        }
+    }
+
+    /// Core ticketing surface, callable by other contracts via `TicketDotCore`
+    impl TicketDotCore for TicketDot {
+        #[ink(message)]
+        fn get_event(&self, event_id: u64) -> Option<Event> {
+            self.events.get(event_id)
+        }
+
+        #[ink(message)]
+        fn get_ticket(&self, ticket_id: u64) -> Option<Ticket> {
+            self.tickets.get(ticket_id)
+        }
+
+        #[ink(message)]
+        fn get_current_price(&self, event_id: u64) -> Option<Balance> {
+            let event = self.events.get(event_id)?;
+            self.base_price(&event, self.env().block_timestamp(), 0).ok()
+        }
+
+        #[ink(message)]
+        fn verify_ticket(&self, ticket_id: u64) -> TicketStatus {
+            let Some(ticket) = self.tickets.get(ticket_id) else {
+                return TicketStatus::NotFound;
+            };
+            let Some(event) = self.events.get(ticket.event_id) else {
+                return TicketStatus::NotFound;
+            };
+
+            if ticket.is_refunded {
+                TicketStatus::Refunded
+            } else if ticket.uses_remaining == 0 {
+                TicketStatus::Used
+            } else if event.is_cancelled() {
+                TicketStatus::EventCancelled
+            } else if event.frozen {
+                TicketStatus::EventFrozen
+            } else if event.is_completed() {
+                TicketStatus::EventCompleted
+            } else if event.schedule.has_ended(self.env().block_timestamp()) {
+                TicketStatus::Expired
+            } else if event.schedule.check_event_window(self.env().block_timestamp()).is_err() {
+                TicketStatus::WrongWindow
+            } else {
+                match self.check_in_day(&event, ticket_id, self.env().block_timestamp()) {
+                    Ok(_) => TicketStatus::Valid,
+                    Err(Error::TicketAlreadyUsed) => TicketStatus::Used,
+                    Err(_) => TicketStatus::WrongWindow,
+                }
+            }
+        }
+
+        #[ink(message, payable)]
+        fn buy_ticket(&mut self, event_id: u64) -> Result<u64, Error> {
+            self.purchase_ticket(event_id, None, None)
+        }
+
+        #[ink(message)]
+        fn transfer_ticket(
+            &mut self,
+            ticket_id: u64,
+            to: AccountId,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            // Get ticket
+            let ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+
+            // Verify ownership or approval
+            if !self.is_authorized(&ticket, caller) {
+                return Err(Error::NotTicketOwner);
+            }
+
+            self.move_ticket(ticket, to, None)
+        }
+
+        #[ink(message)]
+        fn use_ticket(&mut self, ticket_id: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+
+            // Get ticket
+            let mut ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+
+            // Get event to verify organizer
+            let event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+
+            // Only organizer, co-organizers, check-in staff, or admin can mark ticket as used
+            if caller != event.organizer
+                && !self.co_organizers.contains((event.id, caller))
+                && !self.checkin_staff.contains((event.id, caller))
+                && !self.has_role(Role::Admin, caller)
+            {
+                return Err(Error::NotTicketOwner);
+            }
+
+            // Can't use refunded ticket
+            if ticket.is_refunded {
+                return Err(Error::TicketAlreadyRefunded);
+            }
+
+            // Can't use ticket with no check-ins left
+            if ticket.uses_remaining == 0 {
+                return Err(Error::TicketAlreadyUsed);
+            }
+
+            // Can't use ticket for cancelled or frozen event
+            if event.is_cancelled() {
+                return Err(Error::EventCancelled);
+            }
+            if event.frozen {
+                return Err(Error::EventFrozen);
+            }
+
+            // Can't use ticket for completed event
+            if event.is_completed() {
+                return Err(Error::EventCompleted);
+            }
+
+            // Check-in only during the event window; tickets expire at its end
+            let now = self.env().block_timestamp();
+            if event.schedule.has_ended(now) {
+                return Err(Error::TicketExpired);
+            }
+            event.schedule.check_event_window(now)?;
+
+            // Credential-gated events check the holder's credential again at the door
+            let claim_hash = self.check_credential(event.id, ticket.owner)?;
+
+            // Multi-day events admit each ticket once per day
+            let day = self.check_in_day(&event, ticket_id, now)?;
+            if let Some(day) = day {
+                self.day_checkins.insert((ticket_id, day), &());
+            }
+            if let Some(claim_hash) = claim_hash {
+                if !self.checked_credentials.contains(ticket_id) {
+                    self.checked_credentials.insert(ticket_id, &claim_hash);
+                }
+            }
+
+            // Use up one check-in (a used ticket can no longer be resold)
+            let first_use = ticket.times_used == 0;
+            ticket.uses_remaining = ticket.uses_remaining.saturating_sub(1);
+            ticket.times_used = ticket.times_used.saturating_add(1);
+            self.tickets.insert(ticket_id, &ticket);
+            self.listings.remove(ticket_id);
+            let state = if ticket.uses_remaining == 0 {
+                TicketState::Used
+            } else {
+                TicketState::PartlyUsed
+            };
+            self.ticket_state_changed(&ticket, state);
+
+            // Append to the holder's attendance history on first entry
+            if first_use {
+                self.update_event_stats(ticket.event_id, |stats| {
+                    stats.tickets_checked_in = stats.tickets_checked_in.saturating_add(1);
+                });
+                self.award_points(ticket.owner, self.state.points_per_checkin);
+                let index = self.attendance_count.get(ticket.owner).unwrap_or(0);
+                self.attendance_records.insert(
+                    (ticket.owner, index),
+                    &AttendanceRecord {
+                        event_id: ticket.event_id,
+                        ticket_id,
+                        tier_id: ticket.tier_id,
+                        attended_at: self.env().block_timestamp(),
+                    },
+                );
+                self.attendance_count.insert(ticket.owner, &index.saturating_add(1));
+            }
+
+            // Count each event once towards the holder's summary
+            if !self.attended_events.contains((ticket.owner, ticket.event_id)) {
+                self.attended_events.insert((ticket.owner, ticket.event_id), &());
+                self.update_summary(ticket.owner, |summary| {
+                    summary.events_attended = summary.events_attended.saturating_add(1);
+                });
+                self.mint_attendance_nft(&ticket);
+            }
+
+            // Emit event
+            self.env().emit_event(TicketUsed {
+                ticket_id,
+                event_id: ticket.event_id,
+                uses_remaining: ticket.uses_remaining,
+            });
+
+            Ok(())
+        }
+    }
+
+    /// PSP34 view of tickets, backed by `tickets` and the owner ticket index
+    impl PSP34 for TicketDot {
+        #[ink(message)]
+        fn collection_id(&self) -> Id {
+            Id::Bytes(scale::Encode::encode(&self.env().account_id()))
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> u32 {
+            self.owner_ticket_count.get(owner).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        fn owner_of(&self, id: Id) -> Option<AccountId> {
+            self.ticket_for_id(&id).map(|ticket| ticket.owner)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool {
+            if self.operator_approvals.contains((owner, operator)) {
+                return true;
+            }
+            match id.and_then(|id| self.ticket_for_id(&id)) {
+                Some(ticket) => {
+                    ticket.owner == owner
+                        && self.ticket_approvals.get(ticket.id) == Some(operator)
+                }
+                None => false,
+            }
+        }
+
+        #[ink(message)]
+        fn approve(
+            &mut self,
+            operator: AccountId,
+            id: Option<Id>,
+            approved: bool,
+        ) -> Result<(), PSP34Error> {
+            match id {
+                Some(token) => {
+                    let ticket = self.ticket_for_id(&token).ok_or(PSP34Error::TokenNotExists)?;
+                    self.set_ticket_approval(ticket.id, operator, approved)?;
+                }
+                None => self.set_approval_for_all(operator, approved)?,
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn transfer(&mut self, to: AccountId, id: Id, _data: Vec<u8>) -> Result<(), PSP34Error> {
+            let caller = self.env().caller();
+            let ticket = self.ticket_for_id(&id).ok_or(PSP34Error::TokenNotExists)?;
+
+            if !self.is_authorized(&ticket, caller) {
+                return Err(PSP34Error::NotApproved);
+            }
+
+            self.move_ticket(ticket, to, None)?;
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn total_supply(&self) -> u128 {
+            self.ticket_counter.saturating_sub(self.state.burned_tickets) as u128
+        }
+    }
+
+    /// PSP34 metadata of tickets and of the collection
+    ///
+    /// For tickets the supported keys are `name` (the event name) and `uri`
+    /// (`ipfs://<CID>`, see `get_ticket_metadata`). For the collection ID
+    /// they are `name`, `symbol`, and `baseURI` (see `get_collection_metadata`).
+    impl PSP34Metadata for TicketDot {
+        #[ink(message)]
+        fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>> {
+            if id == PSP34::collection_id(self) {
+                let metadata = &self.state.collection_metadata;
+                let value = match key.as_slice() {
+                    b"name" => &metadata.name,
+                    b"symbol" => &metadata.symbol,
+                    b"baseURI" => &metadata.base_uri,
+                    _ => return None,
+                };
+                return Some(value.clone().into_bytes()).filter(|value| !value.is_empty());
+            }
+
+            let ticket = self.ticket_for_id(&id)?;
+            match key.as_slice() {
+                b"name" => Some(self.events.get(ticket.event_id)?.name.into_bytes()),
+                b"uri" => {
+                    let cid = self.ticket_metadata_cid(&ticket)?;
+                    Some(ink::prelude::format!("ipfs://{}", cid).into_bytes())
+                }
+                _ => None,
+            }
+        }
+    }
+
+    /// Unit tests
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn create_event_works() {
+            let mut contract = TicketDot::new();
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            assert_eq!(event_id, 0);
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.name, "Test Event");
+            assert_eq!(event.available_tickets, 100);
+        }
+
+        #[ink::test]
+        fn buy_ticket_works() {
+            let mut contract = TicketDot::new();
+            
+            // Create event
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            // Buy ticket
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            assert_eq!(ticket_id, 0);
+            let ticket = contract.get_ticket(ticket_id).unwrap();
+            assert_eq!(ticket.event_id, event_id);
+        }
+
+        #[ink::test]
+        fn transfer_ticket_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            
+            // Create event and buy ticket
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            // Transfer ticket
+            contract.transfer_ticket(ticket_id, accounts.bob).unwrap();
+            
+            let ticket = contract.get_ticket(ticket_id).unwrap();
+            assert_eq!(ticket.owner, accounts.bob);
+        }
+
+        #[ink::test]
+        fn core_trait_messages_work() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            // Call through the trait, as other contracts do
+            let event = TicketDotCore::get_event(&contract, event_id).unwrap();
+            assert_eq!(event.name, "Test Event");
+            assert_eq!(TicketDotCore::get_current_price(&contract, event_id), Some(1000));
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = TicketDotCore::buy_ticket(&mut contract, event_id).unwrap();
+            assert_eq!(TicketDotCore::verify_ticket(&contract, ticket_id), TicketStatus::Valid);
+
+            TicketDotCore::transfer_ticket(&mut contract, ticket_id, accounts.bob).unwrap();
+            TicketDotCore::use_ticket(&mut contract, ticket_id).unwrap();
+            let ticket = TicketDotCore::get_ticket(&contract, ticket_id).unwrap();
+            assert_eq!(ticket.owner, accounts.bob);
+            assert_eq!(ticket.uses_remaining, 0);
+        }
+
+        #[ink::test]
+        fn cancel_event_works() {
+            let mut contract = TicketDot::new();
+            
+            // Create event
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            // Cancel event
+            contract.cancel_event(event_id).unwrap();
+            
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.status, EventStatus::Cancelled);
+        }
+
+        #[ink::test]
+        fn complete_event_works() {
+            let mut contract = TicketDot::new();
+            
+            // Create event
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            // Complete event
+            contract.complete_event(event_id).unwrap();
+            
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.status, EventStatus::Completed);
+        }
+
+        #[ink::test]
+        fn cancel_ticket_works() {
+            let mut contract = TicketDot::new();
+            
+            // Create event
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            // Buy ticket
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            // Check event has 99 available tickets
+            let event_before = contract.get_event(event_id).unwrap();
+            assert_eq!(event_before.available_tickets, 99);
+
+            // Cancel ticket
+            contract.cancel_ticket(ticket_id).unwrap();
+            
+            // Check ticket is refunded
+            let ticket = contract.get_ticket(ticket_id).unwrap();
+            assert!(ticket.is_refunded);
+
+            // Check event now has 100 available tickets again
+            let event_after = contract.get_event(event_id).unwrap();
+            assert_eq!(event_after.available_tickets, 100);
+        }
+
+        #[ink::test]
+        fn attendance_history_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let first = contract.buy_ticket(event_id).unwrap();
+            let second = contract.buy_ticket(event_id).unwrap();
+
+            // Check in both tickets
+            contract.use_ticket(first).unwrap();
+            contract.use_ticket(second).unwrap();
+
+            assert_eq!(contract.get_attendance_count(accounts.alice), 2);
+            let history = contract.get_attendance_history(accounts.alice, 1, 10);
+            assert_eq!(history.len(), 1);
+            assert_eq!(history[0].ticket_id, second);
+            assert_eq!(history[0].event_id, event_id);
+        }
+
+        #[ink::test]
+        fn submit_review_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            // Unused ticket can't review
+            assert_eq!(
+                contract.submit_review(ticket_id, 5, String::from("QmReview")),
+                Err(Error::TicketNotUsed)
+            );
+
+            contract.use_ticket(ticket_id).unwrap();
+
+            // Event must be completed first
+            assert_eq!(
+                contract.submit_review(ticket_id, 5, String::from("QmReview")),
+                Err(Error::EventNotCompleted)
+            );
+
+            contract.complete_event(event_id).unwrap();
+            assert_eq!(
+                contract.submit_review(ticket_id, 6, String::new()),
+                Err(Error::InvalidInput)
+            );
+            contract.submit_review(ticket_id, 4, String::from("QmReview")).unwrap();
+
+            // Double review is rejected
+            assert_eq!(
+                contract.submit_review(ticket_id, 5, String::new()),
+                Err(Error::AlreadyReviewed)
+            );
+
+            let rating = contract.get_event_rating(event_id);
+            assert_eq!(rating.review_count, 1);
+            assert_eq!(rating.average_x100, 400);
+            assert_eq!(contract.get_organizer_rating(accounts.alice), rating);
+        }
+
+        #[ink::test]
+        fn referral_purchase_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+            contract.set_platform_fee(500).unwrap();
+            assert_eq!(contract.set_referral_fee_share(10_001), Err(Error::InvalidInput));
+            contract.set_referral_fee_share(2_000).unwrap();
+            contract.register_referral_code(String::from("ALICE")).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // Code owner can't refer themselves
+            assert_eq!(
+                contract.buy_ticket_with_referral(event_id, String::from("ALICE")),
+                Err(Error::SelfReferral)
+            );
+
+            // Bob buys with Alice's code, but the event hasn't opted in yet
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.buy_ticket_with_referral(event_id, String::from("ALICE")),
+                Err(Error::ReferralsDisabled)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.set_referrals_enabled(event_id, true).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let ticket_id = contract
+                .buy_ticket_with_referral(event_id, String::from("ALICE"))
+                .unwrap();
+
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().owner, accounts.bob);
+
+            // Alice earned 20% of the 50 fee, taken from the platform's cut
+            assert_eq!(contract.get_referral_rewards(accounts.alice, None), 10);
+            assert_eq!(contract.get_platform_fees(None), 40);
+            assert_eq!(contract.escrow_balance.get(event_id), Some(950));
+        }
+
+        #[ink::test]
+        fn user_summary_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let first = contract.buy_ticket(event_id).unwrap();
+            let second = contract.buy_ticket(event_id).unwrap();
+            let third = contract.buy_ticket(event_id).unwrap();
+
+            // Two check-ins at the same event count as one event attended
+            contract.use_ticket(first).unwrap();
+            contract.use_ticket(second).unwrap();
+            contract.cancel_ticket(third).unwrap();
+
+            let summary = contract.get_user_summary(accounts.alice);
+            assert_eq!(summary.tickets_bought, 3);
+            assert_eq!(summary.total_spent, 3000);
+            assert_eq!(summary.refunds_received, 1000);
+            assert_eq!(summary.events_attended, 1);
+        }
+
+        #[ink::test]
+        fn psp34_transfer_and_approval_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            let refunded = contract.buy_ticket(event_id).unwrap();
+            contract.cancel_ticket(refunded).unwrap();
+
+            // Refunded tickets are burned
+            assert_eq!(PSP34::total_supply(&contract), 1);
+            assert_eq!(PSP34::owner_of(&contract, Id::U64(refunded)), None);
+            assert_eq!(PSP34::owner_of(&contract, Id::U64(ticket_id)), Some(accounts.alice));
+            assert_eq!(PSP34::balance_of(&contract, accounts.alice), 1);
+
+            // Bob can't move the ticket until approved
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                PSP34::transfer(&mut contract, accounts.bob, Id::U64(ticket_id), Vec::new()),
+                Err(PSP34Error::NotApproved)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            PSP34::approve(&mut contract, accounts.bob, Some(Id::U64(ticket_id)), true).unwrap();
+            assert!(PSP34::allowance(
+                &contract,
+                accounts.alice,
+                accounts.bob,
+                Some(Id::U64(ticket_id))
+            ));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            PSP34::transfer(&mut contract, accounts.charlie, Id::U64(ticket_id), Vec::new())
+                .unwrap();
+            assert_eq!(PSP34::owner_of(&contract, Id::U64(ticket_id)), Some(accounts.charlie));
+
+            // Approval is cleared by the transfer
+            assert!(!PSP34::allowance(
+                &contract,
+                accounts.charlie,
+                accounts.bob,
+                Some(Id::U64(ticket_id))
+            ));
+        }
+
+        #[ink::test]
+        fn buy_ticket_tier_works() {
+            let mut contract = TicketDot::new();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    3,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+            let vip = contract
+                .add_ticket_tier(event_id, String::from("VIP"), 5000, 1, String::from("QmVip"), 1)
+                .unwrap();
+
+            // Tier capacity can't exceed unsold general tickets
+            assert_eq!(
+                contract.add_ticket_tier(event_id, String::from("Extra"), 5000, 3, String::new(), 1),
+                Err(Error::InvalidInput)
+            );
+
+            // Tier price must be paid in full
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(
+                contract.buy_ticket_tier(event_id, vip),
+                Err(Error::InsufficientPayment { expected: 5000, got: 1000 })
+            );
+            assert_eq!(contract.buy_ticket_tier(event_id, 7), Err(Error::TierNotFound));
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(5000);
+            let ticket_id = contract.buy_ticket_tier(event_id, vip).unwrap();
+            let ticket = contract.get_ticket(ticket_id).unwrap();
+            assert_eq!(ticket.tier_id, Some(vip));
+            assert_eq!(ticket.price_paid, 5000);
+            assert_eq!(contract.buy_ticket_tier(event_id, vip), Err(Error::SoldOut));
+
+            // General admission has the remaining two tickets
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            contract.buy_ticket(event_id).unwrap();
+            contract.buy_ticket(event_id).unwrap();
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::SoldOut));
+
+            // Cancelling the VIP ticket returns it to the tier
+            contract.cancel_ticket(ticket_id).unwrap();
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.tiers[0].available, 1);
+            assert_eq!(event.available_tickets, 1);
+        }
+
+        #[ink::test]
+        fn reserved_seating_works() {
+            let mut contract = TicketDot::new();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    4,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            // Layout must have a seat for every ticket
+            assert_eq!(
+                contract.set_seat_layout(event_id, SeatLayout::List(vec![1, 2])),
+                Err(Error::InvalidInput)
+            );
+            contract
+                .set_seat_layout(event_id, SeatLayout::Grid { rows: 2, seats_per_row: 2 })
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::SeatRequired));
+            assert_eq!(contract.buy_ticket_seat(event_id, 4), Err(Error::InvalidSeat));
+
+            let ticket_id = contract.buy_ticket_seat(event_id, 2).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().seat_id, Some(2));
+            assert_eq!(contract.buy_ticket_seat(event_id, 2), Err(Error::SeatTaken));
+            assert_eq!(contract.get_available_seats(event_id), vec![0, 1, 3]);
+
+            // Cancelling the ticket frees the seat
+            contract.cancel_ticket(ticket_id).unwrap();
+            assert_eq!(contract.get_available_seats(event_id), vec![0, 1, 2, 3]);
+        }
+
+        #[ink::test]
+        fn schedule_gates_sales_and_check_in() {
+            let mut contract = TicketDot::new();
+
+            let schedule = EventSchedule {
+                sale_start: 100,
+                sale_end: 200,
+                event_start: 300,
+                event_end: 400,
+            };
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { schedule, ..Default::default() },
+                )
+                .unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().schedule, schedule);
+
+            // Sales open at sale_start and close at sale_end
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::SaleNotOpen { opens_at: 100 }));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(150);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(200);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::SaleClosed));
+
+            // Check-in only during the event
+            assert_eq!(contract.use_ticket(ticket_id), Err(Error::OutsideEventWindow));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(350);
+            contract.use_ticket(ticket_id).unwrap();
+
+            // Inverted windows are rejected
+            let inverted = EventSchedule { sale_start: 200, sale_end: 100, ..Default::default() };
+            assert_eq!(
+                contract.create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { schedule: inverted, ..Default::default() },
+                ),
+                Err(Error::InvalidInput)
+            );
+        }
+
+        #[ink::test]
+        fn buy_tickets_works() {
+            let mut contract = TicketDot::new();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            // Payment must cover the whole batch
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(3000);
+            assert_eq!(
+                contract.buy_tickets(event_id, 4),
+                Err(Error::InsufficientPayment { expected: 4000, got: 3000 })
+            );
+            assert_eq!(contract.buy_tickets(event_id, 0), Err(Error::InvalidInput));
+
+            let ticket_ids = contract.buy_tickets(event_id, 3).unwrap();
+            assert_eq!(ticket_ids, vec![0, 1, 2]);
+            assert_eq!(contract.get_event(event_id).unwrap().available_tickets, 97);
+
+            // Batch size is capped
+            contract.set_max_tickets_per_purchase(2).unwrap();
+            assert_eq!(
+                contract.buy_tickets(event_id, 3),
+                Err(Error::PurchaseLimitReached { limit: 2 })
+            );
+        }
+
+        #[ink::test]
+        fn resale_marketplace_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            contract.list_ticket_for_sale(ticket_id, 1500).unwrap();
+            assert_eq!(contract.get_listing(ticket_id).unwrap().price, 1500);
+
+            // Bob must pay the listing price exactly
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.buy_listed_ticket(ticket_id),
+                Err(Error::InsufficientPayment { expected: 1500, got: 1000 })
+            );
+            assert_eq!(contract.delist_ticket(ticket_id), Err(Error::NotTicketOwner));
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1500);
+            contract.buy_listed_ticket(ticket_id).unwrap();
+
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().owner, accounts.bob);
+            assert_eq!(contract.get_listing(ticket_id), None);
+            assert_eq!(contract.buy_listed_ticket(ticket_id), Err(Error::NotListed));
+        }
+
+        #[ink::test]
+        fn resale_royalty_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { royalty_bps: 1_000, ..Default::default() },
+                )
+                .unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().royalty_bps, 1_000);
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            // Bob buys Charlie's ticket on the marketplace
+            contract.transfer_ticket(ticket_id, accounts.charlie).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.list_ticket_for_sale(ticket_id, 2000).unwrap();
+
+            let charlie_before =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie)
+                    .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2000);
+            contract.buy_listed_ticket(ticket_id).unwrap();
+
+            // Seller receives the price minus the 10% royalty
+            let charlie_after =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie)
+                    .unwrap();
+            assert_eq!(charlie_after - charlie_before, 1800);
+            assert_eq!(contract.escrow_balance.get(event_id), Some(1200));
+
+            // Royalties above the cap are rejected
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                contract.create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { royalty_bps: MAX_ROYALTY_BPS + 1, ..Default::default() },
+                ),
+                Err(Error::InvalidInput)
+            );
+        }
+
+        #[ink::test]
+        fn resale_price_cap_works() {
+            let mut contract = TicketDot::new();
+
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { max_resale_multiplier_bps: 11_000, ..Default::default() },
+                )
+                .unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().max_resale_multiplier_bps, 11_000);
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            // 110% of face value is the most a ticket can be listed for
+            assert_eq!(
+                contract.list_ticket_for_sale(ticket_id, 1101),
+                Err(Error::ResalePriceTooHigh)
+            );
+            contract.list_ticket_for_sale(ticket_id, 1100).unwrap();
+
+            // Caps below face value are rejected
+            assert_eq!(
+                contract.create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { max_resale_multiplier_bps: 9_000, ..Default::default() },
+                ),
+                Err(Error::InvalidInput)
+            );
+        }
+
+        #[ink::test]
+        fn soulbound_tickets_cannot_move() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { transferable: false, ..Default::default() },
+                )
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            assert_eq!(
+                contract.transfer_ticket(ticket_id, accounts.bob),
+                Err(Error::TicketNotTransferable)
+            );
+            assert_eq!(
+                contract.list_ticket_for_sale(ticket_id, 1000),
+                Err(Error::TicketNotTransferable)
+            );
+            assert!(PSP34::transfer(&mut contract, accounts.bob, Id::U64(ticket_id), Vec::new())
+                .is_err());
+        }
+
+        #[ink::test]
+        fn psp22_event_rejects_native_payment() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { payment_token: Some(accounts.django), ..Default::default() },
+                )
+                .unwrap();
+            assert_eq!(
+                contract.get_event(event_id).unwrap().payment_token,
+                Some(accounts.django)
+            );
+
+            // Native value can't be used to pay for a token-priced ticket
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::UnexpectedNativePayment));
+        }
+
+        #[ink::test]
+        fn platform_fee_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(contract.set_platform_fee(MAX_PLATFORM_FEE_BPS + 1), Err(Error::InvalidInput));
+            contract.set_platform_fee(500).unwrap();
+            contract.set_treasury(accounts.eve).unwrap();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().fee_paid, 50);
+            assert_eq!(contract.get_platform_fees(None), 50);
+
+            // The fee is kept when the ticket is cancelled
+            contract.cancel_ticket(ticket_id).unwrap();
+            assert_eq!(contract.get_user_summary(accounts.alice).refunds_received, 950);
+
+            // Fees go to the treasury
+            let treasury_before =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve)
+                    .unwrap();
+            assert_eq!(contract.withdraw_platform_fees(), Ok(50));
+            let treasury_after =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve)
+                    .unwrap();
+            assert_eq!(treasury_after - treasury_before, 50);
+            assert_eq!(contract.get_platform_fees(None), 0);
+
+            // Only fee managers can withdraw
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.withdraw_platform_fees(), Err(Error::MissingRole));
+        }
+
+        #[ink::test]
+        fn escrow_ledger_isolates_events() {
+            let mut contract = TicketDot::new();
+            contract.set_platform_fee(100).unwrap();
+
+            let first = contract
+                .create_event(
+                    String::from("First Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+            let second = contract
+                .create_event(
+                    String::from("Second Event"),
+                    2000,
+                    100,
+                    String::from("QmTest456"),
+                )
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let kept = contract.buy_ticket(first).unwrap();
+            let cancelled = contract.buy_ticket(first).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2000);
+            contract.buy_ticket(second).unwrap();
+
+            // Escrow is credited net of fees and debited on refunds
+            contract.cancel_ticket(cancelled).unwrap();
+            assert_eq!(contract.escrow_balance.get(first), Some(990));
+            assert_eq!(contract.escrow_balance.get(second), Some(1980));
+
+            // Withdrawing only drains the event's own escrow
+            contract.use_ticket(kept).unwrap();
+            contract.complete_event(first).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(DEFAULT_DISPUTE_WINDOW);
+            contract.withdraw_earnings(first).unwrap();
+            assert_eq!(contract.escrow_balance.get(first), Some(0));
+            assert_eq!(contract.escrow_balance.get(second), Some(1980));
+        }
+
+        #[ink::test]
+        fn withdraw_earnings_is_incremental() {
+            let mut contract = TicketDot::new();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            contract.buy_ticket(event_id).unwrap();
+            contract.buy_ticket(event_id).unwrap();
+            contract.complete_event(event_id).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(DEFAULT_DISPUTE_WINDOW);
+
+            // The same earnings can't be pulled twice
+            contract.withdraw_earnings(event_id).unwrap();
+            assert_eq!(contract.get_total_withdrawn(event_id), 2000);
+            assert_eq!(contract.withdraw_earnings(event_id), Err(Error::NothingToWithdraw));
+
+            // Funds reaching the escrow later can be withdrawn on top
+            contract.credit_escrow(event_id, 300);
+            contract.withdraw_earnings(event_id).unwrap();
+            assert_eq!(contract.get_total_withdrawn(event_id), 2300);
+        }
+
+        #[ink::test]
+        fn pause_blocks_mutations_but_not_refunds() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            contract.pause().unwrap();
+            assert!(contract.is_paused());
+
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::ContractPaused));
+            assert_eq!(
+                contract.transfer_ticket(ticket_id, accounts.bob),
+                Err(Error::ContractPaused)
+            );
+            assert_eq!(contract.use_ticket(ticket_id), Err(Error::ContractPaused));
+
+            // Refunds keep working
+            contract.cancel_ticket(ticket_id).unwrap();
+
+            // Only pausers can unpause
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.unpause(), Err(Error::MissingRole));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.unpause().unwrap();
+            contract.buy_ticket(event_id).unwrap();
+        }
+
+        #[ink::test]
+        fn roles_work() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(contract.has_role(Role::Admin, accounts.alice));
+            contract.grant_role(Role::Pauser, accounts.bob).unwrap();
+            contract.grant_role(Role::FeeManager, accounts.charlie).unwrap();
+
+            // Bob can pause but not set fees
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.pause().unwrap();
+            assert_eq!(contract.set_platform_fee(100), Err(Error::MissingRole));
+            assert_eq!(
+                contract.grant_role(Role::Admin, accounts.bob),
+                Err(Error::MissingRole)
+            );
+
+            // Charlie can set fees
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.set_platform_fee(100).unwrap();
+
+            // The last admin can't be revoked; revoked roles stop working
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                contract.revoke_role(Role::Admin, accounts.alice),
+                Err(Error::CannotRemoveLastAdmin)
+            );
+            contract.revoke_role(Role::Pauser, accounts.bob).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.unpause(), Err(Error::MissingRole));
+        }
+
+        #[ink::test]
+        fn migrate_requires_outdated_storage() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(contract.get_storage_version(), STORAGE_VERSION);
+            assert_eq!(contract.migrate(), Err(Error::AlreadyMigrated));
+
+            // Storage left behind by older code is brought up to date
+            contract.state.storage_version = 0;
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.migrate(), Err(Error::MissingRole));
+            assert_eq!(
+                contract.upgrade_contract(Hash::from([1u8; 32])),
+                Err(Error::MissingRole)
+            );
+
+            // Nothing changes state until migration finishes
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                contract.create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                ),
+                Err(Error::MigrationPending)
+            );
+            contract.migrate().unwrap();
+            assert_eq!(contract.get_storage_version(), STORAGE_VERSION);
+        }
+
+        #[ink::test]
+        fn migrate_converts_event_flags_to_status() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            // Write events the way version 1 stored them
+            let flags = [(0, true, false, false), (1, false, true, false), (2, false, false, true)];
+            for (event_id, active, cancelled, completed) in flags {
+                let legacy = EventV1 {
+                    id: event_id,
+                    name: String::from("Test Event"),
+                    organizer: accounts.alice,
+                    price: 1000,
+                    total_tickets: 100,
+                    available_tickets: 100,
+                    timestamp: 0,
+                    metadata_cid: String::from("QmTest123"),
+                    active,
+                    cancelled,
+                    completed,
+                };
+                let key = (StorageKey::key(&contract.events), event_id);
+                ink::env::set_contract_storage(&key, &legacy);
+            }
+            contract.event_counter = 3;
+            contract.state.storage_version = 1;
+
+            contract.migrate().unwrap();
+            assert_eq!(contract.get_storage_version(), STORAGE_VERSION);
+            assert_eq!(contract.get_event(0).unwrap().status, EventStatus::OnSale);
+            assert_eq!(contract.get_event(1).unwrap().status, EventStatus::Cancelled);
+            assert_eq!(contract.get_event(2).unwrap().status, EventStatus::Completed);
+            assert_eq!(contract.get_event(0).unwrap().uses_per_ticket, 1);
+        }
+
+        #[ink::test]
+        fn migrate_upgrades_baseline_storage() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            // Root bytes written by version 1: both counters, then the admin
+            let root = scale::Encode::encode(&(2u64, 3u64, accounts.bob));
+            let mut contract =
+                <TicketDot as ink::storage::traits::Storable>::decode(&mut &root[..]).unwrap();
+            assert_eq!(contract.get_storage_version(), 1);
+            assert!(!contract.has_role(Role::Admin, accounts.bob));
+
+            for (event_id, cancelled) in [(0, false), (1, true)] {
+                let legacy = EventV1 {
+                    id: event_id,
+                    name: String::from("Test Event"),
+                    organizer: accounts.bob,
+                    price: 1000,
+                    total_tickets: 100,
+                    available_tickets: 98,
+                    timestamp: 0,
+                    metadata_cid: String::from("QmTest123"),
+                    active: !cancelled,
+                    cancelled,
+                    completed: false,
+                };
+                let key = (StorageKey::key(&contract.events), event_id);
+                ink::env::set_contract_storage(&key, &legacy);
+            }
+            let tickets = [(0, accounts.charlie, true, false), (1, accounts.charlie, false, false)];
+            for (ticket_id, owner, is_used, is_refunded) in tickets
+                .into_iter()
+                .chain([(2, accounts.django, false, true)])
+            {
+                let legacy = TicketV1 {
+                    id: ticket_id,
+                    event_id: ticket_id / 2,
+                    owner,
+                    purchase_time: 0,
+                    is_used,
+                    is_refunded,
+                };
+                let key = (StorageKey::key(&contract.tickets), ticket_id);
+                ink::env::set_contract_storage(&key, &legacy);
+            }
+
+            // Only the admin recorded by version 1 can migrate
+            assert_eq!(contract.migrate(), Err(Error::MissingRole));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.migrate().unwrap();
+            assert_eq!(contract.get_storage_version(), STORAGE_VERSION);
+            assert!(contract.has_role(Role::Admin, accounts.bob));
+
+            assert_eq!(contract.get_event(1).unwrap().status, EventStatus::Cancelled);
+            let migrated = contract.get_tickets(vec![0, 1, 2]);
+            let used = migrated[0].clone().unwrap();
+            assert_eq!((used.uses_remaining, used.times_used), (0, 1));
+            let unused = migrated[1].clone().unwrap();
+            assert_eq!((unused.uses_remaining, unused.price_paid), (1, 1000));
+            assert!(migrated[2].clone().unwrap().is_refunded);
+
+            let mut held = contract.get_my_tickets(accounts.charlie);
+            held.sort();
+            assert_eq!(held, vec![0, 1]);
+            assert!(contract.get_my_tickets(accounts.django).is_empty());
+            assert_eq!(contract.get_event_ticket_count(0), 2);
+            assert_eq!(contract.get_event_stats(0).unwrap().net_escrow, 2000);
+            assert_eq!(contract.get_event_stats(1).unwrap().tickets_refunded, 1);
+        }
+
+        #[ink::test]
+        fn allowlist_presale_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { presale_end: 100, ..Default::default() },
+                )
+                .unwrap();
+            contract.add_to_allowlist(event_id, vec![accounts.bob]).unwrap();
+            assert!(contract.is_allowlisted(event_id, accounts.bob));
+
+            // During the presale only Bob can buy
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::NotAllowlisted));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.buy_ticket(event_id).unwrap();
+
+            // Everyone can buy once the presale closes
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(100);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.buy_ticket(event_id).unwrap();
+
+            // Only the organizer manages the allowlist
+            assert_eq!(
+                contract.remove_from_allowlist(event_id, vec![accounts.bob]),
+                Err(Error::NotOrganizer)
+            );
+        }
+
+        #[ink::test]
+        fn merkle_allowlist_presale_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { presale_end: 100, ..Default::default() },
+                )
+                .unwrap();
+
+            // Two-leaf tree of Bob and Charlie
+            let bob_leaf = merkle_leaf(&accounts.bob);
+            let charlie_leaf = merkle_leaf(&accounts.charlie);
+            let mut input = [0u8; 64];
+            let (first, second) = if bob_leaf <= charlie_leaf {
+                (bob_leaf, charlie_leaf)
+            } else {
+                (charlie_leaf, bob_leaf)
+            };
+            input[..32].copy_from_slice(first.as_ref());
+            input[32..].copy_from_slice(second.as_ref());
+            let mut root = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&input, &mut root);
+            let root = Hash::from(root);
+
+            // No root set yet
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.buy_ticket_with_proof(event_id, vec![charlie_leaf]),
+                Err(Error::InvalidProof)
+            );
+
+            // Only the organizer sets the root
+            assert_eq!(contract.set_allowlist_root(event_id, Some(root)), Err(Error::NotOrganizer));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.set_allowlist_root(event_id, Some(root)).unwrap();
+            assert_eq!(contract.get_allowlist_root(event_id), Some(root));
+
+            // Members buy with a proof, others can't
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::NotAllowlisted));
+            contract.buy_ticket_with_proof(event_id, vec![charlie_leaf]).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(
+                contract.buy_ticket_with_proof(event_id, vec![charlie_leaf]),
+                Err(Error::InvalidProof)
+            );
+        }
+
+        #[ink::test]
+        fn price_phases_work() {
+            let mut contract = TicketDot::new();
+
+            // Phases must be strictly ordered
+            let unordered = vec![
+                PricePhase { start: 100, price: 1500 },
+                PricePhase { start: 100, price: 2000 },
+            ];
+            assert_eq!(
+                contract.create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { price_phases: unordered, ..Default::default() },
+                ),
+                Err(Error::InvalidInput)
+            );
+
+            // Early-bird until 100, regular until 200, then last-minute
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(50);
+            let phases = vec![
+                PricePhase { start: 100, price: 1500 },
+                PricePhase { start: 200, price: 2000 },
+            ];
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { price_phases: phases, ..Default::default() },
+                )
+                .unwrap();
+            assert_eq!(contract.get_current_price(event_id), Some(1000));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(150);
+            assert_eq!(contract.get_current_price(event_id), Some(1500));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(
+                contract.buy_ticket(event_id),
+                Err(Error::InsufficientPayment { expected: 1500, got: 1000 })
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1500);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().price_paid, 1500);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(200);
+            assert_eq!(contract.get_current_price(event_id), Some(2000));
+        }
+
+        #[ink::test]
+        fn dutch_auction_pricing_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            // Floor must be below the start price
+            let invalid = DutchAuction {
+                start_price: 1000,
+                floor_price: 1000,
+                start: 0,
+                duration: 100,
+                step: 0,
+            };
+            assert_eq!(
+                contract.create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { dutch_auction: Some(invalid), ..Default::default() },
+                ),
+                Err(Error::InvalidInput)
+            );
+
+            // 2000 falling to 1000 in steps of 10 over 100
+            let auction = DutchAuction {
+                start_price: 2000,
+                floor_price: 1000,
+                start: 0,
+                duration: 100,
+                step: 10,
+            };
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { dutch_auction: Some(auction), ..Default::default() },
+                )
+                .unwrap();
+            assert_eq!(contract.get_current_price(event_id), Some(2000));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(55);
+            assert_eq!(contract.get_current_price(event_id), Some(1500));
+
+            // Overpayment is refunded at the clearing price
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1400);
+            assert_eq!(
+                contract.buy_ticket(event_id),
+                Err(Error::InsufficientPayment { expected: 1500, got: 1400 })
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2000);
+            let balance_before =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob)
+                    .unwrap();
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().price_paid, 1500);
+            let balance_after =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob)
+                    .unwrap();
+            assert_eq!(balance_after - balance_before, 500);
+
+            // Never drops below the floor
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(500);
+            assert_eq!(contract.get_current_price(event_id), Some(1000));
+        }
+
+        #[ink::test]
+        fn raffle_allocation_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            // Two tickets, raffle entries open until 100
+            let seed = Hash::from([7; 32]);
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    2,
+                    String::from("QmTest123"),
+                    EventOptions {
+                        raffle_end: 100,
+                        raffle_seed_hash: Some(raffle_seed_hash(&seed)),
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::RaffleOnly));
+            let entrants = [accounts.bob, accounts.charlie, accounts.django];
+            for entrant in entrants {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(entrant);
+                contract.enter_raffle(event_id).unwrap();
+            }
+            assert_eq!(contract.enter_raffle(event_id), Err(Error::AlreadyEntered));
+            assert_eq!(contract.claim_raffle_ticket(event_id), Err(Error::RaffleNotDrawn));
+            assert_eq!(contract.draw_raffle(event_id, seed), Err(Error::RaffleStillOpen));
+
+            // Entries close and winners are drawn once, with the committed seed
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(contract.enter_raffle(event_id), Err(Error::RaffleClosed));
+            assert_eq!(
+                contract.draw_raffle(event_id, Hash::from([8; 32])),
+                Err(Error::RaffleSeedMismatch)
+            );
+            contract.draw_raffle(event_id, seed).unwrap();
+            assert_eq!(contract.draw_raffle(event_id, seed), Err(Error::RaffleAlreadyDrawn));
+            assert_eq!(contract.get_raffle(event_id).unwrap().winners, 2);
+
+            // The winners' tickets are held back from the organizer's giveaways
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.airdrop_tickets(event_id, vec![accounts.eve]), Err(Error::SoldOut));
+
+            // Two winners claim tickets, the other entrant gets a refund
+            let mut tickets = 0;
+            let mut refunds = 0;
+            for entrant in entrants {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(entrant);
+                if contract.is_raffle_winner(event_id, entrant) {
+                    assert_eq!(
+                        contract.claim_raffle_refund(event_id),
+                        Err(Error::RaffleWinnerMustClaim)
+                    );
+                    let ticket_id = contract.claim_raffle_ticket(event_id).unwrap();
+                    assert_eq!(contract.get_ticket(ticket_id).unwrap().owner, entrant);
+                    tickets += 1;
+                } else {
+                    assert_eq!(contract.claim_raffle_ticket(event_id), Err(Error::NotRaffleWinner));
+                    assert_eq!(contract.claim_raffle_refund(event_id), Ok(1000));
+                    refunds += 1;
+                }
+                assert_eq!(contract.claim_raffle_refund(event_id), Err(Error::NotRaffleEntrant));
+            }
+            assert_eq!((tickets, refunds), (2, 1));
+            assert_eq!(contract.get_event(event_id).unwrap().available_tickets, 0);
+        }
+
+        #[ink::test]
+        fn raffle_claims_need_an_open_unfrozen_event() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let seed = Hash::from([7; 32]);
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    2,
+                    String::from("QmTest123"),
+                    EventOptions {
+                        raffle_end: 100,
+                        raffle_seed_hash: Some(raffle_seed_hash(&seed)),
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            for entrant in [accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(entrant);
+                contract.enter_raffle(event_id).unwrap();
+            }
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(100);
+            contract.draw_raffle(event_id, seed).unwrap();
+
+            // Winners can't claim while sales are paused or the event is frozen
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.pause_sales(event_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.claim_raffle_ticket(event_id), Err(Error::SalesPaused));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.resume_sales(event_id).unwrap();
+            contract.freeze_event(event_id, true).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.claim_raffle_ticket(event_id), Err(Error::EventFrozen));
+
+            // A winner who can't claim can take the refund instead
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.claim_raffle_refund(event_id), Ok(1000));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.freeze_event(event_id, false).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let ticket_id = contract.claim_raffle_ticket(event_id).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().owner, accounts.bob);
+        }
+
+        #[ink::test]
+        fn waitlist_offers_work() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 1, String::from("QmTest123"))
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.join_waitlist(event_id), Err(Error::NotSoldOut));
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            // Charlie then Django queue up
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.join_waitlist(event_id), Ok(0));
+            assert_eq!(contract.join_waitlist(event_id), Err(Error::AlreadyWaitlisted));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.join_waitlist(event_id), Ok(1));
+
+            // Bob's cancellation is reserved for Charlie
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.cancel_ticket(ticket_id).unwrap();
+            let expires_at = contract.get_waitlist_offer(event_id, accounts.charlie).unwrap();
+            assert!(!contract.is_waitlisted(event_id, accounts.charlie));
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::SoldOut));
+
+            // Charlie lets it expire and it moves on to Django
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(
+                contract.expire_waitlist_offer(event_id, accounts.charlie),
+                Err(Error::WaitlistOfferActive)
+            );
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(expires_at);
+            contract.expire_waitlist_offer(event_id, accounts.charlie).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.claim_waitlist_offer(event_id), Err(Error::NoWaitlistOffer));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            let ticket_id = contract.claim_waitlist_offer(event_id).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().owner, accounts.django);
+            assert_eq!(contract.get_waitlist_offer(event_id, accounts.django), None);
+        }
+
+        #[ink::test]
+        fn rejoining_the_waitlist_goes_to_the_back() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 1, String::from("QmTest123"))
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            // Charlie queues first, then leaves and rejoins behind Django
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.leave_waitlist(event_id), Err(Error::NotWaitlisted));
+            assert_eq!(contract.join_waitlist(event_id), Ok(0));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.join_waitlist(event_id), Ok(1));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.leave_waitlist(event_id).unwrap();
+            assert!(!contract.is_waitlisted(event_id, accounts.charlie));
+            assert_eq!(contract.join_waitlist(event_id), Ok(2));
+
+            // The returned ticket is offered to Django, who is now ahead
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.cancel_ticket(ticket_id).unwrap();
+            assert!(contract.get_waitlist_offer(event_id, accounts.django).is_some());
+            assert_eq!(contract.get_waitlist_offer(event_id, accounts.charlie), None);
+            assert!(contract.is_waitlisted(event_id, accounts.charlie));
+        }
+
+        #[ink::test]
+        fn promo_codes_work() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            let code_hash = promo_code_hash("EARLY25");
+
+            // Only the organizer registers codes
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.add_promo_code(event_id, code_hash, 2500, 1), Err(Error::NotOrganizer));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.add_promo_code(event_id, code_hash, 10_001, 1), Err(Error::InvalidInput));
+            contract.add_promo_code(event_id, code_hash, 2500, 1).unwrap();
+
+            // 25% off the base price
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(750);
+            assert_eq!(
+                contract.buy_ticket_with_code(event_id, String::from("LATE25")),
+                Err(Error::PromoCodeNotFound)
+            );
+            let ticket_id = contract.buy_ticket_with_code(event_id, String::from("EARLY25")).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().price_paid, 750);
+
+            // Uses are capped and recorded
+            assert_eq!(
+                contract.buy_ticket_with_code(event_id, String::from("EARLY25")),
+                Err(Error::PromoCodeExhausted)
+            );
+            let promo = contract.get_promo_code(event_id, code_hash).unwrap();
+            assert_eq!((promo.remaining_uses, promo.times_used, promo.total_discount), (0, 1, 250));
+        }
+
+        #[ink::test]
+        fn referral_rewards_work() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            assert_eq!(contract.set_referral_reward_bps(event_id, 2_001), Err(Error::InvalidInput));
+            contract.set_referral_reward_bps(event_id, 1_000).unwrap();
+
+            // Event must opt in
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(
+                contract.buy_ticket_with_referrer(event_id, accounts.charlie),
+                Err(Error::ReferralsDisabled)
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.set_referrals_enabled(event_id, true).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.buy_ticket_with_referrer(event_id, accounts.bob),
+                Err(Error::SelfReferral)
+            );
+            contract.buy_ticket_with_referrer(event_id, accounts.charlie).unwrap();
+
+            // Charlie earned 10% of the sale, taken from the organizer's escrow
+            assert_eq!(contract.get_referral_rewards(accounts.charlie, None), 100);
+            assert_eq!(contract.escrow_balance.get(event_id), Some(900));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.claim_referral_rewards(), Ok(100));
+            assert_eq!(contract.claim_referral_rewards(), Err(Error::NothingToWithdraw));
+        }
+
+        #[ink::test]
+        fn comp_tickets_work() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            assert_eq!(
+                contract.mint_comp_ticket(event_id, accounts.bob),
+                Err(Error::CompQuotaExceeded)
+            );
+            contract.set_comp_quota(event_id, 1).unwrap();
+
+            // Only the organizer issues comps
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.mint_comp_ticket(event_id, accounts.bob), Err(Error::NotOrganizer));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            let ticket_id = contract.mint_comp_ticket(event_id, accounts.bob).unwrap();
+            let ticket = contract.get_ticket(ticket_id).unwrap();
+            assert!(ticket.is_comp);
+            assert_eq!((ticket.owner, ticket.price_paid), (accounts.bob, 0));
+            assert_eq!(contract.get_event(event_id).unwrap().available_tickets, 99);
+            assert_eq!(contract.escrow_balance.get(event_id).unwrap_or(0), 0);
+
+            assert_eq!(
+                contract.mint_comp_ticket(event_id, accounts.charlie),
+                Err(Error::CompQuotaExceeded)
+            );
+        }
+
+        #[ink::test]
+        fn airdrop_tickets_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 2, String::from("QmTest123"))
+                .unwrap();
+
+            // Only the organizer airdrops, within capacity
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.airdrop_tickets(event_id, vec![accounts.bob]),
+                Err(Error::NotOrganizer)
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.airdrop_tickets(event_id, Vec::new()), Err(Error::InvalidInput));
+
+            let ticket_ids = contract
+                .airdrop_tickets(event_id, vec![accounts.bob, accounts.charlie])
+                .unwrap();
+            assert_eq!(ticket_ids.len(), 2);
+            let ticket = contract.get_ticket(ticket_ids[1]).unwrap();
+            assert_eq!((ticket.owner, ticket.price_paid, ticket.is_comp), (accounts.charlie, 0, true));
+            assert_eq!(contract.get_event(event_id).unwrap().available_tickets, 0);
+
+            assert_eq!(
+                contract.airdrop_tickets(event_id, vec![accounts.django]),
+                Err(Error::SoldOut)
+            );
+        }
+
+        #[ink::test]
+        fn redeem_voucher_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            // Organizer account derived from a known ECDSA key
+            let organizer = AccountId::from([
+                235, 10, 159, 8, 3, 251, 202, 78, 186, 3, 112, 207, 26, 137, 124, 54, 103, 236,
+                94, 35, 72, 115, 133, 71, 60, 173, 135, 229, 123, 62, 158, 35,
+            ]);
+            // Organizer's signature over (contract, event 0, nonce 7, bob)
+            let signature = [
+                188, 142, 205, 145, 223, 208, 112, 107, 121, 146, 187, 10, 41, 30, 139, 189, 84,
+                66, 71, 119, 137, 169, 130, 169, 85, 149, 248, 135, 175, 1, 232, 123, 112, 107,
+                44, 146, 163, 0, 157, 88, 236, 170, 156, 29, 34, 221, 78, 151, 87, 178, 78, 139,
+                21, 193, 1, 117, 3, 165, 78, 82, 239, 136, 211, 102, 0,
+            ];
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(AccountId::from([0xc0; 32]));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(organizer);
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            assert_eq!(event_id, 0);
+
+            // Wrong nonce doesn't match the signature
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.redeem_voucher(event_id, 8, signature), Err(Error::InvalidSignature));
+
+            // Nobody but the recipient can redeem it
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                contract.redeem_voucher(event_id, 7, signature),
+                Err(Error::InvalidSignature)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let ticket_id = contract.redeem_voucher(event_id, 7, signature).unwrap();
+            let ticket = contract.get_ticket(ticket_id).unwrap();
+            assert_eq!((ticket.owner, ticket.price_paid), (accounts.bob, 0));
+            assert!(contract.is_voucher_redeemed(event_id, 7));
+
+            // Each voucher works once
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                contract.redeem_voucher(event_id, 7, signature),
+                Err(Error::VoucherAlreadyRedeemed)
+            );
+        }
+
+        #[ink::test]
+        fn checkin_staff_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            let other_event = contract
+                .create_event(String::from("Other Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            let other_ticket = contract.buy_ticket(other_event).unwrap();
+
+            // Only the organizer appoints staff
+            assert_eq!(
+                contract.add_checkin_staff(event_id, accounts.charlie),
+                Err(Error::NotOrganizer)
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.add_checkin_staff(event_id, accounts.charlie).unwrap();
+            assert!(contract.is_checkin_staff(event_id, accounts.charlie));
+
+            // Staff can check in tickets for their event only
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.use_ticket(other_ticket), Err(Error::NotTicketOwner));
+            contract.use_ticket(ticket_id).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.remove_checkin_staff(event_id, accounts.charlie).unwrap();
+            assert!(!contract.is_checkin_staff(event_id, accounts.charlie));
+        }
+
+        #[ink::test]
+        fn verify_checkin_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            // Holder account derived from a known ECDSA key
+            let holder = AccountId::from([
+                235, 10, 159, 8, 3, 251, 202, 78, 186, 3, 112, 207, 26, 137, 124, 54, 103, 236,
+                94, 35, 72, 115, 133, 71, 60, 173, 135, 229, 123, 62, 158, 35,
+            ]);
+            // Holder's signature over (contract, ticket 0, challenge)
+            let challenge = [9u8; 32];
+            let signature = [
+                22, 198, 42, 129, 39, 197, 184, 95, 35, 169, 194, 41, 46, 242, 9, 15, 73, 139,
+                206, 226, 59, 235, 7, 238, 26, 178, 93, 171, 64, 22, 211, 42, 102, 94, 168, 195,
+                225, 172, 33, 17, 163, 240, 154, 112, 193, 222, 238, 39, 212, 36, 140, 140, 94,
+                198, 88, 128, 250, 82, 121, 8, 111, 251, 108, 109, 0,
+            ];
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(AccountId::from([0xc0; 32]));
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(holder);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            assert_eq!(ticket_id, 0);
+
+            // The scanner needs check-in rights
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.verify_checkin(ticket_id, challenge, signature),
+                Err(Error::NotTicketOwner)
+            );
+
+            // A signature over a different challenge is rejected
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                contract.verify_checkin(ticket_id, [8u8; 32], signature),
+                Err(Error::InvalidSignature)
+            );
+
+            contract.verify_checkin(ticket_id, challenge, signature).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().uses_remaining, 0);
+        }
+
+        #[ink::test]
+        fn multi_entry_tickets_work() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            // Three-day pass
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { uses_per_ticket: 3, ..Default::default() },
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().uses_remaining, 3);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            for remaining in [2, 1, 0] {
+                contract.use_ticket(ticket_id).unwrap();
+                let ticket = contract.get_ticket(ticket_id).unwrap();
+                assert_eq!(ticket.uses_remaining, remaining);
+            }
+            assert_eq!(contract.use_ticket(ticket_id), Err(Error::TicketAlreadyUsed));
+
+            // Re-entry counts once in the attendance history
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().times_used, 3);
+            assert_eq!(contract.get_attendance_count(accounts.bob), 1);
+        }
+
+        #[ink::test]
+        fn attendance_nft_minted_on_checkin() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let first = contract.buy_ticket(event_id).unwrap();
+            let second = contract.buy_ticket(event_id).unwrap();
+
+            // No NFT until the organizer enables it
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.use_ticket(first).unwrap();
+            assert_eq!(contract.get_attendance_nft(event_id, accounts.bob), None);
+
+            // Enabling only matters for holders who haven't attended yet
+            contract.set_attendance_nft(event_id, Some(String::from("QmPoap"))).unwrap();
+            contract.use_ticket(second).unwrap();
+            assert_eq!(contract.get_attendance_nft(event_id, accounts.bob), None);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            let third = contract.buy_ticket(event_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.use_ticket(third).unwrap();
+            let token = contract.get_attendance_nft(event_id, accounts.charlie).unwrap();
+            assert_eq!((token.id, token.ticket_id), (0, third));
+            assert_eq!(token.metadata_cid, String::from("QmPoap"));
+        }
+
+        #[ink::test]
+        fn upgrade_ticket_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 10, String::from("QmTest123"))
+                .unwrap();
+            let vip = contract
+                .add_ticket_tier(event_id, String::from("VIP"), 5000, 1, String::from("QmVip"), 2)
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().available_tickets, 9);
+
+            // The full difference must be paid
+            assert_eq!(
+                contract.upgrade_ticket(ticket_id, vip),
+                Err(Error::InsufficientPayment { expected: 4000, got: 1000 })
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(4000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.upgrade_ticket(ticket_id, vip), Err(Error::NotTicketOwner));
+
+            // Paused or frozen events don't sell upgrades
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.pause_sales(event_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.upgrade_ticket(ticket_id, vip), Err(Error::SalesPaused));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.resume_sales(event_id).unwrap();
+            contract.freeze_event(event_id, true).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.upgrade_ticket(ticket_id, vip), Err(Error::EventFrozen));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.freeze_event(event_id, false).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.upgrade_ticket(ticket_id, vip).unwrap();
+
+            let ticket = contract.get_ticket(ticket_id).unwrap();
+            assert_eq!((ticket.tier_id, ticket.price_paid, ticket.uses_remaining), (Some(vip), 5000, 2));
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!((event.available_tickets, event.tiers[0].available), (9, 0));
+            assert_eq!(contract.escrow_balance.get(event_id), Some(5000));
+
+            // The tier is now full
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let other = contract.buy_ticket(event_id).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(4000);
+            assert_eq!(contract.upgrade_ticket(other, vip), Err(Error::SoldOut));
+        }
+
+        #[ink::test]
+        fn upgrades_charge_face_value_and_keep_check_ins() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    10,
+                    String::from("QmTest123"),
+                    EventOptions { uses_per_ticket: 2, ..Default::default() },
+                )
+                .unwrap();
+            let vip = contract
+                .add_ticket_tier(event_id, String::from("VIP"), 5000, 2, String::from("QmVip"), 3)
+                .unwrap();
+            contract.set_comp_quota(event_id, 1).unwrap();
+
+            // A comp ticket pays the full face value difference and stops being a comp
+            let comp = contract.mint_comp_ticket(event_id, accounts.bob).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(4000);
+            contract.upgrade_ticket(comp, vip).unwrap();
+            let ticket = contract.get_ticket(comp).unwrap();
+            assert_eq!((ticket.price_paid, ticket.is_comp), (4000, false));
+
+            // Check-ins already made carry over to the new tier
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.use_ticket(ticket_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(4000);
+            contract.upgrade_ticket(ticket_id, vip).unwrap();
+            let ticket = contract.get_ticket(ticket_id).unwrap();
+            assert_eq!((ticket.times_used, ticket.uses_remaining), (1, 2));
+        }
+
+        #[ink::test]
+        fn transfer_tickets_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let first = contract.buy_ticket(event_id).unwrap();
+            let second = contract.buy_ticket(event_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let bobs = contract.buy_ticket(event_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            // Nothing moves if any ticket isn't the caller's
+            assert_eq!(
+                contract.transfer_tickets(vec![first, bobs], accounts.charlie),
+                Err(Error::NotTicketOwner)
+            );
+            assert_eq!(
+                contract.transfer_tickets(vec![first, first], accounts.charlie),
+                Err(Error::InvalidInput)
+            );
+            assert_eq!(contract.get_ticket(first).unwrap().owner, accounts.alice);
+
+            contract.transfer_tickets(vec![first, second], accounts.charlie).unwrap();
+            assert_eq!(contract.get_my_tickets(accounts.charlie), vec![first, second]);
+            assert!(contract.get_my_tickets(accounts.alice).is_empty());
+        }
+
+        #[ink::test]
+        fn transfer_ticket_by_operator_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let first = contract.buy_ticket(event_id).unwrap();
+            let second = contract.buy_ticket(event_id).unwrap();
+
+            // Single-ticket approval
+            assert_eq!(contract.approve_ticket(first, Some(accounts.bob)), Err(Error::SelfApproval));
+            contract.approve_ticket(first, Some(accounts.charlie)).unwrap();
+            assert_eq!(contract.get_approved(first), Some(accounts.charlie));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                contract.transfer_ticket(second, accounts.django),
+                Err(Error::NotTicketOwner)
+            );
+            contract.transfer_ticket(first, accounts.django).unwrap();
+            assert_eq!(contract.get_ticket(first).unwrap().owner, accounts.django);
+            assert_eq!(contract.get_approved(first), None);
+
+            // Operator for all of Bob's tickets
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.set_approval_for_all(accounts.eve, true).unwrap();
+            assert!(contract.is_approved_for_all(accounts.bob, accounts.eve));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            contract.transfer_ticket(second, accounts.django).unwrap();
+            assert_eq!(contract.get_ticket(second).unwrap().owner, accounts.django);
+        }
+
+        #[ink::test]
+        fn transfer_with_permit_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            // Owner account derived from a known ECDSA key
+            let owner = AccountId::from([
+                235, 10, 159, 8, 3, 251, 202, 78, 186, 3, 112, 207, 26, 137, 124, 54, 103, 236,
+                94, 35, 72, 115, 133, 71, 60, 173, 135, 229, 123, 62, 158, 35,
+            ]);
+            // Owner's permit for (contract, ticket 0, Bob, deadline 1000, nonce 0)
+            let signature = [
+                91, 196, 120, 236, 153, 63, 91, 244, 244, 91, 88, 87, 109, 155, 85, 95, 136, 225,
+                29, 39, 109, 174, 42, 12, 172, 19, 6, 75, 21, 78, 241, 117, 28, 255, 31, 157, 234,
+                231, 135, 40, 99, 204, 119, 68, 219, 53, 136, 164, 120, 13, 225, 130, 37, 77, 12,
+                211, 228, 147, 227, 0, 183, 223, 130, 106, 1,
+            ];
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(AccountId::from([0xc0; 32]));
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(owner);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            assert_eq!(ticket_id, 0);
+
+            // A relayer submits; the permit only covers Bob as recipient
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(
+                contract.transfer_with_permit(ticket_id, accounts.charlie, 1000, signature),
+                Err(Error::InvalidSignature)
+            );
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1001);
+            assert_eq!(
+                contract.transfer_with_permit(ticket_id, accounts.bob, 1000, signature),
+                Err(Error::PermitExpired)
+            );
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            contract.transfer_with_permit(ticket_id, accounts.bob, 1000, signature).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().owner, accounts.bob);
+            assert_eq!(contract.get_permit_nonce(owner), 1);
+        }
+
+        #[ink::test]
+        fn reschedule_refund_window_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let first = contract.buy_ticket(event_id).unwrap();
+            let second = contract.buy_ticket(event_id).unwrap();
+            assert_eq!(contract.refund_ticket(first), Err(Error::EventNotActive));
+
+            // Only the organizer reschedules, to a valid window
+            assert_eq!(contract.reschedule_event(event_id, 100, 200), Err(Error::NotOrganizer));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.reschedule_event(event_id, 200, 100), Err(Error::InvalidInput));
+            contract.reschedule_event(event_id, 100, 200).unwrap();
+            let schedule = contract.get_event(event_id).unwrap().schedule;
+            assert_eq!((schedule.event_start, schedule.event_end), (100, 200));
+
+            // Bob opts out within the window and the ticket goes back on sale
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.refund_ticket(first).unwrap();
+            assert!(contract.get_ticket(first).unwrap().is_refunded);
+            assert_eq!(contract.get_event(event_id).unwrap().available_tickets, 99);
+
+            let deadline = contract.get_reschedule_refund_deadline(event_id).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(deadline);
+            assert_eq!(contract.refund_ticket(second), Err(Error::EventNotActive));
+        }
+
+        #[ink::test]
+        fn update_capacity_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 10, String::from("QmTest123"))
+                .unwrap();
+            contract
+                .add_ticket_tier(event_id, String::from("VIP"), 5000, 2, String::from("QmVip"), 1)
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(3000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.buy_tickets(event_id, 3).unwrap();
+            assert_eq!(contract.update_capacity(event_id, 20), Err(Error::NotOrganizer));
+
+            // Growing adds unsold tickets
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.update_capacity(event_id, 20).unwrap();
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!((event.total_tickets, event.available_tickets), (20, 17));
+
+            // Shrinking keeps 3 sold and 2 VIP tickets
+            assert_eq!(contract.update_capacity(event_id, 4), Err(Error::InvalidInput));
+            contract.update_capacity(event_id, 5).unwrap();
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!((event.total_tickets, event.available_tickets), (5, 2));
+        }
+
+        #[ink::test]
+        fn update_event_metadata_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Tset Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            assert_eq!(
+                contract.update_event_metadata(event_id, String::new(), String::from("QmNew")),
+                Err(Error::InvalidInput)
+            );
+            contract
+                .update_event_metadata(event_id, String::from("Test Event"), String::from("QmNew"))
+                .unwrap();
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.name, String::from("Test Event"));
+            assert_eq!(event.metadata_cid, String::from("QmNew"));
+
+            // Locked once tickets are sold
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.buy_ticket(event_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                contract.update_event_metadata(event_id, String::from("Other"), String::from("QmNew")),
+                Err(Error::InvalidInput)
+            );
+        }
+
+        #[ink::test]
+        fn refund_policy_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let day = 24 * 60 * 60 * 1000;
+
+            // 100% until 7 days before, 50% until 1 day before, then nothing
+            let policy = vec![
+                RefundRule { min_time_before_start: 7 * day, refund_bps: 10_000 },
+                RefundRule { min_time_before_start: day, refund_bps: 5_000 },
+            ];
+            let options = EventOptions {
+                schedule: EventSchedule { event_start: 10 * day, ..Default::default() },
+                refund_policy: policy,
+                ..Default::default()
+            };
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    options,
+                )
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let first = contract.buy_ticket(event_id).unwrap();
+            let second = contract.buy_ticket(event_id).unwrap();
+            let third = contract.buy_ticket(event_id).unwrap();
+            assert_eq!(contract.compute_refund(first), Some(1000));
+
+            // 5 days before: half back, half stays in escrow
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5 * day);
+            assert_eq!(contract.compute_refund(second), Some(500));
+            contract.cancel_ticket(second).unwrap();
+            assert_eq!(contract.escrow_balance.get(event_id), Some(2500));
+            assert_eq!(contract.compute_refund(second), None);
+
+            // Under a day before: nothing back
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(9 * day + 1);
+            assert_eq!(contract.compute_refund(third), Some(0));
+        }
+
+        #[ink::test]
+        fn sweep_unclaimed_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            contract.set_treasury(accounts.eve).unwrap();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let claimed = contract.buy_ticket(event_id).unwrap();
+            let unclaimed = contract.buy_ticket(event_id).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.cancel_event(event_id).unwrap();
+            assert_eq!(contract.get_refund_deadline(event_id), Some(REFUND_CLAIM_PERIOD));
+
+            // Nothing can be swept while refunds are claimable
+            assert_eq!(contract.sweep_unclaimed(event_id), Err(Error::RefundPeriodOpen));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.refund_ticket(claimed).unwrap();
+
+            // After the deadline refunds stop and the rest goes to the treasury
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(REFUND_CLAIM_PERIOD);
+            assert_eq!(contract.refund_ticket(unclaimed), Err(Error::RefundPeriodEnded));
+            assert_eq!(contract.sweep_unclaimed(event_id), Err(Error::NotOrganizer));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.sweep_unclaimed(event_id), Ok(1000));
+            assert_eq!(contract.escrow_balance.get(event_id), Some(0));
+            assert_eq!(contract.sweep_unclaimed(event_id), Err(Error::NothingToWithdraw));
+        }
+
+        #[ink::test]
+        fn refund_batch_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let first = contract.buy_ticket(event_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            let second = contract.buy_ticket(event_id).unwrap();
+
+            // Only cancelled events can be refunded in bulk
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.refund_batch(event_id, vec![first]), Err(Error::EventNotActive));
+            contract.cancel_event(event_id).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.refund_batch(event_id, vec![first]), Err(Error::NotOrganizer));
+
+            // Already refunded tickets are skipped
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.refund_batch(event_id, vec![first]), Ok(1));
+            assert_eq!(contract.refund_batch(event_id, vec![first, second]), Ok(1));
+            assert!(contract.get_ticket(second).unwrap().is_refunded);
+            assert_eq!(contract.get_my_tickets(accounts.charlie), Vec::<u64>::new());
+            assert_eq!(contract.escrow_balance.get(event_id), Some(0));
+        }
+
+        #[ink::test]
+        fn freeze_and_force_cancel_work() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            // Bob organizes, Charlie moderates
+            contract.grant_role(Role::Moderator, accounts.charlie).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            // Only moderators can freeze
+            assert_eq!(contract.freeze_event(event_id, true), Err(Error::MissingRole));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.freeze_event(event_id, true).unwrap();
+
+            // Frozen events can't sell or check in
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::EventFrozen));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.use_ticket(ticket_id), Err(Error::EventFrozen));
+
+            // Force-cancelling opens refunds for holders
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.force_cancel_event(event_id).unwrap();
+            assert!(contract.get_event(event_id).unwrap().is_cancelled());
+            assert_eq!(contract.force_cancel_event(event_id), Err(Error::EventCancelled));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            contract.refund_ticket(ticket_id).unwrap();
+            assert_eq!(contract.escrow_balance.get(event_id), Some(0));
+        }
+
+        #[ink::test]
+        fn event_bond_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            contract.set_event_bond(500, 4000).unwrap();
+
+            // The bond must be attached exactly
+            let create = |contract: &mut TicketDot| {
+                contract.create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+            };
+            assert_eq!(
+                create(&mut contract),
+                Err(Error::InsufficientPayment { expected: 500, got: 0 })
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(500);
+            let completed = create(&mut contract).unwrap();
+            let cancelled = create(&mut contract).unwrap();
+            let forced = create(&mut contract).unwrap();
+            assert_eq!(contract.get_event_bond(completed), 500);
+
+            // Completing returns it all
+            contract.complete_event(completed).unwrap();
+            assert_eq!(contract.get_event_bond(completed), 0);
+
+            // Cancelling slashes 40% into the refund pool
+            contract.cancel_event(cancelled).unwrap();
+            assert_eq!(contract.get_event_bond(cancelled), 0);
+            assert_eq!(contract.escrow_balance.get(cancelled), Some(200));
+
+            // Force-cancelling slashes it all
+            contract.grant_role(Role::Moderator, accounts.charlie).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.force_cancel_event(forced).unwrap();
+            assert_eq!(contract.escrow_balance.get(forced), Some(500));
+        }
+
+        #[ink::test]
+        fn dispute_window_gates_withdrawal() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let day = 24 * 60 * 60 * 1000;
+            contract.set_dispute_policy(3 * day, 2_000).unwrap();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let first = contract.buy_ticket(event_id).unwrap();
+            let second = contract.buy_ticket(event_id).unwrap();
+            contract.buy_ticket(event_id).unwrap();
+            contract.buy_ticket(event_id).unwrap();
+            contract.buy_ticket(event_id).unwrap();
+
+            // Disputes only open once the event is completed
+            assert_eq!(
+                contract.file_dispute(first, String::from("QmReason")),
+                Err(Error::EventNotCompleted)
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.complete_event(event_id).unwrap();
+            assert_eq!(contract.get_withdrawal_unlock(event_id), Some(3 * day));
+            assert_eq!(contract.withdraw_earnings(event_id), Err(Error::WithdrawalLocked));
+
+            // One dispute in five tickets is within the 20% threshold, two aren't
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.file_dispute(first, String::from("QmReason")).unwrap();
+            assert_eq!(
+                contract.file_dispute(first, String::from("QmReason")),
+                Err(Error::AlreadyDisputed)
+            );
+            contract.file_dispute(second, String::from("QmReason")).unwrap();
+            assert_eq!(contract.get_open_disputes(event_id), 2);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(3 * day);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.withdraw_earnings(event_id), Err(Error::TooManyDisputes));
+
+            // No disputes after the window
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.file_dispute(first + 2, String::from("QmReason")),
+                Err(Error::DisputeWindowClosed)
+            );
+        }
+
+        #[ink::test]
+        fn dispute_arbitration_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let upheld = contract.buy_ticket(event_id).unwrap();
+            let dismissed = contract.buy_ticket(event_id).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.complete_event(event_id).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let first = contract.file_dispute(upheld, String::from("QmNoShow")).unwrap();
+            let second = contract.file_dispute(dismissed, String::from("QmNoShow")).unwrap();
+            assert_eq!(contract.get_ticket_dispute(upheld), Some(first));
+            assert_eq!(contract.get_dispute(first).unwrap().status, DisputeStatus::Open);
+
+            // Only admins arbitrate
+            assert_eq!(contract.resolve_dispute(first, true), Err(Error::MissingRole));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            // Upheld disputes refund from escrow, dismissed ones don't
+            contract.resolve_dispute(first, true).unwrap();
+            contract.resolve_dispute(second, false).unwrap();
+            assert_eq!(contract.get_dispute(first).unwrap().status, DisputeStatus::Refunded);
+            assert_eq!(contract.get_dispute(second).unwrap().status, DisputeStatus::Dismissed);
+            assert!(contract.get_ticket(upheld).unwrap().is_refunded);
+            assert_eq!(contract.escrow_balance.get(event_id), Some(1000));
+            assert_eq!(contract.get_open_disputes(event_id), 0);
+            assert_eq!(contract.resolve_dispute(second, true), Err(Error::DisputeNotOpen));
+        }
+
+        #[ink::test]
+        fn payout_schedule_vests_earnings() {
+            let mut contract = TicketDot::new();
+            let day = 24 * 60 * 60 * 1000;
+            contract.set_dispute_policy(0, 0).unwrap();
+
+            // Half at completion, half two weeks later
+            let options = EventOptions {
+                payout_schedule: vec![
+                    PayoutTranche { delay: 0, share_bps: 5_000 },
+                    PayoutTranche { delay: 14 * day, share_bps: 5_000 },
+                ],
+                ..Default::default()
+            };
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    options,
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            contract.buy_ticket(event_id).unwrap();
+            contract.buy_ticket(event_id).unwrap();
+            contract.complete_event(event_id).unwrap();
+
+            assert_eq!(contract.get_vested_earnings(event_id), 1000);
+            contract.withdraw_earnings(event_id).unwrap();
+            assert_eq!(contract.get_total_withdrawn(event_id), 1000);
+            assert_eq!(contract.withdraw_earnings(event_id), Err(Error::NothingToWithdraw));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(14 * day);
+            contract.withdraw_earnings(event_id).unwrap();
+            assert_eq!(contract.get_total_withdrawn(event_id), 2000);
+            assert_eq!(contract.escrow_balance.get(event_id), Some(0));
+        }
+
+        #[ink::test]
+        fn payout_split_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            contract.set_dispute_policy(0, 0).unwrap();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            // Venue gets 30%, artist 20%, the organizer keeps 50%
+            let share = |payee, share_bps| PayoutShare { payee, share_bps };
+            let too_much = vec![share(accounts.bob, 6_000), share(accounts.charlie, 5_000)];
+            assert_eq!(contract.set_payout_split(event_id, too_much), Err(Error::InvalidInput));
+            let split = vec![share(accounts.bob, 3_000), share(accounts.charlie, 2_000)];
+            contract.set_payout_split(event_id, split).unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            contract.buy_ticket(event_id).unwrap();
+            contract.buy_ticket(event_id).unwrap();
+            contract.complete_event(event_id).unwrap();
+
+            // The organizer's withdrawal releases everyone's share
+            contract.withdraw_earnings(event_id).unwrap();
+            assert_eq!(contract.get_pending_payout(event_id, accounts.alice), 0);
+            assert_eq!(contract.get_pending_payout(event_id, accounts.bob), 600);
+            assert_eq!(contract.get_pending_payout(event_id, accounts.charlie), 400);
+            assert_eq!(contract.set_payout_split(event_id, Vec::new()), Err(Error::PayoutsStarted));
+
+            // Payees pull independently; outsiders can't
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.withdraw_earnings(event_id).unwrap();
+            assert_eq!(contract.get_pending_payout(event_id, accounts.bob), 0);
+            assert_eq!(contract.withdraw_earnings(event_id), Err(Error::NothingToWithdraw));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.withdraw_earnings(event_id), Err(Error::NotOrganizer));
+        }
+
+        /// Decode every recorded `E` event, oldest first
+        fn recorded<E: ink::env::Event + scale::Decode>() -> Vec<E> {
+            let signature = E::SIGNATURE_TOPIC.map(|topic| topic.to_vec());
+            ink::env::test::recorded_events()
+                .filter(|event| event.topics.first() == signature.as_ref())
+                .map(|event| E::decode(&mut &event.data[..]).unwrap())
+                .collect()
+        }
+
+        #[ink::test]
+        fn indexer_events_are_recorded() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            contract.set_dispute_policy(0, 0).unwrap();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 10, String::from("QmTest123"))
+                .unwrap();
+            let vip = contract
+                .add_ticket_tier(event_id, String::from("VIP"), 5000, 1, String::from("QmVip"), 2)
+                .unwrap();
+            let other_id = contract
+                .create_event(String::from("Other Event"), 1000, 10, String::from("QmTest456"))
+                .unwrap();
+            let split = vec![PayoutShare {
+                payee: accounts.charlie,
+                share_bps: 5_000,
+            }];
+            contract.set_payout_split(event_id, split).unwrap();
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            let other_ticket = contract.buy_ticket(other_id).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(4000);
+            contract.upgrade_ticket(ticket_id, vip).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.use_ticket(ticket_id).unwrap();
+            contract.use_ticket(ticket_id).unwrap();
+            contract.complete_event(event_id).unwrap();
+            contract.withdraw_earnings(event_id).unwrap();
+            contract.cancel_event(other_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.refund_ticket(other_ticket).unwrap();
+
+            // Purchases carry what was bought and for how much
+            let purchases = recorded::<TicketPurchased>();
+            assert_eq!(purchases.len(), 2);
+            assert_eq!((purchases[0].ticket_id, purchases[0].buyer), (ticket_id, accounts.bob));
+            assert_eq!((purchases[0].tier_id, purchases[0].price_paid), (None, 1000));
+
+            // Every step of both tickets' lifecycles is reported
+            let states = recorded::<TicketStateChanged>()
+                .into_iter()
+                .map(|change| (change.ticket_id, change.event_id, change.state))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                states,
+                vec![
+                    (Some(ticket_id), event_id, TicketState::Issued),
+                    (Some(other_ticket), other_id, TicketState::Issued),
+                    (Some(ticket_id), event_id, TicketState::Upgraded),
+                    (Some(ticket_id), event_id, TicketState::PartlyUsed),
+                    (Some(ticket_id), event_id, TicketState::Used),
+                    (None, event_id, TicketState::Expired),
+                    (None, other_id, TicketState::EventCancelled),
+                    (Some(other_ticket), other_id, TicketState::Refunded),
+                ]
+            );
+
+            // Releasing earnings credits each payee
+            let payouts = recorded::<PayoutCredited>()
+                .into_iter()
+                .map(|credit| (credit.payee, credit.amount))
+                .collect::<Vec<_>>();
+            assert_eq!(payouts, vec![(accounts.charlie, 2500), (accounts.alice, 2500)]);
+        }
+
+        #[ink::test]
+        fn co_organizers_share_operations() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            // Only the organizer manages co-organizers
+            assert_eq!(contract.add_co_organizer(event_id, accounts.bob), Err(Error::NotOrganizer));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.add_co_organizer(event_id, accounts.bob).unwrap();
+            assert!(contract.is_co_organizer(event_id, accounts.bob));
+
+            // Co-organizers run the event but can't cancel it
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.add_checkin_staff(event_id, accounts.charlie).unwrap();
+            contract.use_ticket(ticket_id).unwrap();
+            assert_eq!(contract.cancel_event(event_id), Err(Error::NotOrganizer));
+            contract.complete_event(event_id).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.remove_co_organizer(event_id, accounts.bob).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.remove_checkin_staff(event_id, accounts.charlie),
+                Err(Error::NotOrganizer)
+            );
+        }
+
+        #[ink::test]
+        fn organizer_registry_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            contract.set_organizer_registration_required(true).unwrap();
+
+            let create = |contract: &mut TicketDot| {
+                contract.create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+            };
+
+            // Unregistered accounts can't create events
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(create(&mut contract), Err(Error::OrganizerNotRegistered));
+
+            let contact_hash = Hash::from([7; 32]);
+            contract
+                .register_organizer(String::from("Bob Live"), String::from("QmBob"), contact_hash)
+                .unwrap();
+            create(&mut contract).unwrap();
+
+            // Updates keep the registration time
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            contract
+                .register_organizer(String::from("Bob Live Music"), String::from("QmBob2"), contact_hash)
+                .unwrap();
+            let profile = contract.get_organizer(accounts.bob).unwrap();
+            assert_eq!(profile.display_name, String::from("Bob Live Music"));
+            assert_eq!(profile.registered_at, 0);
+        }
+
+        #[ink::test]
+        fn organizer_verification_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            contract.grant_role(Role::Moderator, accounts.charlie).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let contact_hash = Hash::from([7; 32]);
+            contract
+                .register_organizer(String::from("Bob Live"), String::from("QmBob"), contact_hash)
+                .unwrap();
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+
+            // Only moderators verify, and only registered organizers
+            assert_eq!(contract.verify_organizer(accounts.bob), Err(Error::MissingRole));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                contract.verify_organizer(accounts.django),
+                Err(Error::OrganizerNotRegistered)
+            );
+            contract.verify_organizer(accounts.bob).unwrap();
+
+            let (event, profile) = contract.get_event_with_organizer(event_id).unwrap();
+            assert_eq!(event.organizer, accounts.bob);
+            assert!(profile.unwrap().is_verified);
+
+            // Changing the profile drops the badge
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract
+                .register_organizer(String::from("Totally Bob"), String::from("QmBob"), contact_hash)
+                .unwrap();
+            assert!(!contract.is_verified_organizer(accounts.bob));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.verify_organizer(accounts.bob).unwrap();
+            contract.revoke_verification(accounts.bob).unwrap();
+            assert!(!contract.is_verified_organizer(accounts.bob));
+        }
+
+        #[ink::test]
+        fn organizer_ban_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let create = |contract: &mut TicketDot| {
+                contract.create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+            };
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let scam = create(&mut contract).unwrap();
+
+            // Only admins ban, and only the organizer's own events get frozen
+            assert_eq!(contract.ban_organizer(accounts.bob, vec![scam]), Err(Error::MissingRole));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let own = create(&mut contract).unwrap();
+            assert_eq!(contract.ban_organizer(accounts.bob, vec![own]), Err(Error::NotOrganizer));
+            contract.ban_organizer(accounts.bob, vec![scam]).unwrap();
+            assert!(contract.is_organizer_banned(accounts.bob));
+            assert!(contract.get_event(scam).unwrap().frozen);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(create(&mut contract), Err(Error::OrganizerBanned));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.unban_organizer(accounts.bob).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            create(&mut contract).unwrap();
+        }
+
+        #[ink::test]
+        fn blocked_attendees_are_refused() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let held = contract.buy_ticket(event_id).unwrap();
+            contract.buy_ticket(event_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            let gift = contract.buy_ticket(event_id).unwrap();
+
+            // Blocking refunds what the account already holds
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.block_attendee(event_id, accounts.bob), Ok(2));
+            assert!(contract.get_ticket(held).unwrap().is_refunded);
+            assert_eq!(contract.escrow_balance.get(event_id), Some(1000));
+
+            // No buying or receiving tickets afterwards
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::AttendeeBlocked));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.transfer_ticket(gift, accounts.bob), Err(Error::AttendeeBlocked));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.unblock_attendee(event_id, accounts.bob).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.transfer_ticket(gift, accounts.bob).unwrap();
+        }
+
+        #[ink::test]
+        fn token_gate_is_stored() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let create = |contract: &mut TicketDot, token_gate| {
+                let options = EventOptions {
+                    token_gate: Some(token_gate),
+                    ..Default::default()
+                };
+                contract.create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    options,
+                )
+            };
+
+            // A PSP22 gate needs a minimum balance
+            let empty_gate = TokenGate::Psp22 { token: accounts.eve, min_balance: 0 };
+            assert_eq!(create(&mut contract, empty_gate), Err(Error::InvalidInput));
+
+            let gate = TokenGate::Psp34 { collection: accounts.eve };
+            let event_id = create(&mut contract, gate).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().token_gate, Some(gate));
+        }
+
+        #[ink::test]
+        fn config_limits_are_tunable() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(contract.get_config(), Config::default());
+
+            let config = Config {
+                max_tickets_per_event: 50,
+                min_ticket_price: 100,
+                ..Default::default()
+            };
+
+            // Only admins, and only within hard bounds
+            let unbounded = Config {
+                max_tickets_per_event: MAX_TICKETS_PER_EVENT_LIMIT + 1,
+                ..Default::default()
+            };
+            assert_eq!(contract.set_config(unbounded), Err(Error::InvalidInput));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.set_config(config.clone()), Err(Error::MissingRole));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.set_config(config).unwrap();
+
+            let create = |contract: &mut TicketDot, price, total_tickets| {
+                contract.create_event(
+                    String::from("Test Event"),
+                    price,
+                    total_tickets,
+                    String::from("QmTest123"),
+                )
+            };
+            assert_eq!(create(&mut contract, 1000, 51), Err(Error::InvalidInput));
+            assert_eq!(create(&mut contract, 99, 50), Err(Error::InvalidInput));
+            create(&mut contract, 100, 50).unwrap();
+        }
+
+        #[ink::test]
+        fn events_by_organizer_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let create = |contract: &mut TicketDot| {
+                contract
+                    .create_event(
+                        String::from("Test Event"),
+                        1000,
+                        100,
+                        String::from("QmTest123"),
+                    )
+                    .unwrap()
+            };
+            let first = create(&mut contract);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let other = create(&mut contract);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let second = create(&mut contract);
+            let third = create(&mut contract);
+
+            assert_eq!(contract.get_organizer_event_count(accounts.alice), 3);
+            assert_eq!(
+                contract.get_events_by_organizer(accounts.alice, 0, 10),
+                vec![first, second, third]
+            );
+            assert_eq!(contract.get_events_by_organizer(accounts.alice, 1, 1), vec![second]);
+            assert_eq!(contract.get_events_by_organizer(accounts.bob, 0, 10), vec![other]);
+            assert_eq!(contract.get_events_by_organizer(accounts.bob, 5, 10), Vec::<u64>::new());
+        }
+
+        #[ink::test]
+        fn event_tickets_are_listed() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let first = contract.buy_ticket(event_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            let second = contract.buy_ticket(event_id).unwrap();
+            contract.transfer_ticket(second, accounts.django).unwrap();
+
+            assert_eq!(contract.get_event_ticket_count(event_id), 2);
+            let tickets = contract.get_event_tickets(event_id, 0, 10);
+            assert_eq!(tickets.len(), 2);
+            assert_eq!((tickets[0].id, tickets[0].owner), (first, accounts.bob));
+            assert_eq!((tickets[1].id, tickets[1].owner), (second, accounts.django));
+            assert_eq!(contract.get_event_tickets(event_id, 1, 10).len(), 1);
+        }
+
+        #[ink::test]
+        fn event_listing_is_paginated() {
+            let mut contract = TicketDot::new();
+
+            let mut event_ids = Vec::new();
+            for _ in 0..4 {
+                let event_id = contract
+                    .create_event(
+                        String::from("Test Event"),
+                        1000,
+                        100,
+                        String::from("QmTest123"),
+                    )
+                    .unwrap();
+                event_ids.push(event_id);
+            }
+            contract.cancel_event(event_ids[1]).unwrap();
+            contract.complete_event(event_ids[2]).unwrap();
+
+            let page: Vec<u64> = contract.get_events(1, 2).iter().map(|event| event.id).collect();
+            assert_eq!(page, vec![event_ids[1], event_ids[2]]);
+            assert_eq!(contract.get_events(3, 10).len(), 1);
+            assert!(contract.get_events(4, 10).is_empty());
+
+            let active: Vec<u64> = contract
+                .get_active_events(0, 10)
+                .iter()
+                .map(|event| event.id)
+                .collect();
+            assert_eq!(active, vec![event_ids[0], event_ids[3]]);
+        }
+
+        #[ink::test]
+        fn detailed_tickets_include_events() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let concert = contract
+                .create_event(
+                    String::from("Concert"),
+                    1000,
+                    100,
+                    String::from("QmConcert"),
+                )
+                .unwrap();
+            let festival = contract
+                .create_event(
+                    String::from("Festival"),
+                    1000,
+                    100,
+                    String::from("QmFestival"),
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let first = contract.buy_ticket(concert).unwrap();
+            let second = contract.buy_ticket(festival).unwrap();
+
+            let tickets = contract.get_my_tickets_detailed(accounts.bob, 0, 10);
+            assert_eq!(tickets.len(), 2);
+            assert_eq!(tickets[0].0.id, first);
+            assert_eq!(tickets[0].1.name, String::from("Concert"));
+            assert_eq!(tickets[1].0.id, second);
+            assert_eq!(tickets[1].1.metadata_cid, String::from("QmFestival"));
+
+            let page = contract.get_my_tickets_detailed(accounts.bob, 1, 1);
+            assert_eq!(page.len(), 1);
+            assert_eq!(page[0].1.id, festival);
+        }
+
+        #[ink::test]
+        fn batch_getters_work() {
+            let mut contract = TicketDot::new();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            let events = contract.get_events_by_ids(vec![event_id, 99]);
+            assert_eq!(events.len(), 2);
+            assert_eq!(events[0].as_ref().map(|event| event.id), Some(event_id));
+            assert!(events[1].is_none());
+
+            let tickets = contract.get_tickets(vec![99, ticket_id]);
+            assert!(tickets[0].is_none());
+            assert_eq!(tickets[1].as_ref().map(|ticket| ticket.id), Some(ticket_id));
+
+            // Oversized batches are capped
+            let ids = vec![ticket_id; MAX_PAGE_SIZE as usize + 1];
+            assert_eq!(contract.get_tickets(ids).len(), MAX_PAGE_SIZE as usize);
+        }
+
+        #[ink::test]
+        fn event_stats_are_tracked() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let used = contract.buy_ticket(event_id).unwrap();
+            let cancelled = contract.buy_ticket(event_id).unwrap();
+            contract.buy_ticket(event_id).unwrap();
+            contract.cancel_ticket(cancelled).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.use_ticket(used).unwrap();
+
+            let stats = contract.get_event_stats(event_id).unwrap();
+            assert_eq!(stats.tickets_sold, 3);
+            assert_eq!(stats.tickets_refunded, 1);
+            assert_eq!(stats.tickets_checked_in, 1);
+            assert_eq!(stats.gross_revenue, 3000);
+            assert_eq!(stats.refunds_paid, 1000);
+            assert_eq!(stats.net_escrow, 2000);
+            assert_eq!(contract.get_event_stats(99), None);
+        }
+
+        #[ink::test]
+        fn ticket_history_is_recorded() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            // Check user hasn't exceeded maximum tickets
-            let owner_ticket_list = self.owner_tickets.get(caller).unwrap_or_default();
-            if owner_ticket_list.len() >= MAX_TICKETS_PER_USER as usize {
-                return Err(Error::TooManyTickets);
+            let event_id = contract
+                .create_event(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            contract.transfer_ticket(ticket_id, accounts.charlie).unwrap();
+
+            // Resales record their price
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.list_ticket_for_sale(ticket_id, 1200).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1200);
+            contract.buy_listed_ticket(ticket_id).unwrap();
+
+            let history = contract.get_ticket_history(ticket_id);
+            assert_eq!(history.len(), 3);
+            assert_eq!((history[0].from, history[0].to), (None, accounts.bob));
+            assert_eq!((history[1].from, history[1].price), (Some(accounts.bob), None));
+            assert_eq!((history[2].to, history[2].price), (accounts.django, Some(1200)));
+
+            // Only the latest changes are kept
+            for _ in 0..MAX_TICKET_HISTORY {
+                contract.transfer_ticket(ticket_id, accounts.eve).unwrap();
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+                contract.transfer_ticket(ticket_id, accounts.django).unwrap();
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
             }
+            let history = contract.get_ticket_history(ticket_id);
+            assert_eq!(history.len(), MAX_TICKET_HISTORY as usize);
+            assert_eq!(history.last().unwrap().to, accounts.django);
+        }
 
-            // Create ticket ID and NFT
-            let ticket_id = self.ticket_counter;
-            
-            let ticket = Ticket {
-                id: ticket_id,
-                event_id,
-                owner: caller,
-                purchase_time: self.env().block_timestamp(),
-                is_used: false,
-                is_refunded: false,
+        #[ink::test]
+        fn verify_ticket_reports_status() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let options = EventOptions {
+                schedule: EventSchedule { event_start: 1000, ..Default::default() },
+                ..Default::default()
+            };
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    options,
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let used = contract.buy_ticket(event_id).unwrap();
+            let refunded = contract.buy_ticket(event_id).unwrap();
+            let valid = contract.buy_ticket(event_id).unwrap();
+            contract.cancel_ticket(refunded).unwrap();
+
+            assert_eq!(contract.verify_ticket(valid), TicketStatus::WrongWindow);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.verify_ticket(valid), TicketStatus::Valid);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.use_ticket(used).unwrap();
+            assert_eq!(contract.verify_ticket(used), TicketStatus::Used);
+            assert_eq!(contract.verify_ticket(refunded), TicketStatus::Refunded);
+            assert_eq!(contract.verify_ticket(99), TicketStatus::NotFound);
+
+            contract.complete_event(event_id).unwrap();
+            assert_eq!(contract.verify_ticket(valid), TicketStatus::EventCompleted);
+        }
+
+        #[ink::test]
+        fn solvency_views_track_escrow() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let first = contract
+                .create_event(String::from("First"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            let second = contract
+                .create_event(String::from("Second"), 500, 100, String::from("QmTest123"))
+                .unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket = contract.buy_ticket(first).unwrap();
+            contract.buy_ticket(first).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(500);
+            contract.buy_ticket(second).unwrap();
+
+            assert_eq!(contract.get_event_escrow(first), 2000);
+            assert_eq!(contract.get_event_escrow(second), 500);
+            assert_eq!(contract.get_total_escrowed(), 2500);
+
+            // Refunds come out of both the event's escrow and the total
+            contract.cancel_ticket(ticket).unwrap();
+            assert_eq!(contract.get_event_escrow(first), 1000);
+            assert_eq!(contract.get_total_escrowed(), 1500);
+            assert_eq!(contract.get_event_escrow(99), 0);
+        }
+
+        #[ink::test]
+        fn ticket_metadata_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let options = EventOptions {
+                schedule: EventSchedule { event_start: 1000, ..Default::default() },
+                ..Default::default()
+            };
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmEvent"),
+                    options,
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            // Without its own CID a ticket uses the event's
+            assert_eq!(contract.get_ticket_metadata(ticket_id), Some(String::from("QmEvent")));
+            let id = Id::U64(ticket_id);
+            let uri = PSP34Metadata::get_attribute(&contract, id.clone(), b"uri".to_vec());
+            assert_eq!(uri, Some(b"ipfs://QmEvent".to_vec()));
+            let name = PSP34Metadata::get_attribute(&contract, id, b"name".to_vec());
+            assert_eq!(name, Some(b"Test Event".to_vec()));
+
+            // Only the organizer can set it
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.set_ticket_metadata(ticket_id, String::from("QmTicket")),
+                Err(Error::NotOrganizer)
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.set_ticket_metadata(ticket_id, String::from("QmTicket")).unwrap();
+            assert_eq!(contract.get_ticket_metadata(ticket_id), Some(String::from("QmTicket")));
+
+            // Clearing falls back to the event's CID
+            contract.set_ticket_metadata(ticket_id, String::new()).unwrap();
+            assert_eq!(contract.get_ticket_metadata(ticket_id), Some(String::from("QmEvent")));
+
+            // Fixed once the event starts
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(
+                contract.set_ticket_metadata(ticket_id, String::from("QmTicket")),
+                Err(Error::EventStarted)
+            );
+        }
+
+        #[ink::test]
+        fn collection_metadata_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let id = PSP34::collection_id(&contract);
+
+            assert_eq!(contract.get_collection_metadata(), CollectionMetadata::default());
+            let name = PSP34Metadata::get_attribute(&contract, id.clone(), b"name".to_vec());
+            assert_eq!(name, Some(b"TicketDot".to_vec()));
+            let uri = PSP34Metadata::get_attribute(&contract, id.clone(), b"baseURI".to_vec());
+            assert_eq!(uri, None);
+
+            // Only admins can rebrand
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.set_collection_metadata(
+                    String::from("Tickets"),
+                    String::from("TIX"),
+                    String::new()
+                ),
+                Err(Error::MissingRole)
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                contract.set_collection_metadata(String::new(), String::from("TIX"), String::new()),
+                Err(Error::InvalidInput)
+            );
+
+            contract
+                .set_collection_metadata(
+                    String::from("Tickets"),
+                    String::from("TIX"),
+                    String::from("ipfs://QmCollection"),
+                )
+                .unwrap();
+            assert_eq!(contract.get_collection_metadata().symbol, "TIX");
+            let uri = PSP34Metadata::get_attribute(&contract, id, b"baseURI".to_vec());
+            assert_eq!(uri, Some(b"ipfs://QmCollection".to_vec()));
+        }
+
+        #[ink::test]
+        fn events_by_category_and_tag_work() {
+            let mut contract = TicketDot::new();
+            let create = |contract: &mut TicketDot, category: u8, tags: &[&str]| {
+                let options = EventOptions {
+                    category,
+                    tags: tags.iter().map(|tag| String::from(*tag)).collect(),
+                    ..Default::default()
+                };
+                contract.create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    options,
+                )
+            };
+
+            let concert = create(&mut contract, 1, &["rock", "outdoor"]).unwrap();
+            let match_day = create(&mut contract, 2, &["outdoor"]).unwrap();
+            let gig = create(&mut contract, 1, &[]).unwrap();
+
+            assert_eq!(contract.get_events_by_category(1, 0, 10), vec![concert, gig]);
+            assert_eq!(contract.get_events_by_category(1, 1, 10), vec![gig]);
+            assert_eq!(contract.get_events_by_category(3, 0, 10), Vec::<u64>::new());
+            assert_eq!(
+                contract.get_events_by_tag(String::from("outdoor"), 0, 10),
+                vec![concert, match_day]
+            );
+            assert_eq!(contract.get_event(concert).unwrap().tags.len(), 2);
+
+            // Duplicate or empty tags are rejected
+            assert_eq!(create(&mut contract, 1, &["rock", "rock"]), Err(Error::InvalidInput));
+            assert_eq!(create(&mut contract, 1, &[""]), Err(Error::InvalidInput));
+        }
+
+        #[ink::test]
+        fn season_pass_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let create = |contract: &mut TicketDot| {
+                contract.create_event(String::from("Match"), 1000, 100, String::from("QmTest123"))
+            };
+
+            let first = create(&mut contract).unwrap();
+            let second = create(&mut contract).unwrap();
+            let listed = contract
+                .create_pass(
+                    String::from("Half season"),
+                    String::from("QmPass"),
+                    1500,
+                    10,
+                    PassScope::Events(vec![first]),
+                    1000,
+                )
+                .unwrap();
+            let season = contract
+                .create_pass(
+                    String::from("Season"),
+                    String::from("QmPass"),
+                    5000,
+                    1,
+                    PassScope::FutureEvents,
+                    1000,
+                )
+                .unwrap();
+            let later = create(&mut contract).unwrap();
+
+            // Only the organizer's own events can be listed
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.create_pass(
+                    String::from("Fake"),
+                    String::from("QmPass"),
+                    1500,
+                    10,
+                    PassScope::Events(vec![first]),
+                    1000,
+                ),
+                Err(Error::NotOrganizer)
+            );
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1500);
+            let half = contract.buy_pass(listed).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(
+                contract.buy_pass(listed),
+                Err(Error::InsufficientPayment {
+                    expected: 1500,
+                    got: 1000
+                })
+            );
+
+            // Overpayment goes back to the buyer and only the price is held
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2000);
+            contract.buy_pass(listed).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(5000);
+            let full = contract.buy_pass(season).unwrap();
+            assert_eq!(contract.buy_pass(season), Err(Error::SoldOut));
+            assert_eq!(contract.get_pass_token(full).unwrap().owner, accounts.bob);
+            assert_eq!(contract.get_total_escrowed(), 8000);
+
+            // Holders can't check themselves in
+            assert_eq!(contract.use_pass(half, first), Err(Error::NotTicketOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.use_pass(half, first).unwrap();
+            assert!(contract.is_pass_used(half, first));
+            assert_eq!(contract.use_pass(half, first), Err(Error::TicketAlreadyUsed));
+            assert_eq!(contract.use_pass(half, second), Err(Error::PassNotValidForEvent));
+
+            // A future-events pass covers only events created after it
+            assert_eq!(contract.use_pass(full, second), Err(Error::PassNotValidForEvent));
+            contract.use_pass(full, later).unwrap();
+
+            // Earnings unlock when the pass expires
+            assert_eq!(contract.withdraw_pass_earnings(season), Err(Error::WithdrawalLocked));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.use_pass(full, later), Err(Error::PassExpired));
+            assert_eq!(contract.withdraw_pass_earnings(season), Ok(5000));
+            assert_eq!(contract.get_total_escrowed(), 3000);
+        }
+
+        #[ink::test]
+        fn event_series_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let options = EventOptions {
+                schedule: EventSchedule { event_start: 100, event_end: 150, ..Default::default() },
+                ..Default::default()
+            };
+            let create = |contract: &mut TicketDot, sessions: Vec<u64>| {
+                contract.create_event_series(
+                    String::from("Weekly Show"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    options.clone(),
+                    sessions,
+                )
+            };
+
+            assert_eq!(create(&mut contract, vec![]), Err(Error::InvalidInput));
+            assert_eq!(create(&mut contract, vec![2000, 1000]), Err(Error::InvalidInput));
+
+            let series_id = create(&mut contract, vec![1000, 2000, 3000]).unwrap();
+            let sessions = contract.get_series_events(series_id);
+            assert_eq!(sessions.len(), 3);
+            assert_eq!(sessions[1].schedule.event_start, 2000);
+            assert_eq!(sessions[1].schedule.event_end, 2050);
+            assert_eq!(contract.get_event_series(sessions[2].id), Some(series_id));
+
+            // Buyers pick a session by its event
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let ticket_id = contract.buy_ticket(sessions[1].id).unwrap();
+            assert_eq!(contract.cancel_series(series_id), Err(Error::NotOrganizer));
+
+            // Cancelling skips sessions already cancelled
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.cancel_event(sessions[0].id).unwrap();
+            assert_eq!(contract.cancel_series(series_id), Ok(2));
+            assert!(contract.get_series_events(series_id).iter().all(|event| event.is_cancelled()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.refund_ticket(ticket_id).unwrap();
+        }
+
+        #[ink::test]
+        fn multi_day_check_in_works() {
+            let mut contract = TicketDot::new();
+            let day = 24 * 60 * 60 * 1000;
+
+            let options = EventOptions {
+                days: vec![day, 2 * day, 3 * day],
+                ..Default::default()
+            };
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Conference"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    options,
+                )
+                .unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().uses_per_ticket, 3);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            // Nothing to check in for before the first day
+            assert_eq!(contract.use_ticket(ticket_id), Err(Error::OutsideEventWindow));
+
+            // Once per day, any time during the day
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(day + 10);
+            contract.use_ticket(ticket_id).unwrap();
+            assert_eq!(contract.verify_ticket(ticket_id), TicketStatus::Used);
+            assert_eq!(contract.use_ticket(ticket_id), Err(Error::TicketAlreadyUsed));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(3 * day);
+            assert_eq!(contract.verify_ticket(ticket_id), TicketStatus::Valid);
+            contract.use_ticket(ticket_id).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().uses_remaining, 1);
+        }
+
+        #[ink::test]
+        fn addons_work() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            let parking = contract.add_addon(event_id, String::from("Parking"), 200, 1).unwrap();
+            assert_eq!(contract.get_event_addons(event_id).len(), 1);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            let other = contract.buy_ticket(event_id).unwrap();
+
+            // Paid in full, once per ticket, while supply lasts
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(
+                contract.buy_addon(ticket_id, parking),
+                Err(Error::InsufficientPayment { expected: 200, got: 100 })
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(200);
+            assert_eq!(contract.buy_addon(ticket_id, 7), Err(Error::AddOnNotFound));
+            contract.buy_addon(ticket_id, parking).unwrap();
+            assert_eq!(contract.buy_addon(ticket_id, parking), Err(Error::AddOnAlreadyPurchased));
+            assert_eq!(contract.buy_addon(other, parking), Err(Error::SoldOut));
+            assert_eq!(contract.get_event_escrow(event_id), 2200);
+            assert_eq!(contract.get_ticket_addon(ticket_id, parking), Some(false));
+
+            // Redeemed once by staff
+            assert_eq!(contract.redeem_addon(ticket_id, parking), Err(Error::NotTicketOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.redeem_addon(other, parking), Err(Error::AddOnNotPurchased));
+            contract.redeem_addon(ticket_id, parking).unwrap();
+            assert_eq!(contract.redeem_addon(ticket_id, parking), Err(Error::AddOnAlreadyRedeemed));
+
+            // Refunded with the ticket when the event is cancelled
+            contract.cancel_event(event_id).unwrap();
+            assert_eq!(contract.compute_refund(ticket_id), Some(1200));
+        }
+
+        #[ink::test]
+        fn donations_and_tips_work() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Charity Run"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.donate_to_event(event_id), Err(Error::InvalidInput));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(500);
+            contract.donate_to_event(event_id).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(50);
+            contract.tip_organizer(event_id).unwrap();
+
+            // Tracked apart from ticket revenue, but escrowed for the organizer
+            let stats = contract.get_event_stats(event_id).unwrap();
+            assert_eq!(stats.donations, 500);
+            assert_eq!(stats.tips, 50);
+            assert_eq!(stats.gross_revenue, 0);
+            assert_eq!(contract.get_event_escrow(event_id), 550);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            contract.complete_event(event_id).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(DEFAULT_DISPUTE_WINDOW);
+            contract.withdraw_earnings(event_id).unwrap();
+            assert_eq!(contract.get_event_escrow(event_id), 0);
+        }
+
+        #[ink::test]
+        fn bonding_curve_pricing_works() {
+            let mut contract = TicketDot::new();
+            let create = |contract: &mut TicketDot, curve: BondingCurve| {
+                let options = EventOptions { bonding_curve: Some(curve), ..Default::default() };
+                contract.create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    options,
+                )
+            };
+
+            assert_eq!(
+                create(&mut contract, BondingCurve::Step { step: 0, increment: 10 }),
+                Err(Error::InvalidInput)
+            );
+            let linear = create(&mut contract, BondingCurve::Linear { increment: 100 }).unwrap();
+            let step =
+                create(&mut contract, BondingCurve::Step { step: 2, increment: 500 }).unwrap();
+
+            // Raffle deposits are flat, so raffles can't follow a curve
+            let raffle = EventOptions {
+                bonding_curve: Some(BondingCurve::Linear { increment: 100 }),
+                raffle_end: 100,
+                raffle_seed_hash: Some(raffle_seed_hash(&Hash::from([7; 32]))),
+                ..Default::default()
+            };
+            assert_eq!(
+                contract.create_event_with_options(
+                    String::from("Raffle Event"),
+                    1000,
+                    100,
+                    String::from("QmTest456"),
+                    raffle,
+                ),
+                Err(Error::InvalidInput)
+            );
+
+            // Each sale raises the price of the next ticket
+            assert_eq!(contract.get_current_price(linear), Some(1000));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            contract.buy_ticket(linear).unwrap();
+            assert_eq!(contract.get_current_price(linear), Some(1100));
+            assert_eq!(
+                contract.buy_ticket(linear),
+                Err(Error::InsufficientPayment { expected: 1100, got: 1000 })
+            );
+
+            // Batches pay the rising price ticket by ticket: 1100 + 1200 + 1300
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(3600);
+            let ticket_ids = contract.buy_tickets(linear, 3).unwrap();
+            assert_eq!(contract.get_ticket(ticket_ids[2]).unwrap().price_paid, 1300);
+            assert_eq!(contract.get_current_price(linear), Some(1400));
+
+            // Step curves rise once every `step` tickets: 1000 + 1000 + 1500
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(3500);
+            contract.buy_tickets(step, 3).unwrap();
+            assert_eq!(contract.get_current_price(step), Some(1500));
+        }
+
+        #[ink::test]
+        fn update_price_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+
+            assert_eq!(contract.update_price(event_id, 0), Err(Error::InvalidInput));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.update_price(event_id, 800), Err(Error::NotOrganizer));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.update_price(event_id, 800).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().price, 800);
+
+            // Fixed once the first ticket is sold
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(800);
+            contract.buy_ticket(event_id).unwrap();
+            assert_eq!(contract.update_price(event_id, 900), Err(Error::InvalidInput));
+        }
+
+        #[ink::test]
+        fn overpayment_is_refunded() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(999);
+            assert_eq!(
+                contract.buy_ticket(event_id),
+                Err(Error::InsufficientPayment { expected: 1000, got: 999 })
+            );
+
+            // Only the price is charged and escrowed
+            let balance_before =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob)
+                    .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1003);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().price_paid, 1000);
+            assert_eq!(contract.get_event_escrow(event_id), 1000);
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob),
+                Ok(balance_before + 3)
+            );
+        }
+
+        #[ink::test]
+        fn pause_and_resume_sales_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.pause_sales(event_id), Err(Error::NotOrganizer));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.pause_sales(event_id).unwrap();
+
+            // Only buying stops
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::SalesPaused));
+            assert_eq!(contract.get_event(event_id).unwrap().status, EventStatus::Paused);
+            contract.transfer_ticket(ticket_id, accounts.bob).unwrap();
+
+            contract.resume_sales(event_id).unwrap();
+            contract.buy_ticket(event_id).unwrap();
+        }
+
+        #[ink::test]
+        fn tickets_expire_after_event_end() {
+            let mut contract = TicketDot::new();
+
+            let options = EventOptions {
+                schedule: EventSchedule { event_start: 100, event_end: 200, ..Default::default() },
+                ..Default::default()
+            };
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    options,
+                )
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+
+            assert_eq!(contract.verify_ticket(ticket_id), TicketStatus::WrongWindow);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(150);
+            assert_eq!(contract.verify_ticket(ticket_id), TicketStatus::Valid);
+
+            // Stale once the event ends, even if never used
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(200);
+            assert_eq!(contract.verify_ticket(ticket_id), TicketStatus::Expired);
+            assert_eq!(contract.use_ticket(ticket_id), Err(Error::TicketExpired));
+        }
+
+        #[ink::test]
+        fn finalize_event_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let create = |contract: &mut TicketDot, event_end: u64| {
+                let options = EventOptions {
+                    schedule: EventSchedule { event_end, ..Default::default() },
+                    ..Default::default()
+                };
+                contract.create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    options,
+                )
             };
+            let event_id = create(&mut contract, 1000).unwrap();
+            let open_ended = create(&mut contract, 0).unwrap();
 
-            // Update event availability
-            event.available_tickets = event.available_tickets.saturating_sub(1);
-            self.events.insert(event_id, &event);
+            // Anyone, but only after the grace period
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                1000 + DEFAULT_FINALIZE_GRACE_PERIOD - 1,
+            );
+            assert_eq!(contract.finalize_event(event_id), Err(Error::EventNotEnded));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                1000 + DEFAULT_FINALIZE_GRACE_PERIOD,
+            );
+            contract.finalize_event(event_id).unwrap();
+            assert!(contract.get_event(event_id).unwrap().is_completed());
+            assert!(contract.get_withdrawal_unlock(event_id).is_some());
+            assert_eq!(contract.finalize_event(event_id), Err(Error::EventCompleted));
 
-            // Store ticket
-            self.tickets.insert(ticket_id, &ticket);
-            self.ticket_counter = self.ticket_counter.saturating_add(1);
+            // Events without an end are left to the organizer
+            assert_eq!(contract.finalize_event(open_ended), Err(Error::EventNotEnded));
+        }
 
-            // Update owner's ticket list (using BTreeSet for efficient operations)
-            let mut owner_ticket_set = self.owner_tickets.get(caller).unwrap_or_default();
-            owner_ticket_set.insert(ticket_id);
-            self.owner_tickets.insert(caller, &owner_ticket_set);
+        #[ink::test]
+        fn owner_ticket_index_works() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            // NOTE: Payment is held in contract as escrow
-            // Organizer can withdraw earnings after event is completed
-            // This enables automatic refunds if event is cancelled
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let first = contract.buy_ticket(event_id).unwrap();
+            let second = contract.buy_ticket(event_id).unwrap();
+            let third = contract.buy_ticket(event_id).unwrap();
+            assert_eq!(contract.get_my_tickets(accounts.alice), vec![first, second, third]);
+            assert_eq!(contract.get_my_tickets_page(accounts.alice, 1, 1), vec![second]);
 
-            // Emit event
-            self.env().emit_event(TicketPurchased {
-                ticket_id,
-                event_id,
-                buyer: caller,
-                price: event.price,
-            });
+            // Removing a ticket moves the last one into its slot
+            contract.transfer_ticket(first, accounts.bob).unwrap();
+            assert_eq!(contract.get_my_tickets(accounts.alice), vec![third, second]);
+            assert_eq!(contract.get_my_tickets(accounts.bob), vec![first]);
+            contract.cancel_ticket(second).unwrap();
+            assert_eq!(contract.get_my_tickets(accounts.alice), vec![third]);
+            assert_eq!(PSP34::balance_of(&contract, accounts.alice), 1);
 
-            Ok(ticket_id)
+            // Slots are reused without gaps
+            contract.buy_ticket(event_id).unwrap();
+            assert_eq!(contract.get_my_tickets_page(accounts.alice, 0, 10).len(), 2);
         }
 
-        /// Transfer ticket to another user
-        /// 
-        /// # Arguments
-        /// * `ticket_id` - ID of the ticket to transfer
-        /// * `to` - Account ID of the recipient
-        #[ink(message)]
-        pub fn transfer_ticket(
-            &mut self,
-            ticket_id: u64,
-            to: AccountId,
-        ) -> Result<(), Error> {
-            let caller = self.env().caller();
+        #[ink::test]
+        fn draft_events_sell_after_publishing() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            // Get ticket
-            let mut ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            let options = EventOptions {
+                draft: true,
+                ..Default::default()
+            };
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    options,
+                )
+                .unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().status, EventStatus::Draft);
 
-            // Verify ownership
-            if ticket.owner != caller {
-                return Err(Error::NotTicketOwner);
-            }
+            // Drafts can't sell, pause, or complete
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::EventNotActive));
+            assert_eq!(contract.pause_sales(event_id), Err(Error::InvalidStatusTransition));
+            assert_eq!(contract.complete_event(event_id), Err(Error::InvalidStatusTransition));
 
-            // Can't transfer used ticket
-            if ticket.is_used {
-                return Err(Error::TicketAlreadyUsed);
-            }
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.publish_event(event_id), Err(Error::NotOrganizer));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.publish_event(event_id).unwrap();
+            assert_eq!(contract.publish_event(event_id), Err(Error::InvalidStatusTransition));
+            contract.buy_ticket(event_id).unwrap();
 
-            // Can't transfer refunded ticket
-            if ticket.is_refunded {
-                return Err(Error::TicketAlreadyRefunded);
-            }
+            // Terminal statuses stay put
+            contract.cancel_event(event_id).unwrap();
+            assert_eq!(contract.resume_sales(event_id), Err(Error::EventCancelled));
+            assert_eq!(contract.complete_event(event_id), Err(Error::EventCancelled));
+        }
 
-            // Check recipient hasn't exceeded maximum tickets
-            let new_owner_tickets = self.owner_tickets.get(to).unwrap_or_default();
-            if new_owner_tickets.len() >= MAX_TICKETS_PER_USER as usize {
-                return Err(Error::TooManyTickets);
+        #[ink::test]
+        fn error_codes_are_stable() {
+            // Existing codes never move
+            assert_eq!(Error::EventNotFound.code(), 0);
+            assert_eq!(Error::InsufficientPayment { expected: 1000, got: 0 }.code(), 2);
+            assert_eq!(Error::SaleNotOpen { opens_at: 100 }.code(), 29);
+            assert_eq!(Error::PurchaseLimitReached { limit: 2 }.code(), 93);
+        }
+
+        #[ink::test]
+        fn export_ticket_mirrors_into_pallet_nfts() {
+            struct MockNfts;
+            impl ink::env::test::ChainExtension for MockNfts {
+                fn ext_id(&self) -> u16 {
+                    1
+                }
+
+                fn call(&mut self, _func_id: u16, _input: &[u8], output: &mut Vec<u8>) -> u32 {
+                    scale::Encode::encode_to(&Ok::<(), crate::nfts::NftsError>(()), output);
+                    0
+                }
             }
+            ink::env::test::register_chain_extension(MockNfts);
 
-            // Update ticket owner
-            let old_owner = ticket.owner;
-            ticket.owner = to;
-            self.tickets.insert(ticket_id, &ticket);
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            // Update old owner's ticket list (using BTreeSet for efficient removal)
-            let mut old_owner_set = self.owner_tickets.get(old_owner).unwrap_or_default();
-            old_owner_set.remove(&ticket_id);
-            self.owner_tickets.insert(old_owner, &old_owner_set);
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
 
-            // Update new owner's ticket list (using BTreeSet for efficient insertion)
-            let mut new_owner_set = self.owner_tickets.get(to).unwrap_or_default();
-            new_owner_set.insert(ticket_id);
-            self.owner_tickets.insert(to, &new_owner_set);
+            // Exports need a collection
+            assert_eq!(contract.export_ticket(ticket_id), Err(Error::NftCollectionNotSet));
+            contract.set_nft_collection(Some(7)).unwrap();
+            assert_eq!(contract.get_nft_collection(), Some(7));
 
-            // Emit event
-            self.env().emit_event(TicketTransferred {
-                ticket_id,
-                from: caller,
-                to,
-            });
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.export_ticket(ticket_id), Err(Error::NotTicketOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.export_ticket(ticket_id).unwrap();
+            assert!(contract.is_ticket_exported(ticket_id));
+            assert_eq!(contract.export_ticket(ticket_id), Err(Error::TicketAlreadyExported));
 
-            Ok(())
+            // The mirror follows the ticket and goes when it is refunded
+            contract.transfer_ticket(ticket_id, accounts.bob).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.cancel_ticket(ticket_id).unwrap();
+            assert!(!contract.is_ticket_exported(ticket_id));
         }
 
-        /// Mark ticket as used (called by event organizer or admin)
-        #[ink(message)]
-        pub fn use_ticket(&mut self, ticket_id: u64) -> Result<(), Error> {
-            let caller = self.env().caller();
+        #[ink::test]
+        fn buy_ticket_xcm_mints_to_beneficiary() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            // Get ticket
-            let mut ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            let message_id = Hash::from([1u8; 32]);
 
-            // Get event to verify organizer
-            let event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+            // Only trusted origins relay purchases
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(
+                contract.buy_ticket_xcm(event_id, accounts.bob, message_id),
+                Err(Error::UntrustedXcmOrigin)
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.set_xcm_origin(accounts.charlie, true).unwrap();
+            assert!(contract.is_xcm_origin(accounts.charlie));
 
-            // Only organizer or admin can mark ticket as used
-            if caller != event.organizer && caller != self.admin {
-                return Err(Error::NotTicketOwner);
-            }
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            let ticket_id = contract.buy_ticket_xcm(event_id, accounts.bob, message_id).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().owner, accounts.bob);
 
-            // Can't use refunded ticket
-            if ticket.is_refunded {
-                return Err(Error::TicketAlreadyRefunded);
-            }
+            // A deposit can't be replayed
+            assert_eq!(
+                contract.buy_ticket_xcm(event_id, accounts.bob, message_id),
+                Err(Error::XcmDepositAlreadyProcessed)
+            );
 
-            // Can't use already used ticket
-            if ticket.is_used {
-                return Err(Error::TicketAlreadyUsed);
-            }
+            // Message IDs are tracked per origin
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.set_xcm_origin(accounts.django, true).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            contract.buy_ticket_xcm(event_id, accounts.bob, message_id).unwrap();
 
-            // Can't use ticket for cancelled event
-            if event.cancelled {
-                return Err(Error::EventCancelled);
-            }
+            // The deposit must be attached and cover the price
+            let message_id = Hash::from([2u8; 32]);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            assert_eq!(
+                contract.buy_ticket_xcm(event_id, accounts.bob, message_id),
+                Err(Error::XcmDepositMissing)
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(500);
+            assert_eq!(
+                contract.buy_ticket_xcm(event_id, accounts.bob, message_id),
+                Err(Error::InsufficientPayment {
+                    expected: 1000,
+                    got: 500
+                })
+            );
+        }
 
-            // Can't use ticket for completed event
-            if event.completed {
-                return Err(Error::EventCompleted);
-            }
+        #[ink::test]
+        fn usd_priced_events_need_an_oracle() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            // Mark as used
-            ticket.is_used = true;
-            self.tickets.insert(ticket_id, &ticket);
+            let create = |contract: &mut TicketDot, options: EventOptions| {
+                contract.create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    options,
+                )
+            };
 
-            // Emit event
-            self.env().emit_event(TicketUsed {
-                ticket_id,
-                event_id: ticket.event_id,
-            });
+            // USD prices need native payment and a fixed price
+            let usd = EventOptions {
+                usd_price_cents: Some(2500),
+                ..Default::default()
+            };
+            let token = EventOptions {
+                payment_token: Some(accounts.django),
+                ..usd.clone()
+            };
+            assert_eq!(create(&mut contract, token), Err(Error::InvalidInput));
+            let event_id = create(&mut contract, usd).unwrap();
+            assert_eq!(contract.get_usd_price(event_id), Some(2500));
 
-            Ok(())
+            // Without an oracle nothing can be sold
+            assert_eq!(contract.get_price_in_native(event_id), Err(Error::OracleUnavailable));
+            assert_eq!(contract.get_current_price(event_id), None);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::OracleUnavailable));
+
+            assert_eq!(contract.set_price_oracle(Some(accounts.eve), 0), Err(Error::InvalidInput));
+            contract.set_price_oracle(Some(accounts.eve), 60_000).unwrap();
+            assert_eq!(contract.get_price_oracle(), (Some(accounts.eve), 60_000));
         }
 
-        /// Cancel an event and enable refunds for all ticket holders
-        /// Only the event organizer can cancel
-        #[ink(message)]
-        pub fn cancel_event(&mut self, event_id: u64) -> Result<(), Error> {
-            let caller = self.env().caller();
+        #[ink::test]
+        fn ticket_holders_vote_in_polls() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            // Get event
-            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
 
-            // Only organizer can cancel
-            if caller != event.organizer {
-                return Err(Error::NotOrganizer);
-            }
+            // Only the organizer opens polls
+            let options = vec![String::from("Encore A"), String::from("Encore B")];
+            let question = String::from("Which encore?");
+            assert_eq!(
+                contract.create_poll(event_id, question.clone(), options.clone(), 100),
+                Err(Error::NotOrganizer)
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let poll_id = contract.create_poll(event_id, question, options, 100).unwrap();
 
-            // Can't cancel already cancelled event
-            if event.cancelled {
-                return Err(Error::EventCancelled);
-            }
+            // One vote per ticket, by its holder
+            assert_eq!(contract.vote(ticket_id, poll_id, 1), Err(Error::NotTicketOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.vote(ticket_id, poll_id, 2), Err(Error::InvalidInput));
+            contract.vote(ticket_id, poll_id, 1).unwrap();
+            assert_eq!(contract.vote(ticket_id, poll_id, 0), Err(Error::AlreadyVoted));
+            assert_eq!(contract.get_poll(event_id, poll_id).unwrap().votes, vec![0, 1]);
+            assert_eq!(contract.get_ticket_vote(event_id, poll_id, ticket_id), Some(1));
 
-            // Can't cancel completed event
-            if event.completed {
-                return Err(Error::EventCompleted);
-            }
+            // The new holder can't vote again with the same ticket
+            contract.transfer_ticket(ticket_id, accounts.charlie).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.vote(ticket_id, poll_id, 0), Err(Error::AlreadyVoted));
 
-            // Mark event as cancelled and inactive
-            event.cancelled = true;
-            event.active = false;
-            self.events.insert(event_id, &event);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(contract.vote(ticket_id, poll_id, 0), Err(Error::PollClosed));
+            assert_eq!(contract.get_event_polls(event_id).len(), 1);
+        }
 
-            // Emit event
-            self.env().emit_event(EventCancelled {
-                event_id,
-                organizer: caller,
-            });
+        #[ink::test]
+        fn loyalty_points_are_earned_and_spent() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            Ok(())
-        }
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            let vip_event = contract
+                .create_event(String::from("VIP Night"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            let terms = LoyaltyTerms {
+                min_points: 15,
+                redeem_points: 10,
+                discount_bps: 5_000,
+            };
+            contract.set_loyalty_terms(vip_event, terms).unwrap();
 
-        /// Mark an event as completed (no more tickets can be used)
-        /// Only the event organizer can mark as completed
-        #[ink(message)]
-        pub fn complete_event(&mut self, event_id: u64) -> Result<(), Error> {
-            let caller = self.env().caller();
+            // Points for buying, and a bonus for showing up
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            assert_eq!(contract.get_points(accounts.bob), 10);
+            assert_eq!(contract.buy_ticket(vip_event), Err(Error::InsufficientPoints));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.use_ticket(ticket_id).unwrap();
+            assert_eq!(contract.get_points(accounts.bob), 15);
 
-            // Get event
-            let mut event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
+            // Points buy a discount at the gated event
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(500);
+            let vip_ticket = contract.buy_ticket_with_points(vip_event).unwrap();
+            assert_eq!(contract.get_ticket(vip_ticket).unwrap().price_paid, 500);
+            assert_eq!(contract.get_points(accounts.bob), 15);
+            assert_eq!(contract.buy_ticket_with_points(event_id), Err(Error::InvalidInput));
+        }
 
-            // Only organizer can mark as completed
-            if caller != event.organizer {
-                return Err(Error::NotOrganizer);
-            }
+        #[ink::test]
+        fn transfer_limits_are_enforced() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            // Can't complete cancelled event
-            if event.cancelled {
-                return Err(Error::EventCancelled);
-            }
+            let transfer_limits = TransferLimits {
+                cooldown: 100,
+                max_transfers: 2,
+            };
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions { transfer_limits, ..Default::default() },
+                )
+                .unwrap();
+            assert_eq!(contract.get_transfer_limits(event_id), transfer_limits);
 
-            // Can't complete already completed event
-            if event.completed {
-                return Err(Error::EventCompleted);
-            }
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
 
-            // Mark event as completed and inactive
-            event.completed = true;
-            event.active = false;
-            self.events.insert(event_id, &event);
+            // Each holder keeps the ticket for the cooldown
+            assert_eq!(
+                contract.transfer_ticket(ticket_id, accounts.bob),
+                Err(Error::TransferCooldown { until: 100 })
+            );
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(100);
+            contract.transfer_ticket(ticket_id, accounts.bob).unwrap();
 
-            // Emit event
-            self.env().emit_event(EventCompleted {
-                event_id,
-            });
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(200);
+            contract.transfer_ticket(ticket_id, accounts.charlie).unwrap();
 
-            Ok(())
+            // Two transfers is all this ticket gets, resales included
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(300);
+            assert_eq!(
+                contract.transfer_ticket(ticket_id, accounts.django),
+                Err(Error::TransferLimitReached { limit: 2 })
+            );
         }
 
-        /// Refund a ticket for a cancelled event
-        /// Only works if the event has been cancelled and ticket hasn't been refunded yet
-        /// 
-        /// # Security
-        /// - Immediately removes ticket ID from owner's list for efficient queries
-        /// - Prevents double refunds with is_refunded flag
-        #[ink(message)]
-        pub fn refund_ticket(&mut self, ticket_id: u64) -> Result<(), Error> {
-            let caller = self.env().caller();
-
-            // Get ticket
-            let mut ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+        #[ink::test]
+        fn purchase_rate_limits_are_enforced() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            // Only ticket owner can request refund
-            if ticket.owner != caller {
-                return Err(Error::NotTicketOwner);
-            }
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 100, String::from("QmTest123"))
+                .unwrap();
+            let limits = RateLimits {
+                max_mints_per_block: 2,
+                purchase_cooldown: 3,
+            };
+            contract.set_rate_limits(limits).unwrap();
+            assert_eq!(contract.get_rate_limits(), limits);
 
-            // Check if already refunded
-            if ticket.is_refunded {
-                return Err(Error::TicketAlreadyRefunded);
-            }
+            // Two tickets per block for the event
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(3000);
+            assert_eq!(contract.buy_tickets(event_id, 3), Err(Error::BlockMintLimitReached));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            contract.buy_ticket(event_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            contract.buy_ticket(event_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::BlockMintLimitReached));
 
-            // Get event
-            let event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+            // And a cooldown between an account's purchases
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            contract.buy_ticket(event_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.buy_ticket(event_id),
+                Err(Error::PurchaseCooldown { until_block: 3 })
+            );
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            contract.buy_ticket(event_id).unwrap();
+        }
 
-            // Can only refund if event is cancelled
-            if !event.cancelled {
-                return Err(Error::EventNotActive);
-            }
+        #[ink::test]
+        fn personhood_gated_events_need_attestation() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            // Mark ticket as refunded
-            ticket.is_refunded = true;
-            self.tickets.insert(ticket_id, &ticket);
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions {
+                        tickets_per_person: 2,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            let open_id = contract
+                .create_event(String::from("Open Event"), 1000, 100, String::from("QmTest456"))
+                .unwrap();
+            assert_eq!(contract.get_tickets_per_person(event_id), 2);
+            assert_eq!(contract.get_tickets_per_person(open_id), 0);
 
-            // IMMEDIATELY remove ticket from owner's list for efficient queries
-            let mut owner_ticket_set = self.owner_tickets.get(caller).unwrap_or_default();
-            owner_ticket_set.remove(&ticket_id);
-            self.owner_tickets.insert(caller, &owner_ticket_set);
+            // Without a registry nobody is attested
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::NotVerifiedHuman));
+            assert_eq!(
+                contract.set_personhood_registry(Some(accounts.eve)),
+                Err(Error::MissingRole)
+            );
 
-            // Transfer refund to ticket owner
-            let refund_amount = event.price;
-            if self.env().transfer(caller, refund_amount).is_err() {
-                return Err(Error::TransferFailed);
-            }
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.set_personhood_registry(Some(accounts.eve)).unwrap();
+            assert_eq!(contract.get_personhood_registry(), Some(accounts.eve));
 
-            // Emit event
-            self.env().emit_event(TicketRefunded {
-                ticket_id,
-                owner: caller,
-                amount: refund_amount,
-            });
+            // Ungated events don't consult the registry
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.buy_ticket(open_id).unwrap();
 
-            Ok(())
+            // Cancelling a ticket gives it back to its buyer's quota
+            let ticket_id = contract.buy_ticket(open_id).unwrap();
+            let person = Hash::from([9; 32]);
+            contract.ticket_persons.insert(ticket_id, &person);
+            contract.person_tickets.insert((open_id, person), &2);
+            contract.cancel_ticket(ticket_id).unwrap();
+            assert_eq!(contract.person_tickets.get((open_id, person)), Some(1));
+            assert!(!contract.ticket_persons.contains(ticket_id));
         }
 
-        /// Cancel a ticket and get refund (before event starts/completes)
-        /// This makes the ticket available for sale again
-        /// 
-        /// # Security
-        /// - Immediately removes ticket ID from owner's list for efficient queries
-        /// - Returns ticket to available pool
-        #[ink(message)]
-        pub fn cancel_ticket(&mut self, ticket_id: u64) -> Result<(), Error> {
-            let caller = self.env().caller();
+        #[ink::test]
+        fn credential_gated_events_need_a_valid_credential() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            // Get ticket
-            let mut ticket = self.tickets.get(ticket_id).ok_or(Error::TicketNotFound)?;
+            let event_id = contract
+                .create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    EventOptions {
+                        credential_attester: Some(accounts.eve),
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            let open_id = contract
+                .create_event(String::from("Open Event"), 1000, 100, String::from("QmTest456"))
+                .unwrap();
+            let raffle_id = contract
+                .create_event_with_options(
+                    String::from("Raffle Event"),
+                    1000,
+                    100,
+                    String::from("QmTest789"),
+                    EventOptions {
+                        credential_attester: Some(accounts.eve),
+                        raffle_end: 100,
+                        raffle_seed_hash: Some(raffle_seed_hash(&Hash::from([7; 32]))),
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            assert_eq!(contract.get_credential_attester(event_id), Some(accounts.eve));
+            assert_eq!(contract.get_credential_attester(open_id), None);
 
-            // Only ticket owner can cancel
-            if ticket.owner != caller {
-                return Err(Error::NotTicketOwner);
-            }
+            // Buyers need a credential from the event's attester
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::CredentialRequired));
+            assert_eq!(contract.enter_raffle(raffle_id), Err(Error::CredentialRequired));
+            let credential = Credential {
+                attester: accounts.django,
+                claim_hash: Hash::from([0x18; 32]),
+            };
+            assert_eq!(
+                contract.present_credential(event_id, credential),
+                Err(Error::InvalidCredential)
+            );
+            assert_eq!(contract.get_credential(event_id, accounts.bob), None);
 
-            // Check if already refunded
-            if ticket.is_refunded {
-                return Err(Error::TicketAlreadyRefunded);
-            }
+            // Ungated events don't take credentials
+            assert_eq!(
+                contract.present_credential(open_id, credential),
+                Err(Error::InvalidInput)
+            );
+            let ticket_id = contract.buy_ticket(open_id).unwrap();
+            assert_eq!(contract.get_checked_credential(ticket_id), None);
+        }
 
-            // Check if ticket already used
-            if ticket.is_used {
-                return Err(Error::TicketAlreadyUsed);
-            }
+        #[ink::test]
+        fn private_events_need_the_access_key() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            // Get event
-            let mut event = self.events.get(ticket.event_id).ok_or(Error::EventNotFound)?;
+            let private = |listed| EventOptions {
+                access_hash: Some(access_key_hash(b"open sesame")),
+                listed,
+                ..Default::default()
+            };
+            let hidden_id = contract
+                .create_event_with_options(
+                    String::from("Secret Show"),
+                    1000,
+                    100,
+                    String::from("QmTest123"),
+                    private(false),
+                )
+                .unwrap();
+            let listed_id = contract
+                .create_event_with_options(
+                    String::from("Listed Show"),
+                    1000,
+                    100,
+                    String::from("QmTest456"),
+                    private(true),
+                )
+                .unwrap();
+            assert!(contract.is_private_event(hidden_id));
 
-            // Can't cancel ticket for cancelled event (use refund_ticket instead)
-            if event.cancelled {
-                return Err(Error::EventCancelled);
-            }
+            // Only listed events show up among the active events
+            let active: Vec<u64> = contract.get_active_events(0, 10).iter().map(|e| e.id).collect();
+            assert_eq!(active, vec![listed_id]);
 
-            // Can't cancel ticket for completed event
-            if event.completed {
-                return Err(Error::EventCompleted);
-            }
+            // Buyers need the key
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.buy_ticket(hidden_id), Err(Error::AccessKeyRequired));
+            assert_eq!(
+                contract.buy_ticket_private(hidden_id, b"guess".to_vec()),
+                Err(Error::InvalidAccessKey)
+            );
+            contract.buy_ticket_private(hidden_id, b"open sesame".to_vec()).unwrap();
 
-            // Mark ticket as refunded
-            ticket.is_refunded = true;
-            self.tickets.insert(ticket_id, &ticket);
+            // Once in, the buyer can use the other purchase paths
+            contract.buy_ticket(hidden_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.buy_ticket(hidden_id), Err(Error::AccessKeyRequired));
+        }
 
-            // Increase available tickets count
-            event.available_tickets = event.available_tickets.saturating_add(1);
-            self.events.insert(ticket.event_id, &event);
+        #[ink::test]
+        fn commit_reveal_sales_draw_among_reveals() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            // IMMEDIATELY remove ticket from owner's list (using BTreeSet for O(log n) removal)
-            let mut owner_ticket_set = self.owner_tickets.get(caller).unwrap_or_default();
-            owner_ticket_set.remove(&ticket_id);
-            self.owner_tickets.insert(caller, &owner_ticket_set);
+            // One ticket for three committers; the draw needs a committed seed
+            let phases = CommitReveal {
+                commit_end: 100,
+                reveal_end: 200,
+            };
+            let create = |contract: &mut TicketDot, raffle_seed_hash| {
+                contract.create_event_with_options(
+                    String::from("Test Event"),
+                    1000,
+                    1,
+                    String::from("QmTest123"),
+                    EventOptions {
+                        commit_reveal: Some(phases),
+                        raffle_seed_hash,
+                        ..Default::default()
+                    },
+                )
+            };
+            assert_eq!(create(&mut contract, None), Err(Error::InvalidInput));
+            let seed = Hash::from([7; 32]);
+            let event_id = create(&mut contract, Some(raffle_seed_hash(&seed))).unwrap();
+            assert_eq!(contract.get_commit_reveal(event_id), Some(phases));
 
-            // Transfer refund to ticket owner
-            let refund_amount = event.price;
-            if self.env().transfer(caller, refund_amount).is_err() {
-                return Err(Error::TransferFailed);
+            // Commitments respect paused sales like regular purchases
+            contract.pause_sales(event_id).unwrap();
+            assert_eq!(
+                contract.commit_purchase(event_id, Hash::from([0; 32])),
+                Err(Error::SalesPaused)
+            );
+            contract.resume_sales(event_id).unwrap();
+
+            // Regular sales wait for the draw
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::SaleNotOpen { opens_at: 200 }));
+
+            let salt = |account: AccountId| {
+                Hash::from(if account == accounts.bob { [1; 32] } else { [3; 32] })
+            };
+            for committer in [accounts.bob, accounts.charlie, accounts.django] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(committer);
+                contract
+                    .commit_purchase(event_id, purchase_commitment(&committer, &salt(committer)))
+                    .unwrap();
             }
+            assert_eq!(
+                contract.commit_purchase(event_id, Hash::from([0; 32])),
+                Err(Error::AlreadyEntered)
+            );
+            assert_eq!(
+                contract.reveal_purchase(event_id, salt(accounts.django)),
+                Err(Error::SaleNotOpen { opens_at: 100 })
+            );
 
-            // Emit event
-            self.env().emit_event(TicketCancelled {
-                ticket_id,
-                event_id: ticket.event_id,
-                owner: caller,
-                refund_amount,
-            });
+            // Bob and Charlie reveal into the lottery; Django never does
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(150);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.reveal_purchase(event_id, Hash::from([2; 32])),
+                Err(Error::CommitmentMismatch)
+            );
+            assert_eq!(contract.reveal_purchase(event_id, salt(accounts.bob)), Ok(0));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.reveal_purchase(event_id, salt(accounts.charlie)), Ok(1));
+            assert_eq!(contract.draw_commit_reveal(event_id, seed), Err(Error::RaffleStillOpen));
 
-            Ok(())
-        }
+            // The lottery picks one reveal for the single ticket
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(200);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(
+                contract.reveal_purchase(event_id, salt(accounts.django)),
+                Err(Error::PhaseClosed)
+            );
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::SaleNotOpen { opens_at: 200 }));
+            assert_eq!(
+                contract.draw_commit_reveal(event_id, Hash::from([8; 32])),
+                Err(Error::RaffleSeedMismatch)
+            );
+            contract.draw_commit_reveal(event_id, seed).unwrap();
+            assert_eq!(contract.get_commit_draw(event_id).unwrap().winners, 1);
 
-        /// Withdraw earnings from a completed event
-        /// Only the organizer can withdraw after event is marked as completed
-        /// 
-        /// # Security
-        /// - Only organizer can withdraw
-        /// - Event must be completed first
-        /// - Calculates earnings based on tickets sold
-        #[ink(message)]
-        pub fn withdraw_earnings(&mut self, event_id: u64) -> Result<(), Error> {
-            let caller = self.env().caller();
-            
-            // Get event
-            let event = self.events.get(event_id).ok_or(Error::EventNotFound)?;
-            
-            // Only organizer can withdraw
-            if caller != event.organizer {
-                return Err(Error::NotOrganizer);
-            }
-            
-            // Event must be completed
-            if !event.completed {
-                return Err(Error::EventNotCompleted);
-            }
-            
-            // Calculate earnings (tickets sold * price)
-            let tickets_sold = event.total_tickets.saturating_sub(event.available_tickets);
-            let earnings = event.price.saturating_mul(tickets_sold as u128);
-            
-            // Transfer earnings to organizer
-            if self.env().transfer(caller, earnings).is_err() {
-                return Err(Error::TransferFailed);
+            // Django's unrevealed deposit is forfeited, by anyone, to the platform
+            assert_eq!(contract.claim_commit_refund(event_id), Err(Error::CommitmentForfeited));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.forfeit_commitment(event_id, accounts.django), Ok(1000));
+            assert_eq!(contract.get_platform_fees(None), 1000);
+            assert_eq!(contract.get_purchase_commitment(event_id, accounts.django), None);
+            assert_eq!(
+                contract.forfeit_commitment(event_id, accounts.bob),
+                Err(Error::AlreadyEntered)
+            );
+
+            let mut tickets = 0;
+            for revealer in [accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(revealer);
+                match contract.claim_commit_ticket(event_id) {
+                    Ok(ticket_id) => {
+                        assert_eq!(contract.get_ticket(ticket_id).unwrap().owner, revealer);
+                        tickets += 1;
+                    }
+                    Err(error) => {
+                        assert_eq!(error, Error::NotRaffleWinner);
+                        assert_eq!(contract.claim_commit_refund(event_id), Ok(1000));
+                    }
+                }
+                assert_eq!(contract.get_purchase_commitment(event_id, revealer), None);
             }
-            
-            Ok(())
+            assert_eq!(tickets, 1);
+            assert_eq!(contract.buy_ticket(event_id), Err(Error::SoldOut));
         }
 
-        /// Get event details
-        #[ink(message)]
-        pub fn get_event(&self, event_id: u64) -> Option<Event> {
-            self.events.get(event_id)
-        }
+        #[ink::test]
+        fn seat_auctions_mint_to_the_highest_bidder() {
+            let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-        /// Get ticket details
-        #[ink(message)]
-        pub fn get_ticket(&self, ticket_id: u64) -> Option<Ticket> {
-            self.tickets.get(ticket_id)
-        }
+            let event_id = contract
+                .create_event(String::from("Test Event"), 1000, 4, String::from("QmTest123"))
+                .unwrap();
+            contract
+                .set_seat_layout(event_id, SeatLayout::Grid { rows: 2, seats_per_row: 2 })
+                .unwrap();
+            assert_eq!(
+                contract.create_seat_auction(event_id, 4, 5000, 100),
+                Err(Error::InvalidSeat)
+            );
+            contract.create_seat_auction(event_id, 0, 5000, 100).unwrap();
+            assert_eq!(contract.get_available_seats(event_id), vec![1, 2, 3]);
 
-        /// Get all tickets owned by an account
-        /// 
-        /// Returns only valid (non-refunded) tickets.
-        /// Refunded tickets are immediately removed from this list when cancelled/refunded,
-        /// so no runtime filtering is needed (O(1) lookup instead of O(n) iteration).
-        /// 
-        /// # Performance
-        /// - O(1) lookup using BTreeSet
-        /// - No iteration through refunded tickets
-        /// - Returns tickets sorted by ID
-        #[ink(message)]
-        pub fn get_my_tickets(&self, owner: AccountId) -> Vec<u64> {
-            // Since we immediately remove refunded tickets from owner_tickets,
-            // we can simply return the list without filtering
-            self.owner_tickets
-                .get(owner)
-                .unwrap_or_default()
-                .into_iter()
-                .collect()
-        }
+            // The seat can't be bought outright
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.buy_ticket_seat(event_id, 0), Err(Error::SeatTaken));
 
-        /// Get total number of events created
-        #[ink(message)]
-        pub fn get_event_count(&self) -> u64 {
-            self.event_counter
-        }
+            // Bids must clear the reserve, then beat the highest bid
+            assert_eq!(contract.bid(event_id, 0), Err(Error::BidTooLow { min_bid: 5000 }));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(5000);
+            contract.bid(event_id, 0).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.bid(event_id, 0), Err(Error::BidTooLow { min_bid: 5001 }));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(8000);
+            contract.bid(event_id, 0).unwrap();
+            let auction = contract.get_seat_auction(event_id, 0).unwrap();
+            assert_eq!(auction.highest_bidder, Some(accounts.charlie));
+            assert_eq!(auction.highest_bid, 8000);
+            assert_eq!(contract.get_bid_refund(accounts.bob), 5000);
 
-        /// Get total number of tickets minted
-        #[ink(message)]
-        pub fn get_ticket_count(&self) -> u64 {
-            self.ticket_counter
-        }
+            // Settling mints the seat to the winner at their bid
+            assert_eq!(contract.settle_auction(event_id, 0), Err(Error::AuctionNotEnded));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(contract.bid(event_id, 0), Err(Error::AuctionEnded));
+            let ticket_id = contract.settle_auction(event_id, 0).unwrap().unwrap();
+            let ticket = contract.get_ticket(ticket_id).unwrap();
+            assert_eq!(ticket.owner, accounts.charlie);
+            assert_eq!(ticket.seat_id, Some(0));
+            assert_eq!(ticket.price_paid, 8000);
+            assert_eq!(contract.settle_auction(event_id, 0), Err(Error::AuctionNotFound));
 
-        /// Get contract admin
-        #[ink(message)]
-        pub fn get_admin(&self) -> AccountId {
-            self.admin
-        }
-    }
+            // A winner who can't get the ticket is refunded
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.create_seat_auction(event_id, 1, 5000, 200).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(6000);
+            contract.bid(event_id, 1).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.block_attendee(event_id, accounts.bob).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(200);
+            assert_eq!(contract.settle_auction(event_id, 1), Ok(None));
+            assert_eq!(contract.get_available_seats(event_id), vec![1, 2, 3]);
+            contract.unblock_attendee(event_id, accounts.bob).unwrap();
 
-    /// Unit tests
-    #[cfg(test)]
-    mod tests {
-        use super::*;
+            // So is a winner who no longer meets the purchase restrictions
+            contract.create_seat_auction(event_id, 2, 5000, 300).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.bid(event_id, 2).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let terms = LoyaltyTerms {
+                min_points: 1,
+                redeem_points: 0,
+                discount_bps: 0,
+            };
+            contract.set_loyalty_terms(event_id, terms).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.bid(event_id, 2), Err(Error::InsufficientPoints));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(300);
+            assert_eq!(contract.settle_auction(event_id, 2), Ok(None));
+
+            // Refunds are pulled, so a failing transfer can't block bidding
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let bob_before =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob)
+                    .unwrap();
+            assert_eq!(contract.withdraw_bid_refunds(), Ok(17_000));
+            let bob_after =
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob)
+                    .unwrap();
+            assert_eq!(bob_after - bob_before, 17_000);
+            assert_eq!(contract.withdraw_bid_refunds(), Err(Error::NothingToWithdraw));
+        }
 
         #[ink::test]
-        fn create_event_works() {
+        fn offers_settle_with_royalties() {
             let mut contract = TicketDot::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
             let event_id = contract
-                .create_event(
+                .create_event_with_options(
                     String::from("Test Event"),
                     1000,
                     100,
                     String::from("QmTest123"),
+                    EventOptions {
+                        royalty_bps: 1_000,
+                        max_resale_multiplier_bps: 20_000,
+                        ..Default::default()
+                    },
                 )
                 .unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let ticket_id = contract.buy_ticket(event_id).unwrap();
 
-            assert_eq!(event_id, 0);
-            let event = contract.get_event(event_id).unwrap();
-            assert_eq!(event.name, "Test Event");
-            assert_eq!(event.available_tickets, 100);
+            // Offers above the resale cap are rejected
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2500);
+            assert_eq!(contract.make_offer(ticket_id), Err(Error::ResalePriceTooHigh));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1500);
+            contract.make_offer(ticket_id).unwrap();
+            assert_eq!(contract.get_offer(ticket_id, accounts.bob).unwrap().amount, 1500);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1800);
+            contract.make_offer(ticket_id).unwrap();
+
+            // Charlie withdraws; Bob's offer is still open
+            assert_eq!(contract.cancel_offer(ticket_id), Ok(1800));
+            assert_eq!(contract.cancel_offer(ticket_id), Err(Error::OfferNotFound));
+            assert_eq!(
+                contract.accept_offer(ticket_id, accounts.bob),
+                Err(Error::NotTicketOwner)
+            );
+
+            // The holder accepts without ever listing the ticket
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                contract.accept_offer(ticket_id, accounts.charlie),
+                Err(Error::OfferNotFound)
+            );
+            contract.accept_offer(ticket_id, accounts.bob).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().owner, accounts.bob);
+            assert_eq!(contract.get_offer(ticket_id, accounts.bob), None);
+            assert_eq!(contract.escrow_balance.get(event_id), Some(1150));
         }
 
         #[ink::test]
-        fn buy_ticket_works() {
+        fn raffle_refunds_entrants_if_never_drawn() {
             let mut contract = TicketDot::new();
-            
-            // Create event
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let seed = Hash::from([7; 32]);
             let event_id = contract
-                .create_event(
+                .create_event_with_options(
                     String::from("Test Event"),
                     1000,
-                    100,
+                    2,
                     String::from("QmTest123"),
+                    EventOptions {
+                        raffle_end: 100,
+                        raffle_seed_hash: Some(raffle_seed_hash(&seed)),
+                        ..Default::default()
+                    },
                 )
                 .unwrap();
-
-            // Buy ticket
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
             ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
-            let ticket_id = contract.buy_ticket(event_id).unwrap();
+            contract.enter_raffle(event_id).unwrap();
 
-            assert_eq!(ticket_id, 0);
-            let ticket = contract.get_ticket(ticket_id).unwrap();
-            assert_eq!(ticket.event_id, event_id);
+            // Until the draw period lapses, entrants wait for the draw
+            let lapsed = 100 + RAFFLE_DRAW_PERIOD;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(lapsed - 1);
+            assert_eq!(contract.claim_raffle_refund(event_id), Err(Error::RaffleNotDrawn));
+
+            // A seed held back past the period can't be drawn with any more
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(lapsed);
+            assert_eq!(contract.draw_raffle(event_id, seed), Err(Error::RaffleClosed));
+            assert_eq!(contract.claim_raffle_refund(event_id), Ok(1000));
         }
 
         #[ink::test]
-        fn transfer_ticket_works() {
+        #[should_panic(expected = "off-chain environment does not support contract invocation")]
+        fn proof_purchases_check_the_token_gate() {
             let mut contract = TicketDot::new();
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            
-            // Create event and buy ticket
+
             let event_id = contract
-                .create_event(
+                .create_event_with_options(
                     String::from("Test Event"),
                     1000,
                     100,
                     String::from("QmTest123"),
+                    EventOptions {
+                        presale_end: 100,
+                        token_gate: Some(TokenGate::Psp34 { collection: accounts.eve }),
+                        ..Default::default()
+                    },
                 )
                 .unwrap();
+            contract.set_allowlist_root(event_id, Some(merkle_leaf(&accounts.bob))).unwrap();
 
+            // A proof holder still has to hold the token. Off-chain the
+            // collection can't be queried, so reaching the balance query
+            // (instead of minting) is what shows the gate is checked.
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
             ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
-            let ticket_id = contract.buy_ticket(event_id).unwrap();
-
-            // Transfer ticket
-            contract.transfer_ticket(ticket_id, accounts.bob).unwrap();
-            
-            let ticket = contract.get_ticket(ticket_id).unwrap();
-            assert_eq!(ticket.owner, accounts.bob);
+            let _ = contract.buy_ticket_with_proof(event_id, Vec::new());
         }
 
         #[ink::test]
-        fn cancel_event_works() {
+        fn proof_purchases_need_loyalty_points() {
             let mut contract = TicketDot::new();
-            
-            // Create event
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
             let event_id = contract
-                .create_event(
+                .create_event_with_options(
                     String::from("Test Event"),
                     1000,
                     100,
                     String::from("QmTest123"),
+                    EventOptions { presale_end: 100, ..Default::default() },
                 )
                 .unwrap();
+            let terms = LoyaltyTerms {
+                min_points: 15,
+                redeem_points: 10,
+                discount_bps: 5_000,
+            };
+            contract.set_loyalty_terms(event_id, terms).unwrap();
+            contract.set_allowlist_root(event_id, Some(merkle_leaf(&accounts.bob))).unwrap();
 
-            // Cancel event
-            contract.cancel_event(event_id).unwrap();
-            
-            let event = contract.get_event(event_id).unwrap();
-            assert!(event.cancelled);
-            assert!(!event.active);
+            // A valid proof doesn't replace the event's points requirement
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(
+                contract.buy_ticket_with_proof(event_id, Vec::new()),
+                Err(Error::InsufficientPoints)
+            );
         }
 
         #[ink::test]
-        fn complete_event_works() {
+        fn proof_purchases_need_a_credential() {
             let mut contract = TicketDot::new();
-            
-            // Create event
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
             let event_id = contract
-                .create_event(
+                .create_event_with_options(
                     String::from("Test Event"),
                     1000,
                     100,
                     String::from("QmTest123"),
+                    EventOptions {
+                        presale_end: 100,
+                        credential_attester: Some(accounts.eve),
+                        ..Default::default()
+                    },
                 )
                 .unwrap();
+            // Single-leaf tree: Bob's leaf is the root
+            contract.set_allowlist_root(event_id, Some(merkle_leaf(&accounts.bob))).unwrap();
 
-            // Complete event
-            contract.complete_event(event_id).unwrap();
-            
-            let event = contract.get_event(event_id).unwrap();
-            assert!(event.completed);
-            assert!(!event.active);
+            // A valid proof doesn't replace the event's credential gate
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(
+                contract.buy_ticket_with_proof(event_id, Vec::new()),
+                Err(Error::CredentialRequired)
+            );
         }
 
         #[ink::test]
-        fn cancel_ticket_works() {
+        fn subscribe_and_unsubscribe_works() {
             let mut contract = TicketDot::new();
-            
-            // Create event
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let endpoint = Hash::from([7u8; 32]);
+
+            // Can't subscribe to a missing event
+            assert_eq!(
+                contract.subscribe(SubscriptionTarget::Event(0), endpoint),
+                Err(Error::EventNotFound)
+            );
+
             let event_id = contract
                 .create_event(
                     String::from("Test Event"),
@@ -907,24 +15087,17 @@ mod ticketdot {
                 )
                 .unwrap();
 
-            // Buy ticket
-            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
-            let ticket_id = contract.buy_ticket(event_id).unwrap();
-
-            // Check event has 99 available tickets
-            let event_before = contract.get_event(event_id).unwrap();
-            assert_eq!(event_before.available_tickets, 99);
-
-            // Cancel ticket
-            contract.cancel_ticket(ticket_id).unwrap();
-            
-            // Check ticket is refunded
-            let ticket = contract.get_ticket(ticket_id).unwrap();
-            assert!(ticket.is_refunded);
+            let target = SubscriptionTarget::Event(event_id);
+            contract.subscribe(target, endpoint).unwrap();
+            contract
+                .subscribe(SubscriptionTarget::Organizer(accounts.alice), endpoint)
+                .unwrap();
+            assert_eq!(contract.get_subscription(accounts.alice, target), Some(endpoint));
 
-            // Check event now has 100 available tickets again
-            let event_after = contract.get_event(event_id).unwrap();
-            assert_eq!(event_after.available_tickets, 100);
+            // Unsubscribe removes the entry
+            contract.unsubscribe(target).unwrap();
+            assert_eq!(contract.get_subscription(accounts.alice, target), None);
+            assert_eq!(contract.unsubscribe(target), Err(Error::SubscriptionNotFound));
         }
     }
 }
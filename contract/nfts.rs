@@ -0,0 +1,59 @@
+use ink::env::{DefaultEnvironment, Environment};
+use ink::primitives::AccountId;
+
+/// Bridge to the runtime's `pallet-nfts` (or Uniques) collection
+///
+/// The runtime registers this under extension ID 1 and maps `item` onto
+/// its own item ID type. Tickets mirrored through it are read-only copies:
+/// the contract keeps deciding validity and moves or burns the mirror
+/// whenever the ticket itself is transferred or refunded.
+#[ink::chain_extension(extension = 1)]
+pub trait PalletNfts {
+    type ErrorCode = NftsError;
+
+    /// Mint `item` in `collection` to `owner`
+    #[ink(function = 1)]
+    fn mint(collection: u32, item: u64, owner: AccountId) -> Result<(), NftsError>;
+
+    /// Move `item` in `collection` to `to`
+    #[ink(function = 2)]
+    fn transfer(collection: u32, item: u64, to: AccountId) -> Result<(), NftsError>;
+
+    /// Destroy `item` in `collection`
+    #[ink(function = 3)]
+    fn burn(collection: u32, item: u64) -> Result<(), NftsError>;
+}
+
+/// Error returned by the `pallet-nfts` chain extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum NftsError {
+    /// The runtime rejected the call, e.g. unknown collection or missing permission
+    Failed,
+}
+
+impl ink::env::chain_extension::FromStatusCode for NftsError {
+    fn from_status_code(status_code: u32) -> Result<(), Self> {
+        match status_code {
+            0 => Ok(()),
+            _ => Err(Self::Failed),
+        }
+    }
+}
+
+/// `DefaultEnvironment` with the `pallet-nfts` chain extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum TicketDotEnvironment {}
+
+impl Environment for TicketDotEnvironment {
+    const MAX_EVENT_TOPICS: usize = <DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+    type AccountId = <DefaultEnvironment as Environment>::AccountId;
+    type Balance = <DefaultEnvironment as Environment>::Balance;
+    type Hash = <DefaultEnvironment as Environment>::Hash;
+    type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+    type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+
+    type ChainExtension = PalletNfts;
+}
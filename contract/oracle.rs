@@ -0,0 +1,24 @@
+use ink::env::call::{build_call, ExecutionInput, Selector};
+use ink::env::DefaultEnvironment;
+use ink::primitives::AccountId;
+
+type Balance = <DefaultEnvironment as ink::env::Environment>::Balance;
+
+/// Call `PriceOracle::latest_price` on `oracle`, returning `None` if the call fails
+///
+/// The oracle answers with the native token price of one US dollar, in
+/// the token's smallest unit, and the millisecond timestamp it was last
+/// updated at.
+pub fn latest_price(oracle: AccountId) -> Option<(Balance, u64)> {
+    let result = build_call::<DefaultEnvironment>()
+        .call(oracle)
+        .exec_input(ExecutionInput::new(Selector::new(ink::selector_bytes!(
+            "PriceOracle::latest_price"
+        ))))
+        .returns::<(Balance, u64)>()
+        .try_invoke();
+    match result {
+        Ok(Ok(price)) => Some(price),
+        _ => None,
+    }
+}
@@ -0,0 +1,25 @@
+use ink::env::call::{build_call, ExecutionInput, Selector};
+use ink::env::DefaultEnvironment;
+use ink::primitives::{AccountId, Hash};
+
+/// Call `PersonhoodRegistry::person_of` on `registry`, returning `None` if
+/// `account` isn't attested or the call fails
+///
+/// The registry answers with a stable identifier for the human behind
+/// `account`, shared by every wallet they've linked to their attestation.
+pub fn person_of(registry: AccountId, account: AccountId) -> Option<Hash> {
+    let result = build_call::<DefaultEnvironment>()
+        .call(registry)
+        .exec_input(
+            ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                "PersonhoodRegistry::person_of"
+            )))
+            .push_arg(account),
+        )
+        .returns::<Option<Hash>>()
+        .try_invoke();
+    match result {
+        Ok(Ok(person)) => person,
+        _ => None,
+    }
+}
@@ -0,0 +1,71 @@
+use ink::env::call::{build_call, ExecutionInput, Selector};
+use ink::env::DefaultEnvironment;
+use ink::prelude::string::String;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+type Balance = <DefaultEnvironment as ink::env::Environment>::Balance;
+
+/// Error type defined by the PSP22 standard
+///
+/// Only needed to decode the result of calls into PSP22 tokens.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+    Custom(String),
+    InsufficientBalance,
+    InsufficientAllowance,
+    ZeroRecipientAddress,
+    ZeroSenderAddress,
+    SafeTransferCheckFailed(String),
+}
+
+/// Call `PSP22::transfer_from` on `token`, returning whether it succeeded
+///
+/// The caller of this function (the TicketDot contract) must have been
+/// approved by `from` for at least `value`.
+pub fn transfer_from(token: AccountId, from: AccountId, to: AccountId, value: Balance) -> bool {
+    let result = build_call::<DefaultEnvironment>()
+        .call(token)
+        .exec_input(
+            ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer_from")))
+                .push_arg(from)
+                .push_arg(to)
+                .push_arg(value)
+                .push_arg(Vec::<u8>::new()),
+        )
+        .returns::<Result<(), PSP22Error>>()
+        .try_invoke();
+    matches!(result, Ok(Ok(Ok(()))))
+}
+
+/// Call `PSP22::transfer` on `token`, returning whether it succeeded
+pub fn transfer(token: AccountId, to: AccountId, value: Balance) -> bool {
+    let result = build_call::<DefaultEnvironment>()
+        .call(token)
+        .exec_input(
+            ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer")))
+                .push_arg(to)
+                .push_arg(value)
+                .push_arg(Vec::<u8>::new()),
+        )
+        .returns::<Result<(), PSP22Error>>()
+        .try_invoke();
+    matches!(result, Ok(Ok(Ok(()))))
+}
+
+/// Call `PSP22::balance_of` on `token`, returning 0 if the call fails
+pub fn balance_of(token: AccountId, owner: AccountId) -> Balance {
+    let result = build_call::<DefaultEnvironment>()
+        .call(token)
+        .exec_input(
+            ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::balance_of")))
+                .push_arg(owner),
+        )
+        .returns::<Balance>()
+        .try_invoke();
+    match result {
+        Ok(Ok(balance)) => balance,
+        _ => 0,
+    }
+}
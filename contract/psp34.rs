@@ -0,0 +1,101 @@
+use ink::env::call::{build_call, ExecutionInput, Selector};
+use ink::env::DefaultEnvironment;
+use ink::prelude::string::String;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// Token identifier as defined by the PSP34 standard
+///
+/// TicketDot tickets are always identified by `Id::U64(ticket_id)`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub enum Id {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Bytes(Vec<u8>),
+}
+
+/// Error type defined by the PSP34 standard
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP34Error {
+    /// Custom error type for implementation-based errors
+    Custom(String),
+    /// Returned when an owner approves themselves
+    SelfApprove,
+    /// Returned when the caller is not allowed to move the token
+    NotApproved,
+    /// Returned when the token already exists
+    TokenExists,
+    /// Returned when the token does not exist
+    TokenNotExists,
+    /// Returned when the receiver rejected the transfer
+    SafeTransferCheckFailed(String),
+}
+
+/// The PSP34 NFT standard interface
+///
+/// Message selectors are derived from `PSP34::<message>`, matching the
+/// standard so wallets and marketplaces can call the contract directly.
+#[ink::trait_definition]
+pub trait PSP34 {
+    /// Returns the collection ID
+    #[ink(message)]
+    fn collection_id(&self) -> Id;
+
+    /// Returns the number of tokens owned by `owner`
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u32;
+
+    /// Returns the owner of token `id`, if it exists
+    #[ink(message)]
+    fn owner_of(&self, id: Id) -> Option<AccountId>;
+
+    /// Returns whether `operator` may move `id` (or every token if `None`) for `owner`
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool;
+
+    /// Grants or revokes `operator` permission to move `id` (or every token if `None`)
+    #[ink(message)]
+    fn approve(
+        &mut self,
+        operator: AccountId,
+        id: Option<Id>,
+        approved: bool,
+    ) -> Result<(), PSP34Error>;
+
+    /// Transfers token `id` from its owner to `to`
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, id: Id, data: Vec<u8>) -> Result<(), PSP34Error>;
+
+    /// Returns the number of tokens in circulation
+    #[ink(message)]
+    fn total_supply(&self) -> u128;
+}
+
+/// Call `PSP34::balance_of` on another `collection`, returning 0 if the call fails
+pub fn balance_of(collection: AccountId, owner: AccountId) -> u32 {
+    let result = build_call::<DefaultEnvironment>()
+        .call(collection)
+        .exec_input(
+            ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP34::balance_of")))
+                .push_arg(owner),
+        )
+        .returns::<u32>()
+        .try_invoke();
+    match result {
+        Ok(Ok(balance)) => balance,
+        _ => 0,
+    }
+}
+
+/// The PSP34 Metadata extension
+#[ink::trait_definition]
+pub trait PSP34Metadata {
+    /// Returns attribute `key` of token `id`, if set
+    #[ink(message)]
+    fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>>;
+}
@@ -0,0 +1,79 @@
+use ink::env::DefaultEnvironment;
+use ink::primitives::AccountId;
+
+use crate::ticketdot::{Error, Event, Ticket, TicketStatus};
+
+type Balance = <DefaultEnvironment as ink::env::Environment>::Balance;
+
+/// The core TicketDot interface for other contracts
+///
+/// Marketplaces, gating contracts, and aggregators can call TicketDot
+/// through `contract_ref!(TicketDotCore)` instead of copying selectors by
+/// hand. Message selectors are derived from `TicketDotCore::<message>`.
+#[ink::trait_definition]
+pub trait TicketDotCore {
+    /// Get event details
+    #[ink(message)]
+    fn get_event(&self, event_id: u64) -> Option<Event>;
+
+    /// Get ticket details
+    #[ink(message)]
+    fn get_ticket(&self, ticket_id: u64) -> Option<Ticket>;
+
+    /// Get the base ticket price an event charges right now
+    ///
+    /// Follows the event's price phases and bonding curve, so this is the
+    /// price of the next ticket sold; tiers keep their own fixed prices.
+    #[ink(message)]
+    fn get_current_price(&self, event_id: u64) -> Option<Balance>;
+
+    /// Check whether a ticket can be checked in right now
+    ///
+    /// Applies the same rules as `use_ticket`, in the same order, so gate
+    /// scanners can validate a ticket in a single read.
+    #[ink(message)]
+    fn verify_ticket(&self, ticket_id: u64) -> TicketStatus;
+
+    /// Buy a ticket for an event
+    ///
+    /// This function mints an NFT ticket and transfers it to the buyer.
+    /// Payment is handled via the payable mechanism, or pulled with PSP22
+    /// `transfer_from` for token-priced events.
+    ///
+    /// # Security
+    /// - Payment must cover the current ticket price; any overpayment is
+    ///   refunded to the buyer
+    /// - Payment is held in escrow until the organizer withdraws earnings,
+    ///   so cancelled events can be refunded
+    /// - Ticket is minted as NFT owned by buyer
+    /// - Enforces maximum tickets per user to prevent DoS
+    ///
+    /// # Arguments
+    /// * `event_id` - ID of the event to buy ticket for
+    ///
+    /// # Returns
+    /// - `Ok(ticket_id)` - The ID of the newly minted ticket
+    /// - `Err(Error::EventNotFound)` - Event doesn't exist
+    /// - `Err(Error::InsufficientPayment)` - Payment below the ticket price
+    /// - `Err(Error::SoldOut)` - No tickets available
+    /// - `Err(Error::EventNotActive)` - Event is not active
+    /// - `Err(Error::TransferFailed)` - Refunding the overpayment failed
+    /// - `Err(Error::TooManyTickets)` - User has reached ticket limit
+    #[ink(message, payable)]
+    fn buy_ticket(&mut self, event_id: u64) -> Result<u64, Error>;
+
+    /// Transfer ticket to another user
+    ///
+    /// The caller must own the ticket or be an approved operator
+    /// (see `approve_ticket` and `set_approval_for_all`).
+    ///
+    /// # Arguments
+    /// * `ticket_id` - ID of the ticket to transfer
+    /// * `to` - Account ID of the recipient
+    #[ink(message)]
+    fn transfer_ticket(&mut self, ticket_id: u64, to: AccountId) -> Result<(), Error>;
+
+    /// Mark ticket as used (called by event organizer, check-in staff, or an admin)
+    #[ink(message)]
+    fn use_ticket(&mut self, ticket_id: u64) -> Result<(), Error>;
+}
@@ -48,7 +48,7 @@ const EventDetails = () => {
       }) as any;
 
       // Get event details (camelCase as Polkadot.js converts it)
-      const { result, output } = await contract.query.getEvent(
+      const { result, output } = await contract.query["ticketDotCore::getEvent"](
         contract.address.toString(),
         { gasLimit, storageDepositLimit: null },
         parseInt(id)
@@ -65,14 +65,9 @@ const EventDetails = () => {
             totalTickets: eventData.Ok.totalTickets,
             availableTickets: eventData.Ok.availableTickets,
             metadataCid: eventData.Ok.metadataCid,
-            active:
-              eventData.Ok.active === true || eventData.Ok.active === "true",
-            cancelled:
-              eventData.Ok.cancelled === true ||
-              eventData.Ok.cancelled === "true",
-            completed:
-              eventData.Ok.completed === true ||
-              eventData.Ok.completed === "true",
+            active: eventData.Ok.status === "OnSale",
+            cancelled: eventData.Ok.status === "Cancelled",
+            completed: eventData.Ok.status === "Completed",
           });
         }
       }
@@ -124,7 +119,7 @@ const EventDetails = () => {
       const priceValue = BigInt(event.price.replace(/,/g, ""));
 
       // Call buyTicket with exact payment amount (camelCase as Polkadot.js converts it)
-      const tx = await contract.tx.buyTicket(
+      const tx = await contract.tx["ticketDotCore::buyTicket"](
         {
           gasLimit,
           storageDepositLimit: null,
@@ -274,7 +269,7 @@ const EventDetails = () => {
       const injector = await web3FromAddress(selectedAccount.address);
 
       // Use camelCase function name as Polkadot.js converts it
-      const tx = await contract.tx.useTicket(
+      const tx = await contract.tx["ticketDotCore::useTicket"](
         { gasLimit, storageDepositLimit: null },
         parseInt(ticketIdToUse)
       );
@@ -63,7 +63,7 @@ const Home = () => {
         const loadedEvents: Event[] = [];
         for (let i = 0; i < eventCount; i++) {
           const { result: eventResult, output: eventOutput } =
-            await contract.query.getEvent(
+            await contract.query["ticketDotCore::getEvent"](
               contract.address.toString(),
               { gasLimit, storageDepositLimit: null },
               i
@@ -82,7 +82,7 @@ const Home = () => {
                 totalTickets: Number(event.totalTickets) || 0,
                 availableTickets: Number(event.availableTickets) || 0,
                 metadataCid: event.metadataCid || "",
-                active: event.active === true || event.active === "true",
+                active: event.status === "OnSale",
               });
             }
           }
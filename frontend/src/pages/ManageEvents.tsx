@@ -63,7 +63,7 @@ const ManageEvents = () => {
         const myEvents: Event[] = [];
         for (let i = 0; i < eventCount; i++) {
           const { result: eventResult, output: eventOutput } =
-            await contract.query.getEvent(
+            await contract.query["ticketDotCore::getEvent"](
               contract.address.toString(),
               { gasLimit, storageDepositLimit: null },
               i
@@ -84,11 +84,9 @@ const ManageEvents = () => {
                   totalTickets: Number(event.totalTickets) || 0,
                   availableTickets: Number(event.availableTickets) || 0,
                   metadataCid: event.metadataCid || "",
-                  active: event.active === true || event.active === "true",
-                  cancelled:
-                    event.cancelled === true || event.cancelled === "true",
-                  completed:
-                    event.completed === true || event.completed === "true",
+                  active: event.status === "OnSale",
+                  cancelled: event.status === "Cancelled",
+                  completed: event.status === "Completed",
                 });
               }
             }
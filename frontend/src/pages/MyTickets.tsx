@@ -71,7 +71,7 @@ const MyTickets = () => {
               : ticketId;
 
           const { result: ticketResult, output: ticketOutput } =
-            await contract.query.getTicket(
+            await contract.query["ticketDotCore::getTicket"](
               selectedAccount.address,
               { gasLimit, storageDepositLimit: null },
               parsedTicketId
@@ -89,7 +89,7 @@ const MyTickets = () => {
                   : ticket.eventId;
 
               const { result: eventResult, output: eventOutput } =
-                await contract.query.getEvent(
+                await contract.query["ticketDotCore::getEvent"](
                   selectedAccount.address,
                   { gasLimit, storageDepositLimit: null },
                   eventId
@@ -119,7 +119,9 @@ const MyTickets = () => {
                 eventId: eventId,
                 owner: ticket.owner,
                 purchaseTime: purchaseTimeNum,
-                isUsed: ticket.isUsed === true || ticket.isUsed === "true",
+                // Checked in at least once (multi-entry tickets may have uses left)
+                isUsed:
+                  parseInt(ticket.timesUsed.toString().replace(/,/g, "")) > 0,
                 isRefunded:
                   ticket.isRefunded === true || ticket.isRefunded === "true",
                 eventName,
@@ -157,7 +159,7 @@ const MyTickets = () => {
 
       const injector = await web3FromAddress(selectedAccount.address);
 
-      const tx = await contract.tx.transferTicket(
+      const tx = await contract.tx["ticketDotCore::transferTicket"](
         { gasLimit, storageDepositLimit: null },
         selectedTicketId,
         transferAddress